@@ -8,7 +8,7 @@
 // except according to those terms.
 
 use crate::approxeq::ApproxEq;
-use crate::trig::Trig;
+use crate::trig::{self, Trig};
 use core::cmp::{Eq, PartialEq};
 use core::hash::Hash;
 use core::iter::Sum;
@@ -27,7 +27,7 @@ pub struct Angle<T> {
 
 impl<T> Angle<T> {
     #[inline]
-    pub fn radians(radians: T) -> Self {
+    pub const fn radians(radians: T) -> Self {
         Angle { radians }
     }
 
@@ -107,7 +107,7 @@ where
 {
     /// Returns (sin(self), cos(self)).
     pub fn sin_cos(self) -> (T, T) {
-        self.radians.sin_cos()
+        trig::sin_cos(self.radians)
     }
 
     /// Returns true if the angle is a finite number.
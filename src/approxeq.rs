@@ -40,3 +40,16 @@ macro_rules! approx_eq {
 
 approx_eq!(f32, 1.0e-6);
 approx_eq!(f64, 1.0e-6);
+
+// The epsilon is larger than for the other types since `f16` only has 11 bits of precision.
+#[cfg(feature = "half")]
+impl ApproxEq<half::f16> for half::f16 {
+    #[inline]
+    fn approx_epsilon() -> half::f16 {
+        half::f16::from_f32_const(1.0e-3)
+    }
+    #[inline]
+    fn approx_eq_eps(&self, other: &half::f16, approx_epsilon: &half::f16) -> bool {
+        num_traits::Float::abs(self.to_f32() - other.to_f32()) < approx_epsilon.to_f32()
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! App units, the fixed-point scalar used by layout.
+//!
+//! An app unit is a 60th of a CSS pixel, which lets common fractions of pixels be
+//! represented exactly while keeping integer arithmetic. `Au` can be used as the scalar
+//! of any geometry type, for example `Rect<Au, CssPx>`.
+//!
+//! Numeric casts (`NumCast`) preserve the number of app units. To convert typed geometry
+//! to floating point pixels, cast it and multiply it by [`au_to_px`]:
+//!
+//! ```rust
+//! use euclid::{point2, rect, Rect};
+//! use euclid::approxeq::ApproxEq;
+//! use euclid::au::{self, Au};
+//!
+//! enum CssPx {}
+//!
+//! let r: Rect<Au, CssPx> = rect(Au::from_px(1), Au(30), Au::from_px(10), Au::from_px(2));
+//! let px = r.cast::<f32>() * au::au_to_px::<CssPx, CssPx>();
+//! assert!(px.origin.approx_eq(&point2(1.0, 0.5)));
+//! assert!(px.max().approx_eq(&point2(11.0, 2.5)));
+//! ```
+
+use crate::scale::Scale;
+use core::cmp::Ordering;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use num_traits::{NumCast, ToPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The number of app units in a CSS pixel.
+pub const AU_PER_PX: i32 = 60;
+
+/// A length in app units, a 60th of a CSS pixel.
+///
+/// The arithmetic operators saturate instead of overflowing.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Au(pub i32);
+
+impl Au {
+    /// The largest representable length.
+    pub const MAX: Au = Au(i32::MAX);
+    /// The smallest representable length.
+    pub const MIN: Au = Au(i32::MIN);
+
+    /// Creates a length from a whole number of pixels, saturating on overflow.
+    #[inline]
+    pub const fn from_px(px: i32) -> Au {
+        Au(px.saturating_mul(AU_PER_PX))
+    }
+
+    /// Creates a length from floating point pixels, rounding to the nearest app unit
+    /// and saturating on overflow.
+    #[inline]
+    pub fn from_f32_px(px: f32) -> Au {
+        Au(crate::num::Round::round(px * AU_PER_PX as f32) as i32)
+    }
+
+    /// Returns the length in whole pixels, rounding towards negative infinity.
+    #[inline]
+    pub fn to_px(self) -> i32 {
+        self.0.div_euclid(AU_PER_PX)
+    }
+
+    /// Returns the length in floating point pixels.
+    #[inline]
+    pub fn to_f32_px(self) -> f32 {
+        self.0 as f32 / AU_PER_PX as f32
+    }
+}
+
+/// Returns the scale converting app units to floating point pixels.
+#[inline]
+pub fn au_to_px<Src, Dst>() -> Scale<f32, Src, Dst> {
+    Scale::new(1.0 / AU_PER_PX as f32)
+}
+
+/// Returns the scale converting floating point pixels to app units.
+#[inline]
+pub fn px_to_au<Src, Dst>() -> Scale<f32, Src, Dst> {
+    Scale::new(AU_PER_PX as f32)
+}
+
+impl Add for Au {
+    type Output = Au;
+    #[inline]
+    fn add(self, other: Au) -> Au {
+        Au(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for Au {
+    type Output = Au;
+    #[inline]
+    fn sub(self, other: Au) -> Au {
+        Au(self.0.saturating_sub(other.0))
+    }
+}
+
+impl AddAssign for Au {
+    #[inline]
+    fn add_assign(&mut self, other: Au) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Au {
+    #[inline]
+    fn sub_assign(&mut self, other: Au) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Au {
+    type Output = Au;
+    #[inline]
+    fn neg(self) -> Au {
+        Au(self.0.saturating_neg())
+    }
+}
+
+impl Mul<i32> for Au {
+    type Output = Au;
+    #[inline]
+    fn mul(self, scale: i32) -> Au {
+        Au(self.0.saturating_mul(scale))
+    }
+}
+
+/// Dividing `Au::MIN` by -1 saturates to `Au::MAX`. Dividing by zero saturates to
+/// `Au::MAX` or `Au::MIN` depending on the sign of the length, like floating point
+/// infinities, and a zero length stays zero.
+impl Div<i32> for Au {
+    type Output = Au;
+    #[inline]
+    fn div(self, scale: i32) -> Au {
+        match self.0.checked_div(scale) {
+            Some(v) => Au(v),
+            None if scale != 0 => Au::MAX,
+            None => match self.0.cmp(&0) {
+                Ordering::Less => Au::MIN,
+                Ordering::Equal => Au(0),
+                Ordering::Greater => Au::MAX,
+            },
+        }
+    }
+}
+
+impl num_traits::Zero for Au {
+    #[inline]
+    fn zero() -> Au {
+        Au(0)
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl ToPrimitive for Au {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        Some(self.0 as i64)
+    }
+
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+
+    #[inline]
+    fn to_f32(&self) -> Option<f32> {
+        Some(self.0 as f32)
+    }
+
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.0 as f64)
+    }
+}
+
+impl NumCast for Au {
+    #[inline]
+    fn from<T: ToPrimitive>(n: T) -> Option<Au> {
+        n.to_i32().map(Au)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default::{Rect, Size2D};
+    use crate::{point2, size2};
+
+    #[test]
+    fn test_px_conversions() {
+        assert_eq!(Au::from_px(2), Au(120));
+        assert_eq!(Au::from_f32_px(1.5), Au(90));
+        assert_eq!(Au::from_f32_px(-0.01), Au(-1));
+        assert_eq!(Au::from_f32_px(1e30), Au::MAX);
+        assert_eq!(Au(-1).to_px(), -1);
+        assert_eq!(Au(119).to_px(), 1);
+        assert_eq!(Au(30).to_f32_px(), 0.5);
+    }
+
+    #[test]
+    fn test_saturating_ops() {
+        assert_eq!(Au::MAX + Au(1), Au::MAX);
+        assert_eq!(Au::MIN - Au(1), Au::MIN);
+        assert_eq!(-Au::MIN, Au::MAX);
+        assert_eq!(Au::MAX * 2, Au::MAX);
+        assert_eq!(Au(120) / 2, Au(60));
+        assert_eq!(Au(-120) / -2, Au(60));
+        assert_eq!(Au::MIN / -1, Au::MAX);
+        assert_eq!(Au(5) / 0, Au::MAX);
+        assert_eq!(Au(-5) / 0, Au::MIN);
+        assert_eq!(Au(0) / 0, Au(0));
+    }
+
+    #[test]
+    fn test_geometry() {
+        let a = Rect::new(point2(Au(0), Au(0)), size2(Au(60), Au(60)));
+        let b = Rect::new(point2(Au(30), Au(-30)), size2(Au(60), Au(60)));
+        assert_eq!(a.intersection(&b), Some(Rect::new(point2(Au(30), Au(0)), size2(Au(30), Au(30)))));
+        assert_eq!(a.union(&b).size, size2(Au(90), Au(90)));
+        assert_eq!(Size2D::<Au>::zero().cast::<i32>(), size2(0, 0));
+        assert_eq!(a.round_out(), a);
+    }
+}
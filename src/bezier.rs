@@ -0,0 +1,440 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::box2d::Box2D;
+use crate::point::Point2D;
+use crate::segment::LineSegment2D;
+use crate::vector::Vector2D;
+
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+/// The maximum number of subdivisions when measuring lengths, which bounds the work
+/// done for degenerate curves and tolerances that are too small for the precision of
+/// the scalar type.
+const MAX_DEPTH: u32 = 24;
+
+/// A 2d cubic Bézier curve, optionally tagged with a unit.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct CubicBezier<T, U> {
+    pub from: Point2D<T, U>,
+    pub ctrl1: Point2D<T, U>,
+    pub ctrl2: Point2D<T, U>,
+    pub to: Point2D<T, U>,
+}
+
+impl<T: Copy, U> Copy for CubicBezier<T, U> {}
+
+impl<T: Clone, U> Clone for CubicBezier<T, U> {
+    fn clone(&self) -> Self {
+        CubicBezier {
+            from: self.from.clone(),
+            ctrl1: self.ctrl1.clone(),
+            ctrl2: self.ctrl2.clone(),
+            to: self.to.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for CubicBezier<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.from == other.from
+            && self.ctrl1 == other.ctrl1
+            && self.ctrl2 == other.ctrl2
+            && self.to == other.to
+    }
+}
+
+impl<T: Eq, U> Eq for CubicBezier<T, U> {}
+
+impl<T: Hash, U> Hash for CubicBezier<T, U> {
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        self.from.hash(h);
+        self.ctrl1.hash(h);
+        self.ctrl2.hash(h);
+        self.to.hash(h);
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for CubicBezier<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CubicBezier")
+            .field(&self.from)
+            .field(&self.ctrl1)
+            .field(&self.ctrl2)
+            .field(&self.to)
+            .finish()
+    }
+}
+
+impl<T, U> CubicBezier<T, U> {
+    /// Constructor.
+    #[inline]
+    pub const fn new(
+        from: Point2D<T, U>,
+        ctrl1: Point2D<T, U>,
+        ctrl2: Point2D<T, U>,
+        to: Point2D<T, U>,
+    ) -> Self {
+        CubicBezier {
+            from,
+            ctrl1,
+            ctrl2,
+            to,
+        }
+    }
+}
+
+impl<T: Float, U> CubicBezier<T, U> {
+    /// Creates the curve going from `from` to `to` with the given tangents, which are
+    /// the derivatives of the curve at its end points (cubic Hermite form).
+    pub fn from_hermite(
+        from: Point2D<T, U>,
+        from_tangent: Vector2D<T, U>,
+        to: Point2D<T, U>,
+        to_tangent: Vector2D<T, U>,
+    ) -> Self {
+        let three = T::one() + T::one() + T::one();
+        CubicBezier::new(
+            from,
+            from + from_tangent / three,
+            to - to_tangent / three,
+            to,
+        )
+    }
+
+    /// Returns the derivative of the curve at `t`.
+    pub fn derivative(&self, t: T) -> Vector2D<T, U> {
+        let one = T::one();
+        let three = one + one + one;
+        let (a, b) = (self.ctrl1 - self.from, self.ctrl2 - self.ctrl1);
+        let c = self.to - self.ctrl2;
+        let (ab, bc) = (a.lerp(b, t), b.lerp(c, t));
+        ab.lerp(bc, t) * three
+    }
+
+    /// Returns the point at `t` along the curve, `from` being at `t = 0` and `to` at
+    /// `t = 1`.
+    pub fn sample(&self, t: T) -> Point2D<T, U> {
+        let (a, b) = (
+            self.from.lerp(self.ctrl1, t),
+            self.ctrl1.lerp(self.ctrl2, t),
+        );
+        let c = self.ctrl2.lerp(self.to, t);
+        let (ab, bc) = (a.lerp(b, t), b.lerp(c, t));
+        ab.lerp(bc, t)
+    }
+
+    /// Splits the curve at `t`, returning the parts before and after it.
+    pub fn split(&self, t: T) -> (Self, Self) {
+        let (a, b) = (
+            self.from.lerp(self.ctrl1, t),
+            self.ctrl1.lerp(self.ctrl2, t),
+        );
+        let c = self.ctrl2.lerp(self.to, t);
+        let (ab, bc) = (a.lerp(b, t), b.lerp(c, t));
+        let p = ab.lerp(bc, t);
+
+        (
+            CubicBezier::new(self.from, a, ab, p),
+            CubicBezier::new(p, bc, c, self.to),
+        )
+    }
+
+    /// Returns the segment going from the start to the end of the curve.
+    #[inline]
+    pub fn baseline(&self) -> LineSegment2D<T, U> {
+        LineSegment2D::new(self.from, self.to)
+    }
+
+    /// Returns a box containing the curve, which is not necessarily the smallest one.
+    #[inline]
+    pub fn fast_bounding_box(&self) -> Box2D<T, U> {
+        Box2D::from_points(&[self.from, self.ctrl1, self.ctrl2, self.to])
+    }
+
+    /// Returns the estimated length of the curve, and an upper bound of the error.
+    ///
+    /// The length of the curve is between the length of its baseline and the length
+    /// of its control polygon.
+    fn estimate_length(&self) -> (T, T) {
+        let chord = (self.to - self.from).length();
+        let polygon = (self.ctrl1 - self.from).length()
+            + (self.ctrl2 - self.ctrl1).length()
+            + (self.to - self.ctrl2).length();
+        let two = T::one() + T::one();
+
+        ((chord + polygon) / two, (polygon - chord) / two)
+    }
+
+    /// Returns `tolerance`, raised to the precision that `T` can achieve on the length of
+    /// this curve, which bounds the number of subdivisions. NaN tolerances give the
+    /// minimum tolerance.
+    fn effective_tolerance(&self, tolerance: T) -> T {
+        let (length, _) = self.estimate_length();
+        let min_tolerance = length * T::epsilon().sqrt();
+        if tolerance > min_tolerance {
+            tolerance
+        } else {
+            min_tolerance
+        }
+    }
+
+    /// Returns the length of the curve, within `tolerance` of the exact length.
+    ///
+    /// Tolerances below about `sqrt(T::epsilon())` times the length of the curve, including
+    /// zero and NaN, are raised to that value. Returns NaN or infinity if the curve has
+    /// non-finite coordinates.
+    ///
+    /// ```rust
+    /// use euclid::default::CubicBezier;
+    /// use euclid::point2;
+    ///
+    /// // An approximation of a quarter of a circle of radius 10.
+    /// let k = 5.5228;
+    /// let arc = CubicBezier::new(point2(10.0, 0.0), point2(10.0, k), point2(k, 10.0), point2(0.0, 10.0));
+    /// let length: f64 = arc.length(0.001);
+    /// assert!((length - 15.708).abs() < 0.01);
+    /// ```
+    pub fn length(&self, tolerance: T) -> T {
+        let (length, _) = self.estimate_length();
+        if !length.is_finite() {
+            return length;
+        }
+
+        self.length_impl(self.effective_tolerance(tolerance), 0)
+    }
+
+    fn length_impl(&self, tolerance: T, depth: u32) -> T {
+        let (length, error) = self.estimate_length();
+        if error <= tolerance || depth >= MAX_DEPTH {
+            return length;
+        }
+
+        let two = T::one() + T::one();
+        let (a, b) = self.split(T::one() / two);
+        a.length_impl(tolerance / two, depth + 1) + b.length_impl(tolerance / two, depth + 1)
+    }
+
+    /// Returns the `t` of the point at the given arc length from the start of the
+    /// curve, with a length error of at most `tolerance`.
+    ///
+    /// Lengths are clamped to the length of the curve, so negative lengths give 0
+    /// and lengths past the end give 1. The tolerance is raised like in `length`, and NaN
+    /// is returned if the curve has non-finite coordinates.
+    ///
+    /// ```rust
+    /// use euclid::default::CubicBezier;
+    /// use euclid::point2;
+    ///
+    /// let curve = CubicBezier::new(point2(0.0, 0.0), point2(0.0, 10.0), point2(10.0, 10.0), point2(10.0, 0.0));
+    /// let half = curve.length(0.001) / 2.0;
+    /// let t: f64 = curve.t_at_length(half, 0.001);
+    /// assert!((t - 0.5).abs() < 0.001);
+    /// ```
+    pub fn t_at_length(&self, length: T, tolerance: T) -> T {
+        if !self.estimate_length().0.is_finite() {
+            return T::nan();
+        }
+        if length <= T::zero() {
+            return T::zero();
+        }
+
+        let tolerance = self.effective_tolerance(tolerance);
+        let mut remaining = length;
+        self.t_at_length_impl(&mut remaining, T::zero(), T::one(), tolerance, 0)
+            .unwrap_or_else(T::one)
+    }
+
+    /// Walks the curve, covering `[t0, t1]` of the original curve, and subtracts its
+    /// length from `remaining` unless the point is found within it.
+    fn t_at_length_impl(
+        &self,
+        remaining: &mut T,
+        t0: T,
+        t1: T,
+        tolerance: T,
+        depth: u32,
+    ) -> Option<T> {
+        let (length, error) = self.estimate_length();
+        let two = T::one() + T::one();
+
+        if error > tolerance && depth < MAX_DEPTH {
+            let (a, b) = self.split(T::one() / two);
+            let mid = (t0 + t1) / two;
+            return a
+                .t_at_length_impl(remaining, t0, mid, tolerance / two, depth + 1)
+                .or_else(|| b.t_at_length_impl(remaining, mid, t1, tolerance / two, depth + 1));
+        }
+
+        if *remaining > length {
+            *remaining = *remaining - length;
+            return None;
+        }
+
+        // The part is flat, but the speed along it can still vary, so search for the
+        // split point whose first half has the remaining length.
+        let (mut lo, mut hi) = (T::zero(), T::one());
+        let mut u = *remaining / length;
+        for _ in 0..MAX_DEPTH {
+            if !(u > lo && u < hi) {
+                u = (lo + hi) / two;
+            }
+            let part = self.split(u).0.length(tolerance / two);
+            if (part - *remaining).abs() <= tolerance {
+                break;
+            }
+            if part < *remaining {
+                lo = u;
+            } else {
+                hi = u;
+            }
+            u = (lo + hi) / two;
+        }
+
+        Some(t0 + (t1 - t0) * u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::default::CubicBezier;
+    use crate::{point2, vec2};
+
+    #[test]
+    fn test_split() {
+        let curve: CubicBezier<f64> = CubicBezier::new(
+            point2(0.0, 0.0),
+            point2(0.0, 10.0),
+            point2(10.0, 10.0),
+            point2(10.0, 0.0),
+        );
+        let (a, b) = curve.split(0.25);
+        assert_eq!(a.to, curve.sample(0.25));
+        assert_eq!(b.from, curve.sample(0.25));
+        assert!((a.sample(0.5) - curve.sample(0.125)).length() < 1e-12);
+        assert!((b.sample(0.5) - curve.sample(0.625)).length() < 1e-12);
+    }
+
+    #[test]
+    fn test_hermite() {
+        let curve: CubicBezier<f64> =
+            CubicBezier::from_hermite(point2(0.0, 0.0), vec2(3.0, 0.0), point2(1.0, 1.0), vec2(0.0, 6.0));
+        assert_eq!(curve.ctrl1, point2(1.0, 0.0));
+        assert_eq!(curve.ctrl2, point2(1.0, -1.0));
+        assert_eq!(curve.derivative(0.0), vec2(3.0, 0.0));
+        assert_eq!(curve.derivative(1.0), vec2(0.0, 6.0));
+    }
+
+    #[test]
+    fn test_length() {
+        let line: CubicBezier<f64> = CubicBezier::new(
+            point2(0.0, 0.0),
+            point2(1.0, 0.0),
+            point2(2.0, 0.0),
+            point2(3.0, 4.0),
+        );
+        let flat: CubicBezier<f64> = CubicBezier::new(
+            point2(0.0, 0.0),
+            point2(1.0, 1.0),
+            point2(2.0, 2.0),
+            point2(3.0, 3.0),
+        );
+        assert!((flat.length(1e-6) - 18f64.sqrt()).abs() < 1e-6);
+
+        // Compare with the length of a fine polyline.
+        let n = 10000;
+        let polyline: f64 = (0..n)
+            .map(|i| {
+                (line.sample((i + 1) as f64 / n as f64) - line.sample(i as f64 / n as f64)).length()
+            })
+            .sum();
+        assert!((line.length(1e-4) - polyline).abs() < 1e-4);
+
+        // A quarter of a circle of radius 1.
+        let k = 0.5522847498;
+        let arc: CubicBezier<f64> = CubicBezier::new(
+            point2(1.0, 0.0),
+            point2(1.0, k),
+            point2(k, 1.0),
+            point2(0.0, 1.0),
+        );
+        assert!((arc.length(1e-6) - core::f64::consts::FRAC_PI_2).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_t_at_length() {
+        // A line with uneven speed: t is not proportional to the length.
+        let line: CubicBezier<f64> = CubicBezier::new(
+            point2(0.0, 0.0),
+            point2(0.0, 0.0),
+            point2(0.0, 0.0),
+            point2(10.0, 0.0),
+        );
+        let length = line.length(1e-6);
+        assert!((length - 10.0).abs() < 1e-6);
+        for &s in &[1.0, 2.5, 5.0, 9.0] {
+            let t = line.t_at_length(s, 1e-6);
+            assert!(
+                (line.sample(t).x - s).abs() < 1e-3,
+                "{} {}",
+                s,
+                line.sample(t).x
+            );
+        }
+
+        assert_eq!(line.t_at_length(-1.0, 1e-3), 0.0);
+        assert_eq!(line.t_at_length(0.0, 1e-3), 0.0);
+        assert_eq!(line.t_at_length(11.0, 1e-3), 1.0);
+    }
+
+    #[test]
+    fn test_degenerate_tolerance() {
+        // These would subdivide the curve into 2^24 parts without a minimum tolerance.
+        let curve: CubicBezier<f64> = CubicBezier::new(
+            point2(0.0, 0.0),
+            point2(0.0, 10.0),
+            point2(10.0, 10.0),
+            point2(10.0, 0.0),
+        );
+        let expected = curve.length(1e-6);
+        for &tolerance in &[0.0, -1.0, 1e-310, f64::NAN] {
+            assert!((curve.length(tolerance) - expected).abs() < 1e-6);
+            assert!((curve.t_at_length(expected / 2.0, tolerance) - 0.5).abs() < 1e-6);
+        }
+
+        let curve: CubicBezier<f32> = CubicBezier::new(
+            curve.from.cast(),
+            curve.ctrl1.cast(),
+            curve.ctrl2.cast(),
+            curve.to.cast(),
+        );
+        assert!((curve.length(0.0) - expected as f32).abs() < 1e-2);
+
+        let nan: CubicBezier<f64> = CubicBezier::new(
+            point2(0.0, 0.0),
+            point2(f64::NAN, 1.0),
+            point2(2.0, 2.0),
+            point2(3.0, 3.0),
+        );
+        assert!(nan.length(1e-3).is_nan());
+        assert!(nan.t_at_length(1.0, 1e-3).is_nan());
+        let infinite = CubicBezier { ctrl1: point2(f64::INFINITY, 0.0), ..nan };
+        assert!(!infinite.length(1e-3).is_finite());
+    }
+}
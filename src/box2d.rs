@@ -17,7 +17,7 @@ use crate::side_offsets::SideOffsets2D;
 use crate::size::Size2D;
 use crate::vector::{vec2, Vector2D};
 
-use num_traits::{NumCast, Float};
+use num_traits::{CheckedMul, CheckedSub, NumCast, Float};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -128,6 +128,25 @@ impl<T, U> Box2D<T, U> {
     }
 }
 
+#[cfg(feature = "rand")]
+impl<T, U> Box2D<T, U>
+where
+    T: rand::distributions::uniform::SampleUniform + PartialOrd + Copy,
+{
+    /// Returns a point sampled uniformly inside of this box, following the semantics
+    /// of `contains` for points on the edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the box is empty.
+    pub fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Point2D<T, U> {
+        point2(
+            rng.gen_range(self.min.x..self.max.x),
+            rng.gen_range(self.min.y..self.max.y),
+        )
+    }
+}
+
 impl<T, U> Box2D<T, U>
 where
     T: PartialOrd,
@@ -148,6 +167,9 @@ where
     }
 
     /// Returns `true` if the two boxes intersect.
+    ///
+    /// Boxes that only share an edge or a corner don't intersect (see
+    /// `intersects_inclusive`).
     #[inline]
     pub fn intersects(&self, other: &Self) -> bool {
         self.min.x < other.max.x
@@ -216,6 +238,52 @@ where
         }
     }
 
+    /// Returns `true` if the two boxes intersect or touch, that is if their
+    /// intersection, edges included, is not negative.
+    ///
+    /// Unlike `intersects`, boxes that only share an edge or a corner are considered
+    /// intersecting, and so are zero-area boxes lying inside or on the edge of the
+    /// other box. Boxes with a negative area or NaN components never intersect.
+    #[inline]
+    pub fn intersects_inclusive(&self, other: &Self) -> bool {
+        let b = self.intersection_unchecked(other);
+        b.min.x <= b.max.x && b.min.y <= b.max.y
+    }
+
+    /// Returns `true` if the two boxes touch without their interiors overlapping,
+    /// that is if they only share (part of) an edge or a corner.
+    ///
+    /// ```rust
+    /// use euclid::default::Box2D;
+    /// use euclid::point2;
+    ///
+    /// let a: Box2D<i32> = Box2D::new(point2(0, 0), point2(10, 10));
+    /// let b = Box2D::new(point2(10, 5), point2(20, 20));
+    /// assert!(a.touches(&b));
+    /// assert!(!a.intersects(&b));
+    /// assert!(a.intersects_inclusive(&b));
+    /// ```
+    #[inline]
+    pub fn touches(&self, other: &Self) -> bool {
+        self.intersects_inclusive(other) && !self.intersects(other)
+    }
+
+    /// Computes the intersection of two boxes, edges included, returning `None` if
+    /// the boxes do not intersect.
+    ///
+    /// Unlike `intersection`, the result can have a zero area if the boxes only
+    /// touch (see `intersects_inclusive`).
+    #[inline]
+    pub fn intersection_inclusive(&self, other: &Self) -> Option<Self> {
+        let b = self.intersection_unchecked(other);
+
+        if b.min.x <= b.max.x && b.min.y <= b.max.y {
+            Some(b)
+        } else {
+            None
+        }
+    }
+
     /// Computes the union of two boxes.
     ///
     /// If either of the boxes is empty, the other one is returned.
@@ -235,6 +303,49 @@ where
     }
 }
 
+impl<T, U> Box2D<T, U>
+where
+    T: Copy + PartialOrd + Add<T, Output = T> + Sub<T, Output = T>,
+{
+    /// Returns `true` if the point is inside of this box or at most `eps` away from
+    /// it on each axis, edges included.
+    ///
+    /// Unlike `contains`, points on any edge are considered inside. This makes hit
+    /// testing near the edges robust to floating point errors.
+    ///
+    /// ```rust
+    /// use euclid::default::Box2D;
+    /// use euclid::point2;
+    ///
+    /// let b: Box2D<f32> = Box2D::new(point2(0.0, 0.0), point2(1.0, 1.0));
+    /// assert!(!b.contains(point2(1.0, 0.5)));
+    /// assert!(b.contains_eps(point2(1.0, 0.5), 0.0));
+    /// assert!(b.contains_eps(point2(1.0 + 1e-6, 0.5), 1e-5));
+    /// ```
+    #[inline]
+    pub fn contains_eps(&self, p: Point2D<T, U>, eps: T) -> bool {
+        self.min.x - eps <= p.x
+            && p.x <= self.max.x + eps
+            && self.min.y - eps <= p.y
+            && p.y <= self.max.y + eps
+    }
+
+    /// Returns `true` if the two boxes intersect after moving the edges of this box
+    /// outward by `eps`.
+    ///
+    /// With a positive `eps` boxes that touch, or are less than `eps` apart on each
+    /// axis, are considered intersecting. With a negative `eps` the interiors of the
+    /// boxes must overlap by more than `-eps`. `intersects_eps(other, 0)` is the same
+    /// as `intersects(other)`.
+    #[inline]
+    pub fn intersects_eps(&self, other: &Self, eps: T) -> bool {
+        self.min.x - eps < other.max.x
+            && other.min.x < self.max.x + eps
+            && self.min.y - eps < other.max.y
+            && other.min.y < self.max.y + eps
+    }
+}
+
 impl<T, U> Box2D<T, U>
 where
     T: Copy + Add<T, Output = T>,
@@ -290,6 +401,8 @@ where
     T: Copy + Add<T, Output = T> + Sub<T, Output = T>,
 {
     /// Inflates the box by the specified sizes on each side respectively.
+    ///
+    /// To inflate each side by a different amount, use `outer_box`.
     #[inline]
     #[must_use]
     pub fn inflate(&self, width: T, height: T) -> Self {
@@ -392,6 +505,17 @@ where
         let size = self.size();
         size.width * size.height
     }
+
+    /// Returns the area of the box, or `None` if computing it overflows.
+    #[inline]
+    pub fn checked_area(&self) -> Option<T>
+    where
+        T: CheckedMul + CheckedSub,
+    {
+        let width = self.max.x.checked_sub(&self.min.x)?;
+        let height = self.max.y.checked_sub(&self.min.y)?;
+        width.checked_mul(&height)
+    }
 }
 
 impl<T, U> Box2D<T, U>
@@ -502,6 +626,13 @@ where
         Box2D::new(self.min.cast_unit(), self.max.cast_unit())
     }
 
+    /// Swap the x and y axes, that is mirror the box around the `x = y` diagonal.
+    #[inline]
+    #[must_use]
+    pub fn transposed(&self) -> Self {
+        Box2D::new(self.min.yx(), self.max.yx())
+    }
+
     #[inline]
     pub fn scale<S: Copy>(&self, x: S, y: S) -> Self
     where
@@ -537,6 +668,16 @@ impl<T: NumCast + Copy, U> Box2D<T, U> {
         }
     }
 
+    /// Fallible cast from one numeric representation to another, preserving the units.
+    ///
+    /// Unlike `try_cast`, the error names the first component that could not be cast.
+    pub fn try_cast_detailed<NewT: NumCast>(&self) -> Result<Box2D<NewT, U>, CastError> {
+        Ok(Box2D::new(
+            point2(cast_component(self.min.x, "min.x")?, cast_component(self.min.y, "min.y")?),
+            point2(cast_component(self.max.x, "max.x")?, cast_component(self.max.y, "max.y")?),
+        ))
+    }
+
     // Convenience functions for common casts
 
     /// Cast into an `f32` box.
@@ -619,6 +760,21 @@ where
     }
 }
 
+impl<T, U> Box2D<T, U>
+where
+    T: RoundHalfEven,
+{
+    /// Return a box with edges rounded to the nearest integer coordinates, edges at
+    /// offset 0.5 rounding to the even integer.
+    ///
+    /// Unlike `round`, this doesn't shift boxes whose edges are on pixel centers in a
+    /// consistent direction, which avoids a drift when repeatedly scaling and rounding.
+    #[must_use]
+    pub fn round_half_even(&self) -> Self {
+        Box2D::new(self.min.round_half_even(), self.max.round_half_even())
+    }
+}
+
 impl<T, U> Box2D<T, U>
 where
     T: Floor + Ceil,
@@ -693,6 +849,15 @@ mod tests {
         assert_eq!(b.area(), 400.0);
     }
 
+    #[test]
+    fn test_checked_area() {
+        let b = Box2D::new(point2(-10, -10), point2(10, 10));
+        assert_eq!(b.checked_area(), Some(400i32));
+
+        let b = Box2D::new(point2(i32::MIN, 0), point2(i32::MAX, 1));
+        assert_eq!(b.checked_area(), None);
+    }
+
     #[test]
     fn test_from_points() {
         let b = Box2D::from_points(&[point2(50.0, 160.0), point2(100.0, 25.0)]);
@@ -811,6 +976,38 @@ mod tests {
         assert!(b1.intersection(&b2).is_none());
     }
 
+    #[test]
+    fn test_intersection_inclusive() {
+        let b1 = Box2D::new(point2(0.0, 0.0), point2(10.0, 10.0));
+        let edge = Box2D::new(point2(10.0, 2.0), point2(20.0, 8.0));
+        let corner = Box2D::new(point2(10.0, 10.0), point2(20.0, 20.0));
+        let overlap = Box2D::new(point2(5.0, 5.0), point2(20.0, 20.0));
+        let apart = Box2D::new(point2(11.0, 0.0), point2(20.0, 10.0));
+
+        assert!(b1.touches(&edge) && edge.touches(&b1));
+        assert!(b1.touches(&corner));
+        assert!(!b1.touches(&overlap));
+        assert!(!b1.touches(&apart));
+
+        assert!(b1.intersects_inclusive(&overlap));
+        assert!(!b1.intersects_inclusive(&apart));
+        assert_eq!(
+            b1.intersection_inclusive(&edge),
+            Some(Box2D::new(point2(10.0, 2.0), point2(10.0, 8.0)))
+        );
+        assert_eq!(
+            b1.intersection_inclusive(&corner),
+            Some(Box2D::new(point2(10.0, 10.0), point2(10.0, 10.0)))
+        );
+        assert_eq!(b1.intersection_inclusive(&apart), None);
+
+        // Negative and NaN boxes never intersect.
+        let negative = Box2D::new(point2(5.0, 5.0), point2(4.0, 6.0));
+        assert!(!b1.intersects_inclusive(&negative));
+        let nan = Box2D::new(point2(f32::NAN, 5.0), point2(6.0, 6.0));
+        assert!(!b1.intersects_inclusive(&nan));
+    }
+
     #[test]
     fn test_scale() {
         let b = Box2D::from_points(&[point2(-10.0, -10.0), point2(10.0, 10.0)]);
@@ -837,6 +1034,34 @@ mod tests {
         assert!(b.contains(point2(-15.3, 10.5)));
     }
 
+    #[test]
+    fn test_contains_eps() {
+        let b = Box2D::new(point2(0.0, 0.0), point2(0.3, 1.0));
+        // 0.1 + 0.2 > 0.3
+        let p = point2(0.1 + 0.2, 0.5);
+        assert!(!b.contains(p));
+        assert!(!b.contains_eps(p, 0.0));
+        assert!(b.contains_eps(p, 1e-9));
+        assert!(b.contains_eps(point2(-0.5, 1.5), 0.5));
+        assert!(!b.contains_eps(point2(-0.5, 1.6), 0.5));
+        assert!(!b.contains_eps(point2(0.1, 0.5), -0.2));
+    }
+
+    #[test]
+    fn test_intersects_eps() {
+        let b1 = Box2D::new(point2(0.0, 0.0), point2(10.0, 10.0));
+        let b2 = Box2D::new(point2(10.5, 2.0), point2(20.0, 8.0));
+        assert!(!b1.intersects_eps(&b2, 0.0));
+        assert!(!b1.intersects_eps(&b2, 0.5));
+        assert!(b1.intersects_eps(&b2, 0.6));
+        assert!(b2.intersects_eps(&b1, 0.6));
+
+        let b3 = Box2D::new(point2(9.0, 2.0), point2(20.0, 8.0));
+        assert_eq!(b1.intersects_eps(&b3, 0.0), b1.intersects(&b3));
+        assert!(b1.intersects_eps(&b3, -0.5));
+        assert!(!b1.intersects_eps(&b3, -1.0));
+    }
+
     #[test]
     fn test_contains_box() {
         let b1 = Box2D::from_points(&[point2(-20.0, -20.0), point2(20.0, 20.0)]);
@@ -0,0 +1,136 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fixed-size binary encoding of the plain data types.
+//!
+//! The `to_bytes` and `from_bytes` methods implemented here write the terms of a value
+//! in declaration order, each in little-endian byte order, without padding.
+
+use crate::{Box2D, Point2D, Point3D, Rect, Size2D, Transform2D, Transform3D, Vector2D, Vector3D};
+
+// Floats go through their bit patterns, whose integer types have the byte conversions.
+macro_rules! scalar_to_bytes {
+    (f32, $value:expr) => { $value.to_bits().to_le_bytes() };
+    (f64, $value:expr) => { $value.to_bits().to_le_bytes() };
+    ($int:ident, $value:expr) => { $value.to_le_bytes() };
+}
+
+macro_rules! scalar_from_bytes {
+    (f32, $bytes:expr) => { f32::from_bits(u32::from_le_bytes($bytes)) };
+    (f64, $bytes:expr) => { f64::from_bits(u64::from_le_bytes($bytes)) };
+    ($int:ident, $bytes:expr) => { $int::from_le_bytes($bytes) };
+}
+
+macro_rules! bytes {
+    ($ty:ident<$($param:ident),+>, $scalar:ident, $len:expr,
+     |$this:ident| $to_terms:expr, |$terms:ident| $from_terms:expr) => {
+        impl<$($param),+> $ty<$scalar, $($param),+> {
+            /// Returns the little-endian encoding of the terms of this value, in
+            /// declaration order.
+            #[inline]
+            pub fn to_bytes(&self) -> [u8; $len * core::mem::size_of::<$scalar>()] {
+                const SIZE: usize = core::mem::size_of::<$scalar>();
+                let $this = self;
+                let terms: [$scalar; $len] = $to_terms;
+                let mut bytes = [0; $len * SIZE];
+                for (chunk, term) in bytes.chunks_mut(SIZE).zip(terms.iter()) {
+                    chunk.copy_from_slice(&scalar_to_bytes!($scalar, *term));
+                }
+                bytes
+            }
+
+            /// Creates a value from the encoding returned by `to_bytes`.
+            #[inline]
+            pub fn from_bytes(bytes: [u8; $len * core::mem::size_of::<$scalar>()]) -> Self {
+                const SIZE: usize = core::mem::size_of::<$scalar>();
+                let mut $terms: [$scalar; $len] = [0 as $scalar; $len];
+                for (term, chunk) in $terms.iter_mut().zip(bytes.chunks(SIZE)) {
+                    let mut term_bytes = [0; SIZE];
+                    term_bytes.copy_from_slice(chunk);
+                    *term = scalar_from_bytes!($scalar, term_bytes);
+                }
+                $from_terms
+            }
+        }
+    };
+}
+
+macro_rules! all_bytes {
+    ($scalar:ident) => {
+        bytes!(Point2D<U>, $scalar, 2, |p| p.to_array(), |t| t.into());
+        bytes!(Point3D<U>, $scalar, 3, |p| p.to_array(), |t| t.into());
+        bytes!(Vector2D<U>, $scalar, 2, |v| v.to_array(), |t| t.into());
+        bytes!(Vector3D<U>, $scalar, 3, |v| v.to_array(), |t| t.into());
+        bytes!(Size2D<U>, $scalar, 2, |s| s.to_array(), |t| t.into());
+        bytes!(
+            Rect<U>, $scalar, 4,
+            |r| [r.origin.x, r.origin.y, r.size.width, r.size.height],
+            |t| Rect::new(Point2D::new(t[0], t[1]), Size2D::new(t[2], t[3]))
+        );
+        bytes!(
+            Box2D<U>, $scalar, 4,
+            |b| [b.min.x, b.min.y, b.max.x, b.max.y],
+            |t| Box2D::new(Point2D::new(t[0], t[1]), Point2D::new(t[2], t[3]))
+        );
+        bytes!(Transform2D<Src, Dst>, $scalar, 6, |m| m.to_array(), |t| Transform2D::from_array(t));
+        bytes!(Transform3D<Src, Dst>, $scalar, 16, |m| m.to_array(), |t| Transform3D::from_array(t));
+    };
+}
+
+all_bytes!(f32);
+all_bytes!(f64);
+all_bytes!(i32);
+
+#[cfg(test)]
+mod tests {
+    use crate::default::{Box2D, Point2D, Rect, Transform2D, Transform3D};
+    use crate::{point2, point3, rect, size2, vec2};
+
+    #[test]
+    fn test_bytes() {
+        let p: Point2D<f32> = point2(1.0, -2.0);
+        let bytes = p.to_bytes();
+        assert_eq!(bytes[..4], 1.0f32.to_le_bytes());
+        assert_eq!(bytes[4..], (-2.0f32).to_le_bytes());
+        assert_eq!(Point2D::<f32>::from_bytes(bytes), p);
+
+        let p = point3::<f64, crate::UnknownUnit>(1.0, 2.0, 3.0);
+        assert_eq!(p.to_bytes().len(), 24);
+        assert_eq!(crate::default::Point3D::<f64>::from_bytes(p.to_bytes()), p);
+
+        let v = vec2::<i32, crate::UnknownUnit>(7, -8);
+        assert_eq!(v.to_bytes(), [7, 0, 0, 0, 0xf8, 0xff, 0xff, 0xff]);
+
+        let s = size2::<f32, crate::UnknownUnit>(3.0, 4.0);
+        assert_eq!(crate::default::Size2D::<f32>::from_bytes(s.to_bytes()), s);
+
+        let r: Rect<i32> = rect(1, 2, 3, 4);
+        let bytes = r.to_bytes();
+        assert_eq!(bytes[12..], 4i32.to_le_bytes());
+        assert_eq!(Rect::<i32>::from_bytes(bytes), r);
+
+        let b: Box2D<i32> = r.to_box2d();
+        assert_eq!(Box2D::<i32>::from_bytes(b.to_bytes()), b);
+    }
+
+    #[test]
+    fn test_transform_bytes() {
+        let m: Transform2D<f32> = Transform2D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+        let bytes = m.to_bytes();
+        assert_eq!(bytes.len(), 24);
+        assert_eq!(bytes[20..], 6.0f32.to_le_bytes());
+        assert_eq!(Transform2D::<f32>::from_bytes(bytes), m);
+
+        let m: Transform3D<f64> = Transform3D::translation(1.0, 2.0, 3.0);
+        let bytes = m.to_bytes();
+        assert_eq!(bytes.len(), 128);
+        assert_eq!(bytes[96..104], 1.0f64.to_le_bytes());
+        assert_eq!(Transform3D::<f64>::from_bytes(bytes), m);
+    }
+}
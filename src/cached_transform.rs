@@ -0,0 +1,149 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::num::{One, Zero};
+use crate::transform3d::Transform3D;
+use core::cell::Cell;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A `Transform3D` along with its inverse, computed the first time it is needed.
+///
+/// This is useful for transforms that are inverted many times, for example to hit
+/// test many points against the same content. The cached inverse is discarded when
+/// the transform is modified through `set` or `update`.
+///
+/// ```rust
+/// use euclid::{point2, CachedTransform3D};
+/// use euclid::default::Transform3D;
+///
+/// let mut transform = CachedTransform3D::new(Transform3D::translation(10.0, 0.0, 0.0));
+/// let inverse = transform.inverse().unwrap();
+/// assert_eq!(inverse.transform_point2d(point2(10.0, 5.0)), Some(point2(0.0, 5.0)));
+///
+/// transform.update(|t| *t = t.then_scale(2.0, 2.0, 1.0));
+/// let inverse = transform.inverse().unwrap();
+/// assert_eq!(inverse.transform_point2d(point2(20.0, 10.0)), Some(point2(0.0, 5.0)));
+/// ```
+pub struct CachedTransform3D<T, Src, Dst> {
+    transform: Transform3D<T, Src, Dst>,
+    inverse: Cell<Option<Option<Transform3D<T, Dst, Src>>>>,
+}
+
+impl<T: Copy, Src, Dst> CachedTransform3D<T, Src, Dst> {
+    /// Wraps a transform, without computing its inverse yet.
+    #[inline]
+    pub fn new(transform: Transform3D<T, Src, Dst>) -> Self {
+        CachedTransform3D {
+            transform,
+            inverse: Cell::new(None),
+        }
+    }
+
+    /// Returns the transform.
+    #[inline]
+    pub fn get(&self) -> &Transform3D<T, Src, Dst> {
+        &self.transform
+    }
+
+    /// Replaces the transform, discarding the cached inverse.
+    #[inline]
+    pub fn set(&mut self, transform: Transform3D<T, Src, Dst>) {
+        self.transform = transform;
+        self.inverse.set(None);
+    }
+
+    /// Modifies the transform in place, discarding the cached inverse.
+    #[inline]
+    pub fn update<F: FnOnce(&mut Transform3D<T, Src, Dst>)>(&mut self, f: F) {
+        f(&mut self.transform);
+        self.inverse.set(None);
+    }
+
+    /// Returns the wrapped transform.
+    #[inline]
+    pub fn into_inner(self) -> Transform3D<T, Src, Dst> {
+        self.transform
+    }
+}
+
+impl<T, Src, Dst> CachedTransform3D<T, Src, Dst>
+where
+    T: Copy
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialOrd
+        + Zero
+        + One,
+{
+    /// Returns the inverse of the transform if possible, computing it on the first call.
+    pub fn inverse(&self) -> Option<Transform3D<T, Dst, Src>> {
+        if let Some(inverse) = self.inverse.get() {
+            return inverse;
+        }
+
+        let inverse = self.transform.inverse();
+        self.inverse.set(Some(inverse));
+
+        inverse
+    }
+}
+
+impl<T: Copy, Src, Dst> Clone for CachedTransform3D<T, Src, Dst> {
+    fn clone(&self) -> Self {
+        CachedTransform3D {
+            transform: self.transform,
+            inverse: Cell::new(self.inverse.get()),
+        }
+    }
+}
+
+impl<T: Copy, Src, Dst> From<Transform3D<T, Src, Dst>> for CachedTransform3D<T, Src, Dst> {
+    fn from(transform: Transform3D<T, Src, Dst>) -> Self {
+        Self::new(transform)
+    }
+}
+
+impl<T, Src, Dst> fmt::Debug for CachedTransform3D<T, Src, Dst>
+where
+    T: Copy + fmt::Debug + PartialEq + One + Zero,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.transform.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default;
+
+    type Mf32 = default::Transform3D<f32>;
+
+    #[test]
+    fn test_cached_inverse() {
+        let mut m = CachedTransform3D::new(Mf32::scale(2.0, 4.0, 1.0));
+        assert_eq!(m.inverse.get(), None);
+        assert_eq!(m.inverse(), Some(Mf32::scale(0.5, 0.25, 1.0)));
+        assert_eq!(m.inverse.get(), Some(Some(Mf32::scale(0.5, 0.25, 1.0))));
+        assert_eq!(m.clone().inverse.get(), m.inverse.get());
+
+        m.set(Mf32::scale(0.0, 1.0, 1.0));
+        assert_eq!(m.inverse.get(), None);
+        assert_eq!(m.inverse(), None);
+        assert_eq!(m.inverse.get(), Some(None));
+
+        m.update(|t| t.m11 = 1.0);
+        assert_eq!(m.get(), &Mf32::identity());
+        assert_eq!(m.inverse(), Some(Mf32::identity()));
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Accumulation of dirty rectangles.
+//!
+//! ```rust
+//! use euclid::rect;
+//! use euclid::damage::DamageTracker;
+//! use euclid::default::Rect;
+//!
+//! let mut damage = DamageTracker::new(2);
+//! damage.add(&rect(0, 0, 10, 10));
+//! damage.add(&rect(100, 100, 10, 10));
+//! damage.add(&rect(12, 0, 10, 10));
+//!
+//! // The two rects on the left are merged since that adds less area than merging
+//! // either of them with the one at the bottom right.
+//! let rects: Vec<Rect<i32>> = damage.drain();
+//! assert_eq!(rects, vec![rect(0, 0, 22, 10), rect(100, 100, 10, 10)]);
+//! assert!(damage.is_empty());
+//! ```
+
+use crate::box2d::Box2D;
+use crate::num::Zero;
+use crate::rect::Rect;
+use alloc::vec::Vec;
+use core::ops::{Add, Mul, Sub};
+
+/// Accumulates dirty rectangles, coalescing them into a bounded number of rectangles.
+///
+/// When adding a rectangle would exceed the limit, the two rectangles whose union
+/// adds the least area on top of their own areas are merged, which keeps the amount
+/// of over-painting low compared to unioning all of the damage into a single rect.
+pub struct DamageTracker<T, U> {
+    rects: Vec<Box2D<T, U>>,
+    max_rects: usize,
+}
+
+impl<T, U> DamageTracker<T, U>
+where
+    T: Copy + Zero + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Creates an empty tracker that holds at most `max_rects` rectangles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_rects` is zero.
+    pub fn new(max_rects: usize) -> Self {
+        assert!(max_rects > 0);
+        DamageTracker {
+            rects: Vec::new(),
+            max_rects,
+        }
+    }
+
+    /// Returns the maximum number of rectangles held by the tracker.
+    #[inline]
+    pub fn max_rects(&self) -> usize {
+        self.max_rects
+    }
+
+    /// Returns the number of rectangles currently held.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rects.len()
+    }
+
+    /// Returns true if no damage has been added since the last drain.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Adds a dirty rectangle. Empty rectangles are ignored.
+    pub fn add(&mut self, rect: &Rect<T, U>) {
+        let b = rect.to_box2d();
+        if b.is_empty() || self.rects.iter().any(|r| r.contains_box(&b)) {
+            return;
+        }
+
+        self.rects.retain(|r| !b.contains_box(r));
+        self.rects.push(b);
+
+        while self.rects.len() > self.max_rects {
+            self.merge_cheapest_pair();
+        }
+    }
+
+    /// Returns the accumulated rectangles.
+    pub fn rects(&self) -> impl Iterator<Item = Rect<T, U>> + '_ {
+        self.rects.iter().map(Box2D::to_rect)
+    }
+
+    /// Returns the smallest rectangle containing all of the damage, or `None` if
+    /// there is none.
+    pub fn bounding_rect(&self) -> Option<Rect<T, U>> {
+        let (first, rest) = self.rects.split_first()?;
+        Some(rest.iter().fold(*first, |acc, r| acc.union(r)).to_rect())
+    }
+
+    /// Returns the accumulated rectangles and resets the tracker, typically once
+    /// per frame.
+    pub fn drain(&mut self) -> Vec<Rect<T, U>> {
+        let rects = self.rects.iter().map(Box2D::to_rect).collect();
+        self.rects.clear();
+
+        rects
+    }
+
+    /// Removes all of the damage.
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+
+    fn merge_cheapest_pair(&mut self) {
+        // The cost of merging two rects is the area of their union minus the sum of their
+        // areas. It is negative for overlapping rects, so the two terms are compared
+        // without subtracting, which would underflow for unsigned scalars.
+        let areas = |a: &Box2D<T, U>, b: &Box2D<T, U>| (a.union(b).area(), a.area() + b.area());
+
+        let mut best = (0, 1);
+        let (mut best_union, mut best_sum) = areas(&self.rects[0], &self.rects[1]);
+        for i in 0..self.rects.len() {
+            for j in (i + 1)..self.rects.len() {
+                let (union, sum) = areas(&self.rects[i], &self.rects[j]);
+                // union - sum < best_union - best_sum
+                if union + best_sum < best_union + sum {
+                    best = (i, j);
+                    best_union = union;
+                    best_sum = sum;
+                }
+            }
+        }
+
+        let merged = self.rects[best.0].union(&self.rects[best.1]);
+        self.rects.swap_remove(best.1);
+        self.rects[best.0] = merged;
+
+        // Remove the rects covered by the merged one, including copies of it.
+        let mut index = 0;
+        self.rects.retain(|r| {
+            let keep = index == best.0 || !merged.contains_box(r);
+            index += 1;
+            keep
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default;
+    use crate::rect;
+
+    #[test]
+    fn test_containment() {
+        let mut damage: DamageTracker<f32, _> = DamageTracker::new(4);
+        damage.add(&rect(10.0, 10.0, 5.0, 5.0));
+        damage.add(&rect(11.0, 11.0, 1.0, 1.0));
+        damage.add(&rect(0.0, 0.0, 0.0, 100.0));
+        assert_eq!(damage.len(), 1);
+
+        damage.add(&rect(0.0, 0.0, 50.0, 50.0));
+        let rects: Vec<default::Rect<f32>> = damage.rects().collect();
+        assert_eq!(rects, vec![rect(0.0, 0.0, 50.0, 50.0)]);
+    }
+
+    #[test]
+    fn test_coalesce() {
+        let mut damage: DamageTracker<i32, _> = DamageTracker::new(3);
+        for i in 0..10 {
+            damage.add(&rect(i * 100, 0, 10, 10));
+        }
+        assert_eq!(damage.len(), 3);
+
+        let bounds: default::Rect<i32> = damage.bounding_rect().unwrap();
+        assert_eq!(bounds, rect(0, 0, 910, 10));
+
+        let rects = damage.drain();
+        for i in 0..10 {
+            assert!(rects.iter().any(|r| r.contains_rect(&rect(i * 100, 0, 10, 10))));
+        }
+        assert!(damage.is_empty());
+        assert_eq!(damage.bounding_rect(), None);
+    }
+
+    #[test]
+    fn test_overlapping_unsigned() {
+        let mut damage: DamageTracker<u32, _> = DamageTracker::new(1);
+        damage.add(&rect(0, 0, 10, 10));
+        damage.add(&rect(5, 0, 10, 10));
+        let rects: Vec<default::Rect<u32>> = damage.rects().collect();
+        assert_eq!(rects, vec![rect(0, 0, 15, 10)]);
+
+        // Overlapping rects are merged before disjoint ones.
+        let mut damage: DamageTracker<u32, _> = DamageTracker::new(2);
+        damage.add(&rect(0, 0, 10, 10));
+        damage.add(&rect(100, 0, 10, 10));
+        damage.add(&rect(5, 5, 10, 10));
+        let rects: Vec<default::Rect<u32>> = damage.rects().collect();
+        assert_eq!(rects, vec![rect(0, 0, 15, 15), rect(100, 0, 10, 10)]);
+    }
+
+    #[test]
+    fn test_merge_removes_duplicates() {
+        // Merging two copies of a rect keeps a single one, and drops the other copies.
+        let mut damage: DamageTracker<i32, _> = DamageTracker::new(3);
+        damage.rects = vec![rect(0, 0, 10, 10).to_box2d(); 3];
+        damage.merge_cheapest_pair();
+        let rects: Vec<default::Rect<i32>> = damage.rects().collect();
+        assert_eq!(rects, vec![rect(0, 0, 10, 10)]);
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Helpers shared by the `fmt::Display` implementations.
+
+use core::fmt::{self, Write};
+
+/// A writer that only counts the characters written to it.
+struct CharCounter(usize);
+
+impl Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Writes a value with the given precision, if any.
+fn write_value<W: Write, T: fmt::Display>(w: &mut W, value: &T, precision: Option<usize>) -> fmt::Result {
+    match precision {
+        Some(precision) => write!(w, "{:.*}", precision, value),
+        None => write!(w, "{}", value),
+    }
+}
+
+/// Returns the number of characters `write_value` produces for a value.
+fn value_width<T: fmt::Display>(value: &T, precision: Option<usize>) -> usize {
+    let mut counter = CharCounter(0);
+    let _ = write_value(&mut counter, value, precision);
+    counter.0
+}
+
+/// Writes the values of a matrix, given in row-major order, with one row per line and
+/// right-aligned columns. Matrices can have up to four columns.
+///
+/// The precision of the formatter, if any, applies to all values. The optional note
+/// is written after the last row.
+pub(crate) fn write_grid<T: fmt::Display>(
+    f: &mut fmt::Formatter,
+    values: &[T],
+    columns: usize,
+    note: Option<&str>,
+) -> fmt::Result {
+    debug_assert!(columns > 0 && columns <= 4);
+    let precision = f.precision();
+
+    let mut widths = [0; 4];
+    for (i, value) in values.iter().enumerate() {
+        let width = &mut widths[i % columns];
+        *width = (*width).max(value_width(value, precision));
+    }
+
+    for (i, value) in values.iter().enumerate() {
+        let column = i % columns;
+        if column == 0 {
+            if i > 0 {
+                f.write_str(" ]\n")?;
+            }
+            f.write_str("[ ")?;
+        } else {
+            f.write_str(", ")?;
+        }
+        for _ in value_width(value, precision)..widths[column] {
+            f.write_char(' ')?;
+        }
+        write_value(f, value, precision)?;
+    }
+    f.write_str(" ]")?;
+
+    if let Some(note) = note {
+        write!(f, " ({})", note)?;
+    }
+
+    Ok(())
+}
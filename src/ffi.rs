@@ -0,0 +1,254 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A C API for the core rectangle, point and transform operations.
+//!
+//! All euclid types are `#[repr(C)]` and their unit parameter is zero-sized, so the
+//! aliases below have exactly the layout of the equivalent C structs:
+//!
+//! ```c
+//! typedef struct { float x, y; } EuclidPoint2D;
+//! typedef struct { float width, height; } EuclidSize2D;
+//! typedef struct { EuclidPoint2D origin; EuclidSize2D size; } EuclidRect;
+//! typedef struct { float m11, m12, m21, m22, m31, m32; } EuclidTransform2D;
+//! typedef struct { float m11, m12, m13, m14, /* ... */ m44; } EuclidTransform3D;
+//! ```
+//!
+//! Functions that can fail write their result through an out parameter and return
+//! `false` on failure, leaving the out parameter untouched.
+
+// Exporting symbols with `#[no_mangle]` counts as unsafe code.
+#![allow(unsafe_code)]
+
+use crate::default;
+
+pub type EuclidPoint2D = default::Point2D<f32>;
+pub type EuclidVector2D = default::Vector2D<f32>;
+pub type EuclidSize2D = default::Size2D<f32>;
+pub type EuclidRect = default::Rect<f32>;
+pub type EuclidTransform2D = default::Transform2D<f32>;
+pub type EuclidTransform3D = default::Transform3D<f32>;
+
+#[no_mangle]
+pub extern "C" fn euclid_rect_is_empty(rect: &EuclidRect) -> bool {
+    rect.is_empty()
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_rect_contains_point(rect: &EuclidRect, point: &EuclidPoint2D) -> bool {
+    rect.contains(*point)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_rect_contains_rect(rect: &EuclidRect, other: &EuclidRect) -> bool {
+    rect.contains_rect(other)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_rect_intersects(a: &EuclidRect, b: &EuclidRect) -> bool {
+    a.intersects(b)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_rect_intersection(
+    a: &EuclidRect,
+    b: &EuclidRect,
+    out: &mut EuclidRect,
+) -> bool {
+    match a.intersection(b) {
+        Some(rect) => {
+            *out = rect;
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_rect_union(a: &EuclidRect, b: &EuclidRect) -> EuclidRect {
+    a.union(b)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_rect_translate(rect: &EuclidRect, by: &EuclidVector2D) -> EuclidRect {
+    rect.translate(*by)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_rect_inflate(rect: &EuclidRect, width: f32, height: f32) -> EuclidRect {
+    rect.inflate(width, height)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_rect_round_out(rect: &EuclidRect) -> EuclidRect {
+    rect.round_out()
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_point_distance_to(a: &EuclidPoint2D, b: &EuclidPoint2D) -> f32 {
+    a.distance_to(*b)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_point_lerp(
+    a: &EuclidPoint2D,
+    b: &EuclidPoint2D,
+    t: f32,
+) -> EuclidPoint2D {
+    a.lerp(*b, t)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_transform2d_identity() -> EuclidTransform2D {
+    EuclidTransform2D::identity()
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_transform2d_then(
+    a: &EuclidTransform2D,
+    b: &EuclidTransform2D,
+) -> EuclidTransform2D {
+    a.then(b)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_transform2d_inverse(
+    m: &EuclidTransform2D,
+    out: &mut EuclidTransform2D,
+) -> bool {
+    match m.inverse() {
+        Some(inverse) => {
+            *out = inverse;
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_transform2d_transform_point(
+    m: &EuclidTransform2D,
+    point: &EuclidPoint2D,
+) -> EuclidPoint2D {
+    m.transform_point(*point)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_transform2d_outer_transformed_rect(
+    m: &EuclidTransform2D,
+    rect: &EuclidRect,
+) -> EuclidRect {
+    m.outer_transformed_rect(rect)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_transform3d_identity() -> EuclidTransform3D {
+    EuclidTransform3D::identity()
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_transform3d_then(
+    a: &EuclidTransform3D,
+    b: &EuclidTransform3D,
+) -> EuclidTransform3D {
+    a.then(b)
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_transform3d_inverse(
+    m: &EuclidTransform3D,
+    out: &mut EuclidTransform3D,
+) -> bool {
+    match m.inverse() {
+        Some(inverse) => {
+            *out = inverse;
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_transform3d_transform_point2d(
+    m: &EuclidTransform3D,
+    point: &EuclidPoint2D,
+    out: &mut EuclidPoint2D,
+) -> bool {
+    match m.transform_point2d(*point) {
+        Some(p) => {
+            *out = p;
+            true
+        }
+        None => false,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn euclid_transform3d_outer_transformed_rect(
+    m: &EuclidTransform3D,
+    rect: &EuclidRect,
+    out: &mut EuclidRect,
+) -> bool {
+    match m.outer_transformed_rect(rect) {
+        Some(r) => {
+            *out = r;
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{point2, rect, vec2};
+
+    #[test]
+    fn test_rect_intersection() {
+        let a: EuclidRect = rect(0.0, 0.0, 10.0, 10.0);
+        let b: EuclidRect = rect(5.0, 5.0, 10.0, 10.0);
+        let c: EuclidRect = rect(20.0, 20.0, 1.0, 1.0);
+
+        let mut out = EuclidRect::zero();
+        assert!(euclid_rect_intersection(&a, &b, &mut out));
+        assert_eq!(out, rect(5.0, 5.0, 5.0, 5.0));
+
+        assert!(!euclid_rect_intersection(&a, &c, &mut out));
+        assert_eq!(out, rect(5.0, 5.0, 5.0, 5.0));
+    }
+
+    #[test]
+    fn test_transform2d() {
+        let m = euclid_transform2d_then(
+            &EuclidTransform2D::scale(2.0, 2.0),
+            &EuclidTransform2D::translation(1.0, 0.0),
+        );
+        let p = euclid_transform2d_transform_point(&m, &point2(1.0, 1.0));
+        assert_eq!(p, point2(3.0, 2.0));
+
+        let mut inv = euclid_transform2d_identity();
+        assert!(euclid_transform2d_inverse(&m, &mut inv));
+        assert_eq!(euclid_transform2d_transform_point(&inv, &p), point2(1.0, 1.0));
+
+        assert!(!euclid_transform2d_inverse(&EuclidTransform2D::scale(0.0, 1.0), &mut inv));
+    }
+
+    #[test]
+    fn test_transform3d() {
+        let m = EuclidTransform3D::translation(1.0, 2.0, 0.0);
+        let mut out = point2(0.0, 0.0);
+        assert!(euclid_transform3d_transform_point2d(&m, &point2(1.0, 1.0), &mut out));
+        assert_eq!(out, point2(2.0, 3.0));
+
+        let r = euclid_rect_translate(&rect(0.0, 0.0, 1.0, 1.0), &vec2(1.0, 2.0));
+        let mut out = EuclidRect::zero();
+        assert!(euclid_transform3d_outer_transformed_rect(&m, &rect(0.0, 0.0, 1.0, 1.0), &mut out));
+        assert_eq!(out, r);
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::box3d::Box3D;
+use crate::num::Zero;
+use crate::plane::Plane3D;
+use crate::point::{point3, Point3D};
+use crate::transform3d::Transform3D;
+use crate::vector::vec3;
+
+use num_traits::Float;
+
+use core::fmt;
+use core::ops::{Add, Mul};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The volume visible through a projection, bounded by six planes facing inwards.
+///
+/// The planes are in the space of the points that are projected, typically world or
+/// view space.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct Frustum<T, U> {
+    /// The left, right, bottom, top, near and far planes, in that order. Their
+    /// normals are normalized and point towards the inside of the frustum.
+    pub planes: [Plane3D<T, U>; 6],
+}
+
+impl<T: Copy, U> Copy for Frustum<T, U> {}
+
+impl<T: Copy, U> Clone for Frustum<T, U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for Frustum<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.planes == other.planes
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for Frustum<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Frustum").field(&self.planes).finish()
+    }
+}
+
+impl<T: Float, U> Frustum<T, U> {
+    /// Extracts the frustum of a projection (typically a projection-view transform),
+    /// for clip spaces where the visible depth goes from -1 to 1, such as with
+    /// `Transform3D::perspective_fov` and `Transform3D::ortho`.
+    ///
+    /// ```rust
+    /// use euclid::default::{Box3D, Frustum, Transform3D};
+    /// use euclid::{point3, Angle};
+    ///
+    /// let projection: Transform3D<f32> =
+    ///     Transform3D::perspective_fov(Angle::degrees(90.0), 1.0, 1.0, 100.0);
+    /// let frustum = Frustum::from_transform(&projection);
+    ///
+    /// // The camera looks towards negative z.
+    /// assert!(frustum.contains_point3d(point3(0.0, 0.0, -10.0)));
+    /// assert!(!frustum.contains_point3d(point3(0.0, 0.0, 10.0)));
+    /// assert!(frustum.intersects_box3d(&Box3D::new(point3(5.0, 5.0, -8.0), point3(20.0, 20.0, -6.0))));
+    /// assert!(!frustum.intersects_box3d(&Box3D::new(point3(10.0, 5.0, -8.0), point3(20.0, 20.0, -6.0))));
+    /// ```
+    pub fn from_transform<Dst>(m: &Transform3D<T, U, Dst>) -> Self {
+        Self::from_transform_impl(m, false)
+    }
+
+    /// Extracts the frustum of a projection for clip spaces where the visible depth
+    /// goes from 0 to 1, such as with `Transform3D::perspective_fov_zo` and
+    /// `Transform3D::ortho_zo`.
+    pub fn from_transform_zo<Dst>(m: &Transform3D<T, U, Dst>) -> Self {
+        Self::from_transform_impl(m, true)
+    }
+
+    fn from_transform_impl<Dst>(m: &Transform3D<T, U, Dst>, zero_to_one: bool) -> Self {
+        // A point is visible if its clip space coordinates satisfy -w <= x <= w,
+        // -w <= y <= w and -w <= z <= w (or 0 <= z <= w). Each coordinate is the dot
+        // product of the point with a column of the matrix, which gives the planes.
+        let plane = |a: T, b: T, c: T, d: T| Plane3D::new(vec3(a, b, c), d).normalize();
+        let near = if zero_to_one {
+            plane(m.m13, m.m23, m.m33, m.m43)
+        } else {
+            plane(m.m14 + m.m13, m.m24 + m.m23, m.m34 + m.m33, m.m44 + m.m43)
+        };
+
+        Frustum {
+            planes: [
+                plane(m.m14 + m.m11, m.m24 + m.m21, m.m34 + m.m31, m.m44 + m.m41),
+                plane(m.m14 - m.m11, m.m24 - m.m21, m.m34 - m.m31, m.m44 - m.m41),
+                plane(m.m14 + m.m12, m.m24 + m.m22, m.m34 + m.m32, m.m44 + m.m42),
+                plane(m.m14 - m.m12, m.m24 - m.m22, m.m34 - m.m32, m.m44 - m.m42),
+                near,
+                plane(m.m14 - m.m13, m.m24 - m.m23, m.m34 - m.m33, m.m44 - m.m43),
+            ],
+        }
+    }
+}
+
+impl<T, U> Frustum<T, U>
+where
+    T: Copy + Zero + PartialOrd + Add<Output = T> + Mul<Output = T>,
+{
+    /// Returns true if the point is inside of the frustum or on its boundary.
+    pub fn contains_point3d(&self, p: Point3D<T, U>) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance_to_point(p) >= T::zero())
+    }
+
+    /// Returns true if the box is at least partially inside of the frustum.
+    ///
+    /// The test is conservative: it never returns false for a box that intersects the
+    /// frustum, but it can return true for some boxes that are outside of it, near
+    /// its edges. Boxes with a negative volume never intersect the frustum.
+    pub fn intersects_box3d(&self, b: &Box3D<T, U>) -> bool {
+        if b.is_negative() {
+            return false;
+        }
+
+        self.planes.iter().all(|plane| {
+            // The corner of the box that is the furthest in front of the plane.
+            let n = plane.normal;
+            let corner = point3(
+                if n.x >= T::zero() { b.max.x } else { b.min.x },
+                if n.y >= T::zero() { b.max.y } else { b.min.y },
+                if n.z >= T::zero() { b.max.z } else { b.min.z },
+            );
+            plane.signed_distance_to_point(corner) >= T::zero()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::default::{Box3D, Frustum, Transform3D};
+    use crate::{point3, Angle};
+
+    #[test]
+    fn test_ortho() {
+        let m: Transform3D<f32> = Transform3D::ortho(-10.0, 10.0, -5.0, 5.0, 1.0, 100.0);
+        let f = Frustum::from_transform(&m);
+
+        assert!(f.contains_point3d(point3(0.0, 0.0, -1.1)));
+        assert!(f.contains_point3d(point3(-9.9, 4.9, -50.0)));
+        assert!(!f.contains_point3d(point3(0.0, 0.0, -0.5)));
+        assert!(!f.contains_point3d(point3(0.0, 0.0, -101.0)));
+        assert!(!f.contains_point3d(point3(10.5, 0.0, -50.0)));
+        assert!(!f.contains_point3d(point3(0.0, -5.5, -50.0)));
+
+        let inside = Box3D::new(point3(-1.0, -1.0, -20.0), point3(1.0, 1.0, -10.0));
+        let crossing = Box3D::new(point3(8.0, 4.0, -200.0), point3(12.0, 6.0, -90.0));
+        let behind = Box3D::new(point3(-1.0, -1.0, 0.0), point3(1.0, 1.0, 10.0));
+        let left = Box3D::new(point3(-30.0, -1.0, -20.0), point3(-11.0, 1.0, -10.0));
+        assert!(f.intersects_box3d(&inside));
+        assert!(f.intersects_box3d(&crossing));
+        assert!(!f.intersects_box3d(&behind));
+        assert!(!f.intersects_box3d(&left));
+        assert!(!f.intersects_box3d(&Box3D::new(point3(1.0, 1.0, -10.0), point3(0.0, 0.0, -20.0))));
+    }
+
+    #[test]
+    fn test_depth_conventions() {
+        let m: Transform3D<f64> = Transform3D::perspective_fov_zo(Angle::degrees(90.0), 2.0, 1.0, 10.0);
+        let f = Frustum::from_transform_zo(&m);
+        assert!(f.contains_point3d(point3(0.0, 0.0, -1.1)));
+        assert!(f.contains_point3d(point3(9.0, 4.0, -5.0)));
+        assert!(!f.contains_point3d(point3(0.0, 0.0, -0.9)));
+        assert!(!f.contains_point3d(point3(0.0, 0.0, -10.1)));
+        assert!(!f.contains_point3d(point3(11.0, 0.0, -5.0)));
+
+        // Moving the camera moves the frustum.
+        let view = Transform3D::translation(0.0, 0.0, -20.0);
+        let f = Frustum::from_transform_zo(&view.then(&m));
+        assert!(f.contains_point3d(point3(0.0, 0.0, 15.0)));
+        assert!(!f.contains_point3d(point3(0.0, 0.0, -5.0)));
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Mapping of rectangles and lines onto regular grids, see `Rect::cells_intersected`,
+//! `Rect::snap_to_grid`, `Point2D::line_to` and `Point2D::cells_to`.
+//!
+//! Cell coordinates are computed with floor division, so that the cell containing
+//! `x = -1` is cell `-1` rather than cell `0` as a truncating division would give.
+
+use crate::box2d::Box2D;
+use crate::num::{One, Zero};
+use crate::point::{point2, Point2D};
+use crate::size::Size2D;
+use crate::UnknownUnit;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::Add;
+use num_traits::{Euclid, Float, NumCast};
+
+/// Iterator over the coordinates of a range of grid cells, in row-major order.
+///
+/// Returned by `Rect::cells_intersected`.
+#[derive(Clone, Debug)]
+pub struct GridCells {
+    range: Box2D<i32, UnknownUnit>,
+    next: Point2D<i32, UnknownUnit>,
+}
+
+impl GridCells {
+    pub(crate) fn new(range: Box2D<i32, UnknownUnit>) -> Self {
+        let range = if range.is_empty() {
+            Box2D::zero()
+        } else {
+            range
+        };
+
+        GridCells {
+            range,
+            next: range.min,
+        }
+    }
+
+    /// Returns the range of cells covered by the iterator, the maximum being exclusive.
+    #[inline]
+    pub fn range(&self) -> Box2D<i32, UnknownUnit> {
+        self.range
+    }
+
+    fn remaining(&self) -> usize {
+        if self.next.y >= self.range.max.y {
+            return 0;
+        }
+        let width = (self.range.max.x - self.range.min.x) as usize;
+        let rows = (self.range.max.y - self.next.y) as usize;
+
+        rows * width - (self.next.x - self.range.min.x) as usize
+    }
+}
+
+impl Iterator for GridCells {
+    type Item = Point2D<i32, UnknownUnit>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next.y >= self.range.max.y {
+            return None;
+        }
+
+        let cell = self.next;
+        self.next.x += 1;
+        if self.next.x >= self.range.max.x {
+            self.next = point2(self.range.min.x, self.next.y + 1);
+        }
+
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.remaining();
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for GridCells {}
+
+/// Returns `floor(x / cell)`, assuming a positive cell size.
+#[inline]
+pub(crate) fn floor_div<T: Euclid>(x: T, cell: T) -> T {
+    x.div_euclid(&cell)
+}
+
+/// Returns `ceil(x / cell)`, assuming a positive cell size.
+#[inline]
+pub(crate) fn ceil_div<T>(x: T, cell: T) -> T
+where
+    T: Euclid + Zero + One + PartialOrd + Add<Output = T>,
+{
+    let q = x.div_euclid(&cell);
+    if x.rem_euclid(&cell) > T::zero() {
+        q + T::one()
+    } else {
+        q
+    }
+}
+
+/// Iterator over the integer points of a line segment, computed with Bresenham's
+/// algorithm. Both end points are included.
+///
+/// Returned by `Point2D::line_to`.
+pub struct LinePoints<U> {
+    current: Point2D<i32, U>,
+    end: Point2D<i32, U>,
+    // The distances between points of `i32` coordinates may not fit in an `i32`.
+    delta: Point2D<i64, U>,
+    step: Point2D<i32, U>,
+    error: i64,
+    done: bool,
+}
+
+impl<U> Clone for LinePoints<U> {
+    fn clone(&self) -> Self {
+        LinePoints {
+            current: self.current,
+            end: self.end,
+            delta: self.delta,
+            step: self.step,
+            error: self.error,
+            done: self.done,
+        }
+    }
+}
+
+impl<U> fmt::Debug for LinePoints<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LinePoints")
+            .field("current", &self.current)
+            .field("end", &self.end)
+            .field("delta", &self.delta)
+            .field("step", &self.step)
+            .field("error", &self.error)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<U> LinePoints<U> {
+    pub(crate) fn new(start: Point2D<i32, U>, end: Point2D<i32, U>) -> Self {
+        let dx = end.x as i64 - start.x as i64;
+        let dy = end.y as i64 - start.y as i64;
+
+        LinePoints {
+            current: start,
+            end,
+            delta: point2(dx.abs(), -dy.abs()),
+            step: point2(dx.signum() as i32, dy.signum() as i32),
+            error: dx.abs() - dy.abs(),
+            done: false,
+        }
+    }
+}
+
+impl<U> Iterator for LinePoints<U> {
+    type Item = Point2D<i32, U>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let p = self.current;
+        if p == self.end {
+            self.done = true;
+            return Some(p);
+        }
+
+        let e2 = 2 * self.error;
+        if e2 >= self.delta.y {
+            self.error += self.delta.y;
+            self.current.x += self.step.x;
+        }
+        if e2 <= self.delta.x {
+            self.error += self.delta.x;
+            self.current.y += self.step.y;
+        }
+
+        Some(p)
+    }
+}
+
+/// Iterator over the cells of a grid crossed by a line segment, using the algorithm
+/// from "A Fast Voxel Traversal Algorithm for Ray Tracing" by Amanatides and Woo.
+///
+/// Returned by `Point2D::cells_to`.
+#[derive(Clone, Debug)]
+pub struct GridTraversal<T> {
+    cell: Point2D<i32, UnknownUnit>,
+    step: Point2D<i32, UnknownUnit>,
+    t_max: Point2D<T, UnknownUnit>,
+    t_delta: Point2D<T, UnknownUnit>,
+    remaining: u64,
+}
+
+impl<T: Float> GridTraversal<T> {
+    pub(crate) fn new<U>(start: Point2D<T, U>, end: Point2D<T, U>, cell_size: Size2D<T, U>) -> Self {
+        let cell_of = |v: T, size: T| -> i32 {
+            NumCast::from((v / size).floor()).expect("grid cell coordinate out of range")
+        };
+        let start_cell: Point2D<i32, UnknownUnit> = point2(cell_of(start.x, cell_size.width), cell_of(start.y, cell_size.height));
+        let end_cell: Point2D<i32, UnknownUnit> = point2(cell_of(end.x, cell_size.width), cell_of(end.y, cell_size.height));
+
+        // Parametric distance (with t going from 0 to 1 along the segment) to the first
+        // cell boundary and between two boundaries, for one axis.
+        let axis = |from: T, to: T, cell: i32, size: T| -> (i32, T, T) {
+            let d = to - from;
+            if d > T::zero() {
+                let boundary = T::from(cell + 1).unwrap() * size;
+                (1, (boundary - from) / d, size / d)
+            } else if d < T::zero() {
+                let boundary = T::from(cell).unwrap() * size;
+                (-1, (boundary - from) / d, -size / d)
+            } else {
+                (0, T::infinity(), T::infinity())
+            }
+        };
+        let (step_x, t_max_x, t_delta_x) = axis(start.x, end.x, start_cell.x, cell_size.width);
+        let (step_y, t_max_y, t_delta_y) = axis(start.y, end.y, start_cell.y, cell_size.height);
+
+        GridTraversal {
+            cell: start_cell,
+            step: point2(step_x, step_y),
+            t_max: point2(t_max_x, t_max_y),
+            t_delta: point2(t_delta_x, t_delta_y),
+            remaining: 1 + cell_distance(start_cell.x, end_cell.x) + cell_distance(start_cell.y, end_cell.y),
+        }
+    }
+}
+
+impl<T: Float> Iterator for GridTraversal<T> {
+    type Item = Point2D<i32, UnknownUnit>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let cell = self.cell;
+        if self.remaining == 0 {
+            // Don't step past the last cell, which may be at the edge of the `i32` range.
+            return Some(cell);
+        }
+        if self.t_max.x <= self.t_max.y {
+            self.cell.x += self.step.x;
+            self.t_max.x = self.t_max.x + self.t_delta.x;
+        } else {
+            self.cell.y += self.step.y;
+            self.t_max.y = self.t_max.y + self.t_delta.y;
+        }
+
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(n) => (n, Some(n)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+/// Returns the number of cells between two cell coordinates.
+fn cell_distance(from: i32, to: i32) -> u64 {
+    (to as i64 - from as i64).unsigned_abs()
+}
@@ -11,12 +11,14 @@ use crate::point::{Point2D, Point3D};
 use crate::vector::{Vector2D, Vector3D};
 
 use crate::num::{One, Zero};
+use crate::UnknownUnit;
 
 use core::cmp::{Eq, PartialEq};
 use core::fmt;
 use core::hash::Hash;
 use core::marker::PhantomData;
 use core::ops::Div;
+use num_traits::NumCast;
 #[cfg(feature = "serde")]
 use serde;
 
@@ -113,6 +115,62 @@ impl<T, U> HomogeneousVector<T, U> {
             _unit: PhantomData,
         }
     }
+
+    /// Tag a unitless value with units.
+    #[inline]
+    pub fn from_untyped(v: HomogeneousVector<T, UnknownUnit>) -> Self {
+        HomogeneousVector::new(v.x, v.y, v.z, v.w)
+    }
+
+    /// Drop the units, preserving only the numeric value.
+    #[inline]
+    pub fn to_untyped(self) -> HomogeneousVector<T, UnknownUnit> {
+        HomogeneousVector::new(self.x, self.y, self.z, self.w)
+    }
+
+    /// Cast the unit, preserving the numeric value.
+    #[inline]
+    pub fn cast_unit<V>(self) -> HomogeneousVector<T, V> {
+        HomogeneousVector::new(self.x, self.y, self.z, self.w)
+    }
+}
+
+impl<T: Copy, U> HomogeneousVector<T, U> {
+    /// Cast into an array with x, y, z and w.
+    #[inline]
+    pub fn to_array(self) -> [T; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    /// Cast into a tuple with x, y, z and w.
+    #[inline]
+    pub fn to_tuple(self) -> (T, T, T, T) {
+        (self.x, self.y, self.z, self.w)
+    }
+}
+
+impl<T: NumCast + Copy, U> HomogeneousVector<T, U> {
+    /// Cast from one numeric representation to another, preserving the units.
+    ///
+    /// When casting from floating point to integer coordinates, the decimals are truncated
+    /// as one would expect from a simple cast.
+    #[inline]
+    pub fn cast<NewT: NumCast>(self) -> HomogeneousVector<NewT, U> {
+        self.try_cast().unwrap()
+    }
+
+    /// Fallible cast from one numeric representation to another, preserving the units.
+    pub fn try_cast<NewT: NumCast>(self) -> Option<HomogeneousVector<NewT, U>> {
+        match (
+            NumCast::from(self.x),
+            NumCast::from(self.y),
+            NumCast::from(self.z),
+            NumCast::from(self.w),
+        ) {
+            (Some(x), Some(y), Some(z), Some(w)) => Some(HomogeneousVector::new(x, y, z, w)),
+            _ => None,
+        }
+    }
 }
 
 impl<T: Copy + Div<T, Output = T> + Zero + PartialOrd, U> HomogeneousVector<T, U> {
@@ -212,4 +270,16 @@ mod homogeneous {
             HomogeneousVector::<f32, ()>::new(1.0, -2.0, -3.0, -2.0).to_point3d()
         );
     }
+
+    #[test]
+    fn casts() {
+        enum Mm {}
+
+        let v: HomogeneousVector<f32, Mm> = HomogeneousVector::new(1.5, -2.0, 3.9, 1.0);
+        assert_eq!(v.cast::<i32>().to_array(), [1, -2, 3, 1]);
+        assert_eq!(v.to_untyped().cast_unit::<Mm>(), v);
+        assert_eq!(HomogeneousVector::<f32, Mm>::from_untyped(v.to_untyped()), v);
+        assert_eq!(v.to_tuple(), (1.5, -2.0, 3.9, 1.0));
+        assert_eq!(HomogeneousVector::<f32, Mm>::new(1e20, 0.0, 0.0, 1.0).try_cast::<i32>(), None);
+    }
 }
@@ -0,0 +1,47 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/// Trait for values that can be interpolated, for example to animate them.
+///
+/// Most types interpolate each of their components linearly, like their `lerp` method.
+/// Transforms are decomposed first, so that the intermediate transforms don't distort
+/// the shapes.
+///
+/// ```rust
+/// use euclid::interpolate::Interpolate;
+/// use euclid::default::{Point2D, Size2D};
+/// use euclid::{point2, size2};
+///
+/// fn halfway<T: Interpolate<f32>>(from: &T, to: &T) -> T {
+///     from.interpolate(to, 0.5)
+/// }
+///
+/// let p: Point2D<f32> = halfway(&point2(0.0, 0.0), &point2(4.0, 2.0));
+/// assert_eq!(p, point2(2.0, 1.0));
+/// let s: Size2D<f32> = halfway(&size2(10.0, 0.0), &size2(20.0, 10.0));
+/// assert_eq!(s, size2(15.0, 5.0));
+/// ```
+pub trait Interpolate<T> {
+    /// Returns the value at `t` between this value, at `t = 0`, and `other`, at `t = 1`.
+    fn interpolate(&self, other: &Self, t: T) -> Self;
+}
+
+macro_rules! interpolate {
+    ($ty:ty) => {
+        impl Interpolate<$ty> for $ty {
+            #[inline]
+            fn interpolate(&self, other: &$ty, t: $ty) -> $ty {
+                (1.0 - t) * *self + t * *other
+            }
+        }
+    };
+}
+
+interpolate!(f32);
+interpolate!(f64);
@@ -9,11 +9,13 @@
 //! A one-dimensional length, tagged with its units.
 
 use crate::approxeq::ApproxEq;
+use crate::interpolate::Interpolate;
 use crate::num::Zero;
 use crate::scale::Scale;
+use crate::{DisplayWithUnit, UnitName};
 use crate::approxord::{max, min};
 
-use crate::num::One;
+use crate::num::{cast_component, CastError, One, Round};
 use core::cmp::Ordering;
 use core::fmt;
 use core::hash::{Hash, Hasher};
@@ -121,6 +123,16 @@ impl<T: Clone, U> Length<T, U> {
     }
 }
 
+impl<T, U> Interpolate<T> for Length<T, U>
+where
+    T: Clone + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    #[inline]
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        self.clone().lerp(other.clone(), t)
+    }
+}
+
 impl<T: PartialOrd, U> Length<T, U> {
     /// Returns minimum between this length and another length.
     #[inline]
@@ -135,6 +147,31 @@ impl<T: PartialOrd, U> Length<T, U> {
     }
 }
 
+impl<T: Round + Mul<Output = T> + Div<Output = T>, U> Length<T, U> {
+    /// Returns this length rounded such that it is a whole number of device pixels
+    /// once converted with `scale`.
+    ///
+    /// The length is converted to device pixels, rounded there and converted back, so
+    /// that content of this length lands on the pixel grid, up to the precision of `T`.
+    ///
+    /// ```rust
+    /// use euclid::{Length, Scale};
+    /// enum Css {}
+    /// enum Device {}
+    ///
+    /// let scale: Scale<f32, Css, Device> = Scale::new(1.5);
+    /// let l: Length<f32, Css> = Length::new(10.0);
+    /// let rounded = l.round_to_device_pixels(scale);
+    /// assert_eq!(rounded, Length::new(10.0));
+    /// assert_eq!(Length::<f32, Css>::new(10.5).round_to_device_pixels(scale) * scale, Length::new(16.0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn round_to_device_pixels<Dst>(self, scale: Scale<T, U, Dst>) -> Self {
+        Length::new((self.0 * scale.0).round() / scale.0)
+    }
+}
+
 impl<T: NumCast + Clone, U> Length<T, U> {
     /// Cast from one numeric representation to another, preserving the units.
     #[inline]
@@ -146,6 +183,33 @@ impl<T: NumCast + Clone, U> Length<T, U> {
     pub fn try_cast<NewT: NumCast>(self) -> Option<Length<NewT, U>> {
         NumCast::from(self.0).map(Length::new)
     }
+
+    /// Fallible cast from one numeric representation to another, preserving the units.
+    ///
+    /// Unlike `try_cast`, the error names the component that could not be cast.
+    pub fn try_cast_detailed<NewT: NumCast>(self) -> Result<Length<NewT, U>, CastError> {
+        cast_component(self.0, "0").map(Length::new)
+    }
+
+    // Convenience functions for common casts.
+
+    /// Cast into an `f32` length.
+    #[inline]
+    pub fn to_f32(self) -> Length<f32, U> {
+        self.cast()
+    }
+
+    /// Cast into an `f64` length.
+    #[inline]
+    pub fn to_f64(self) -> Length<f64, U> {
+        self.cast()
+    }
+
+    /// Cast into an `i32` length, truncating decimals if any.
+    #[inline]
+    pub fn to_i32(self) -> Length<i32, U> {
+        self.cast()
+    }
 }
 
 impl<T: fmt::Debug, U> fmt::Debug for Length<T, U> {
@@ -154,6 +218,26 @@ impl<T: fmt::Debug, U> fmt::Debug for Length<T, U> {
     }
 }
 
+impl<T: fmt::Display, U> fmt::Display for Length<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<T, U: UnitName> Length<T, U> {
+    /// Returns an adaptor displaying the length followed by the suffix of its unit.
+    pub fn display_with_unit(&self) -> DisplayWithUnit<'_, Self> {
+        DisplayWithUnit(self)
+    }
+}
+
+impl<'a, T: fmt::Display, U: UnitName> fmt::Display for DisplayWithUnit<'a, Length<T, U>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0 .0, f)?;
+        write!(f, "{}", U::SUFFIX)
+    }
+}
+
 impl<T: Default, U> Default for Length<T, U> {
     #[inline]
     fn default() -> Self {
@@ -336,6 +420,13 @@ impl<T: Zero, U> Zero for Length<T, U> {
     }
 }
 
+impl<T: One, U> One for Length<T, U> {
+    #[inline]
+    fn one() -> Self {
+        Length::new(One::one())
+    }
+}
+
 impl<U, T: ApproxEq<T>> ApproxEq<T> for Length<T, U> {
     #[inline]
     fn approx_epsilon() -> T {
@@ -351,7 +442,7 @@ impl<U, T: ApproxEq<T>> ApproxEq<T> for Length<T, U> {
 #[cfg(test)]
 mod tests {
     use super::Length;
-    use crate::num::Zero;
+    use crate::num::{One, Zero};
 
     use crate::scale::Scale;
     use core::f32::INFINITY;
@@ -362,6 +453,10 @@ mod tests {
     enum Cm {}
     enum Second {}
 
+    impl crate::UnitName for Mm {
+        const SUFFIX: &'static str = "mm";
+    }
+
     #[cfg(feature = "serde")]
     mod serde {
         use super::*;
@@ -378,6 +473,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_display() {
+        let length: Length<f32, Mm> = Length::new(12.25);
+        assert_eq!(format!("{}", length), "12.25");
+        assert_eq!(format!("{}", length.display_with_unit()), "12.25mm");
+        assert_eq!(format!("{:.1}", length.display_with_unit()), "12.2mm");
+
+        let rect: crate::Rect<i32, Mm> = crate::rect(1, 2, 30, 40);
+        assert_eq!(format!("{}", rect), "30x40 at (1, 2)");
+        assert_eq!(format!("{}", rect.display_with_unit()), "30x40mm at (1mm, 2mm)");
+        assert_eq!(format!("{}", rect.size.display_with_unit()), "30x40mm");
+        assert_eq!(format!("{}", crate::default::Length::new(3).display_with_unit()), "3");
+
+        // Lengths of any unit can be displayed without a suffix.
+        let seconds: Length<i32, Second> = Length::new(5);
+        assert_eq!(format!("{}", seconds), "5");
+    }
+
     #[test]
     fn test_clone() {
         // A cloned Length is a separate length with the state matching the
@@ -551,6 +664,8 @@ mod tests {
 
         let length_as_f32: Length<f32, Cm> = Length::new(5.0);
         assert_eq!(result, length_as_f32);
+        assert_eq!(length_as_i32.to_f32(), length_as_f32);
+        assert_eq!(Length::<f64, Cm>::new(5.7).to_i32(), length_as_i32);
     }
 
     #[test]
@@ -563,6 +678,17 @@ mod tests {
         assert!(length_5_point_0 != length_5_point_1);
     }
 
+    #[test]
+    fn test_round_to_device_pixels() {
+        let scale: Scale<f64, Mm, Cm> = Scale::new(0.1);
+        let l: Length<f64, Mm> = Length::new(123.0);
+        assert_eq!(l.round_to_device_pixels(scale), Length::new(120.0));
+
+        let scale: Scale<f64, Mm, Cm> = Scale::new(2.0);
+        assert_eq!(Length::<f64, Mm>::new(1.2).round_to_device_pixels(scale), Length::new(1.0));
+        assert_eq!(Length::<f64, Mm>::new(1.3).round_to_device_pixels(scale), Length::new(1.5));
+    }
+
     #[test]
     fn test_order() {
         let length_5_point_0: Length<f32, Cm> = Length::new(5.0);
@@ -587,6 +713,17 @@ mod tests {
         assert_eq!(result, length);
     }
 
+    #[test]
+    fn test_one() {
+        type LengthCm = Length<f32, Cm>;
+        fn unit_range<T: Zero + One>() -> (T, T) {
+            (T::zero(), T::one())
+        }
+
+        assert_eq!(unit_range::<LengthCm>(), (Length::new(0.0), Length::new(1.0)));
+        assert_eq!(LengthCm::one() * 5.0, Length::new(5.0));
+    }
+
     #[test]
     fn test_zero_division() {
         type LengthCm = Length<f32, Cm>;
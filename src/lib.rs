@@ -39,24 +39,41 @@
 //! foreign function interfaces (provided the underlying scalar type is also `repr(C)`).
 //!
 #![deny(unconditional_recursion)]
+#![deny(unsafe_code)]
+
+#[cfg(any(feature = "std", feature = "quickcheck"))]
+extern crate alloc;
 
 pub use crate::angle::Angle;
+pub use crate::au::Au;
+pub use crate::bezier::CubicBezier;
 pub use crate::box2d::Box2D;
+pub use crate::cached_transform::CachedTransform3D;
+pub use crate::frustum::Frustum;
 pub use crate::homogen::HomogeneousVector;
 pub use crate::length::Length;
+pub use crate::plane::Plane3D;
 pub use crate::point::{point2, point3, Point2D, Point3D};
-pub use crate::scale::Scale;
-pub use crate::transform2d::Transform2D;
-pub use crate::transform3d::Transform3D;
+pub use crate::projective::ProjectiveTransform2D;
+pub use crate::ray::Ray3D;
+pub use crate::scale::{Scale, Scale2D};
+pub use crate::transform2d::{DecomposedTransform2D, Transform2D, Transform2DBuilder};
+#[cfg(feature = "affine")]
+pub use crate::transform2d::AffineLayout;
+pub use crate::transform3d::{DecomposedTransform3D, Transform3D};
 pub use crate::vector::{bvec2, bvec3, BoolVector2D, BoolVector3D};
 pub use crate::vector::{vec2, vec3, Vector2D, Vector3D};
 
 pub use crate::box3d::{box3d, Box3D};
-pub use crate::rect::{rect, Rect};
+pub use crate::rect::{rect, Align, AnchorCorner, Rect, RectIntersection};
+#[cfg(feature = "std")]
+pub use crate::rect::area_of_union;
 pub use crate::rigid::RigidTransform3D;
 pub use crate::rotation::{Rotation2D, Rotation3D};
+pub use crate::segment::LineSegment2D;
 pub use crate::side_offsets::SideOffsets2D;
 pub use crate::size::{size2, size3, Size2D, Size3D};
+pub use crate::sphere::Sphere;
 pub use crate::translation::{Translation2D, Translation3D};
 pub use crate::trig::Trig;
 
@@ -64,30 +81,97 @@ pub use crate::trig::Trig;
 mod macros;
 
 mod angle;
+pub mod au;
 pub mod approxeq;
 pub mod approxord;
+mod bezier;
 mod box2d;
 mod box3d;
+mod bytes;
+mod cached_transform;
+#[cfg(feature = "std")]
+pub mod damage;
+mod display;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod frustum;
+pub mod grid;
 mod homogen;
+pub mod interpolate;
 mod length;
+pub mod logical;
 pub mod num;
+#[cfg(feature = "std")]
+pub mod packer;
+mod plane;
 mod point;
+#[cfg(feature = "std")]
+pub mod point_buffer;
+#[cfg(feature = "std")]
+pub mod polygon;
+mod projective;
+#[cfg(feature = "std")]
+pub mod quadtree;
+mod ray;
 mod rect;
 mod rigid;
 mod rotation;
+#[cfg(feature = "std")]
+pub mod rtree;
 mod scale;
+mod segment;
+#[cfg(feature = "serde")]
+pub mod serde_array;
 mod side_offsets;
 mod size;
+pub mod snap;
+mod sphere;
+pub mod spline;
 mod transform2d;
 mod transform3d;
+#[cfg(feature = "std")]
+pub mod transform_stack;
 mod translation;
 mod trig;
+pub mod validate;
 mod vector;
 
 /// The default unit.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct UnknownUnit;
 
+/// A unit of measure with a name, used as a suffix when displaying values.
+///
+/// `Length` and the point, size and rectangle types of units implementing this trait
+/// have a `display_with_unit` method, which writes the suffix after each value.
+///
+/// ```rust
+/// use euclid::{Length, UnitName};
+///
+/// pub struct CssPixel;
+/// impl UnitName for CssPixel {
+///     const SUFFIX: &'static str = "px";
+/// }
+///
+/// let width: Length<f32, CssPixel> = Length::new(12.5);
+/// assert_eq!(format!("{}", width), "12.5");
+/// assert_eq!(format!("{}", width.display_with_unit()), "12.5px");
+/// assert_eq!(format!("{}", euclid::point2::<_, CssPixel>(1, 2).display_with_unit()), "(1px, 2px)");
+/// ```
+pub trait UnitName {
+    /// The suffix written after the values of this unit.
+    const SUFFIX: &'static str;
+}
+
+impl UnitName for UnknownUnit {
+    const SUFFIX: &'static str = "";
+}
+
+/// Displays a value followed by the suffix of its unit.
+///
+/// Returned by the `display_with_unit` methods, see `UnitName`.
+pub struct DisplayWithUnit<'a, V>(&'a V);
+
 pub mod default {
     //! A set of aliases for all types, tagged with the default unknown unit.
 
@@ -103,9 +187,17 @@ pub mod default {
     pub type Rect<T> = super::Rect<T, UnknownUnit>;
     pub type Box2D<T> = super::Box2D<T, UnknownUnit>;
     pub type Box3D<T> = super::Box3D<T, UnknownUnit>;
+    pub type LineSegment2D<T> = super::LineSegment2D<T, UnknownUnit>;
+    pub type CubicBezier<T> = super::CubicBezier<T, UnknownUnit>;
+    pub type Plane3D<T> = super::Plane3D<T, UnknownUnit>;
+    pub type Frustum<T> = super::Frustum<T, UnknownUnit>;
+    pub type Sphere<T> = super::Sphere<T, UnknownUnit>;
+    pub type Ray3D<T> = super::Ray3D<T, UnknownUnit>;
     pub type SideOffsets2D<T> = super::SideOffsets2D<T, UnknownUnit>;
     pub type Transform2D<T> = super::Transform2D<T, UnknownUnit, UnknownUnit>;
     pub type Transform3D<T> = super::Transform3D<T, UnknownUnit, UnknownUnit>;
+    pub type CachedTransform3D<T> = super::CachedTransform3D<T, UnknownUnit, UnknownUnit>;
+    pub type ProjectiveTransform2D<T> = super::ProjectiveTransform2D<T, UnknownUnit, UnknownUnit>;
     pub type Rotation2D<T> = super::Rotation2D<T, UnknownUnit, UnknownUnit>;
     pub type Rotation3D<T> = super::Rotation3D<T, UnknownUnit, UnknownUnit>;
     pub type Translation2D<T> = super::Translation2D<T, UnknownUnit, UnknownUnit>;
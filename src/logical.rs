@@ -0,0 +1,365 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Geometry in flow-relative (logical) coordinates.
+//!
+//! Layout expresses positions and sizes along the inline axis, in which text runs, and
+//! the block axis, in which lines and blocks are stacked. How these axes map to the
+//! physical x and y axes depends on the [writing mode](https://drafts.csswg.org/css-writing-modes/).
+//!
+//! The logical types of this module don't carry a unit: the unit appears when converting
+//! to and from the physical types. Converting points and rectangles needs the size of
+//! the containing box, since the logical origin is not always at its top-left corner.
+//!
+//! ```rust
+//! use euclid::{rect, size2};
+//! use euclid::logical::{LogicalRect, WritingMode};
+//! use euclid::default::Rect;
+//!
+//! let container = size2(100, 50);
+//! let r: Rect<i32> = rect(10, 5, 20, 30);
+//!
+//! // In vertical-rl, the block axis goes from right to left.
+//! let logical = LogicalRect::from_physical(WritingMode::VERTICAL_RL, &r, container);
+//! assert_eq!(logical.start.inline, 5);
+//! assert_eq!(logical.start.block, 70);
+//! assert_eq!(logical.size.inline, 30);
+//! assert_eq!(logical.size.block, 20);
+//! assert_eq!(logical.to_physical(WritingMode::VERTICAL_RL, container), r);
+//! ```
+
+use crate::point::{point2, Point2D};
+use crate::rect::Rect;
+use crate::size::{size2, Size2D};
+use core::ops::{Add, Sub};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The direction in which blocks and lines are stacked.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BlockFlowDirection {
+    /// `horizontal-tb`.
+    TopToBottom,
+    /// `vertical-rl`.
+    RightToLeft,
+    /// `vertical-lr`.
+    LeftToRight,
+}
+
+/// The direction in which text runs within a line.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InlineBaseDirection {
+    /// `ltr`, which is top to bottom in vertical writing modes.
+    LeftToRight,
+    /// `rtl`, which is bottom to top in vertical writing modes.
+    RightToLeft,
+}
+
+/// The combination of the `writing-mode` and `direction` properties.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WritingMode {
+    pub block_flow: BlockFlowDirection,
+    pub inline_direction: InlineBaseDirection,
+}
+
+impl WritingMode {
+    /// `horizontal-tb`, left to right.
+    pub const HORIZONTAL_TB: Self = WritingMode::new(
+        BlockFlowDirection::TopToBottom,
+        InlineBaseDirection::LeftToRight,
+    );
+    /// `vertical-rl`, top to bottom.
+    pub const VERTICAL_RL: Self = WritingMode::new(
+        BlockFlowDirection::RightToLeft,
+        InlineBaseDirection::LeftToRight,
+    );
+    /// `vertical-lr`, top to bottom.
+    pub const VERTICAL_LR: Self = WritingMode::new(
+        BlockFlowDirection::LeftToRight,
+        InlineBaseDirection::LeftToRight,
+    );
+
+    /// Constructor.
+    #[inline]
+    pub const fn new(block_flow: BlockFlowDirection, inline_direction: InlineBaseDirection) -> Self {
+        WritingMode {
+            block_flow,
+            inline_direction,
+        }
+    }
+
+    /// Returns the same writing mode, with the inline direction reversed.
+    #[inline]
+    #[must_use]
+    pub fn rtl(self) -> Self {
+        WritingMode::new(self.block_flow, InlineBaseDirection::RightToLeft)
+    }
+
+    /// Returns true if the inline axis is vertical.
+    #[inline]
+    pub fn is_vertical(self) -> bool {
+        self.block_flow != BlockFlowDirection::TopToBottom
+    }
+
+    /// Returns true if the block axis goes from left to right.
+    #[inline]
+    pub fn is_vertical_lr(self) -> bool {
+        self.block_flow == BlockFlowDirection::LeftToRight
+    }
+
+    /// Returns true if the inline axis goes in its natural direction, that is left to
+    /// right or top to bottom.
+    #[inline]
+    pub fn is_inline_ltr(self) -> bool {
+        self.inline_direction == InlineBaseDirection::LeftToRight
+    }
+}
+
+impl Default for WritingMode {
+    fn default() -> Self {
+        WritingMode::HORIZONTAL_TB
+    }
+}
+
+/// A size in flow-relative dimensions.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct LogicalSize<T> {
+    pub inline: T,
+    pub block: T,
+}
+
+impl<T> LogicalSize<T> {
+    /// Constructor.
+    #[inline]
+    pub const fn new(inline: T, block: T) -> Self {
+        LogicalSize { inline, block }
+    }
+
+    /// Converts a physical size to the given writing mode.
+    #[inline]
+    pub fn from_physical<U>(mode: WritingMode, size: Size2D<T, U>) -> Self {
+        if mode.is_vertical() {
+            LogicalSize::new(size.height, size.width)
+        } else {
+            LogicalSize::new(size.width, size.height)
+        }
+    }
+
+    /// Converts this size to physical dimensions.
+    #[inline]
+    pub fn to_physical<U>(self, mode: WritingMode) -> Size2D<T, U> {
+        if mode.is_vertical() {
+            size2(self.block, self.inline)
+        } else {
+            size2(self.inline, self.block)
+        }
+    }
+}
+
+/// A position in flow-relative coordinates, relative to the start corner of a
+/// containing box.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct LogicalPoint<T> {
+    pub inline: T,
+    pub block: T,
+}
+
+impl<T> LogicalPoint<T> {
+    /// Constructor.
+    #[inline]
+    pub const fn new(inline: T, block: T) -> Self {
+        LogicalPoint { inline, block }
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> LogicalPoint<T> {
+    /// Converts a physical point within a box of the given size to the given
+    /// writing mode.
+    pub fn from_physical<U>(mode: WritingMode, point: Point2D<T, U>, container: Size2D<T, U>) -> Self {
+        if mode.is_vertical() {
+            LogicalPoint::new(
+                if mode.is_inline_ltr() { point.y } else { container.height - point.y },
+                if mode.is_vertical_lr() { point.x } else { container.width - point.x },
+            )
+        } else {
+            LogicalPoint::new(
+                if mode.is_inline_ltr() { point.x } else { container.width - point.x },
+                point.y,
+            )
+        }
+    }
+
+    /// Converts this point to physical coordinates within a box of the given size.
+    pub fn to_physical<U>(self, mode: WritingMode, container: Size2D<T, U>) -> Point2D<T, U> {
+        if mode.is_vertical() {
+            point2(
+                if mode.is_vertical_lr() { self.block } else { container.width - self.block },
+                if mode.is_inline_ltr() { self.inline } else { container.height - self.inline },
+            )
+        } else {
+            point2(
+                if mode.is_inline_ltr() { self.inline } else { container.width - self.inline },
+                self.block,
+            )
+        }
+    }
+}
+
+/// A rectangle in flow-relative coordinates, relative to the start corner of a
+/// containing box.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct LogicalRect<T> {
+    /// The corner of the rectangle that is at the start of both the inline and
+    /// the block axes.
+    pub start: LogicalPoint<T>,
+    pub size: LogicalSize<T>,
+}
+
+impl<T> LogicalRect<T> {
+    /// Constructor.
+    #[inline]
+    pub const fn new(start: LogicalPoint<T>, size: LogicalSize<T>) -> Self {
+        LogicalRect { start, size }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T>> LogicalRect<T> {
+    /// Returns the inline coordinate of the end edge of the rectangle.
+    #[inline]
+    pub fn inline_end(&self) -> T {
+        self.start.inline + self.size.inline
+    }
+
+    /// Returns the block coordinate of the end edge of the rectangle.
+    #[inline]
+    pub fn block_end(&self) -> T {
+        self.start.block + self.size.block
+    }
+
+    /// Converts a physical rectangle within a box of the given size to the given
+    /// writing mode.
+    pub fn from_physical<U>(mode: WritingMode, rect: &Rect<T, U>, container: Size2D<T, U>) -> Self {
+        let size = LogicalSize::from_physical(mode, rect.size);
+        let start = if mode.is_vertical() {
+            LogicalPoint::new(
+                if mode.is_inline_ltr() { rect.min_y() } else { container.height - rect.max_y() },
+                if mode.is_vertical_lr() { rect.min_x() } else { container.width - rect.max_x() },
+            )
+        } else {
+            LogicalPoint::new(
+                if mode.is_inline_ltr() { rect.min_x() } else { container.width - rect.max_x() },
+                rect.min_y(),
+            )
+        };
+
+        LogicalRect::new(start, size)
+    }
+
+    /// Converts this rectangle to physical coordinates within a box of the given size.
+    pub fn to_physical<U>(&self, mode: WritingMode, container: Size2D<T, U>) -> Rect<T, U> {
+        let origin = if mode.is_vertical() {
+            point2(
+                if mode.is_vertical_lr() { self.start.block } else { container.width - self.block_end() },
+                if mode.is_inline_ltr() { self.start.inline } else { container.height - self.inline_end() },
+            )
+        } else {
+            point2(
+                if mode.is_inline_ltr() { self.start.inline } else { container.width - self.inline_end() },
+                self.start.block,
+            )
+        };
+
+        Rect::new(origin, self.size.to_physical(mode))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default;
+    use crate::{point2, rect, size2};
+
+    const MODES: [WritingMode; 6] = [
+        WritingMode::HORIZONTAL_TB,
+        WritingMode::VERTICAL_RL,
+        WritingMode::VERTICAL_LR,
+        WritingMode::new(BlockFlowDirection::TopToBottom, InlineBaseDirection::RightToLeft),
+        WritingMode::new(BlockFlowDirection::RightToLeft, InlineBaseDirection::RightToLeft),
+        WritingMode::new(BlockFlowDirection::LeftToRight, InlineBaseDirection::RightToLeft),
+    ];
+
+    #[test]
+    fn test_round_trip() {
+        let container = size2(100, 50);
+        let r: default::Rect<i32> = rect(10, 5, 20, 30);
+        let p: default::Point2D<i32> = point2(15, 40);
+        for &mode in &MODES {
+            let logical = LogicalRect::from_physical(mode, &r, container);
+            assert_eq!(logical.to_physical(mode, container), r);
+            let logical = LogicalPoint::from_physical(mode, p, container);
+            assert_eq!(logical.to_physical(mode, container), p);
+        }
+    }
+
+    #[test]
+    fn test_rtl() {
+        let container = size2(100, 50);
+        let r: default::Rect<i32> = rect(10, 5, 20, 30);
+
+        let logical = LogicalRect::from_physical(WritingMode::HORIZONTAL_TB.rtl(), &r, container);
+        assert_eq!(logical, LogicalRect::new(LogicalPoint::new(70, 5), LogicalSize::new(20, 30)));
+
+        let logical = LogicalRect::from_physical(WritingMode::VERTICAL_LR.rtl(), &r, container);
+        assert_eq!(logical, LogicalRect::new(LogicalPoint::new(15, 10), LogicalSize::new(30, 20)));
+        assert_eq!(logical.inline_end(), 45);
+        assert_eq!(logical.block_end(), 30);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        extern crate serde_test;
+        use serde_test::{assert_tokens, Token};
+
+        assert_tokens(&WritingMode::VERTICAL_RL, &[
+            Token::Struct { name: "WritingMode", len: 2 },
+            Token::Str("block_flow"),
+            Token::UnitVariant { name: "BlockFlowDirection", variant: "RightToLeft" },
+            Token::Str("inline_direction"),
+            Token::UnitVariant { name: "InlineBaseDirection", variant: "LeftToRight" },
+            Token::StructEnd,
+        ]);
+        assert_tokens(&LogicalSize::new(1, 2), &[
+            Token::Struct { name: "LogicalSize", len: 2 },
+            Token::Str("inline"),
+            Token::I32(1),
+            Token::Str("block"),
+            Token::I32(2),
+            Token::StructEnd,
+        ]);
+    }
+}
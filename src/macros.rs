@@ -28,3 +28,117 @@ macro_rules! mint_vec {
         }
     }
 }
+
+/// Shorthand for `Point2D::new(x, y)`.
+///
+/// The unit can optionally be specified after a semicolon.
+///
+/// # Example
+///
+/// ```rust
+/// use euclid::{point2, Point2D};
+/// enum Mm {}
+///
+/// let p = point2!(1.0, 2.0);
+/// assert_eq!(p, euclid::default::Point2D::new(1.0, 2.0));
+///
+/// let p = point2!(1, 2; Mm);
+/// assert_eq!(p, Point2D::<i32, Mm>::new(1, 2));
+/// ```
+#[macro_export]
+macro_rules! point2 {
+    ($x:expr, $y:expr $(,)?) => {
+        $crate::default::Point2D::new($x, $y)
+    };
+    ($x:expr, $y:expr; $unit:ty) => {
+        $crate::Point2D::<_, $unit>::new($x, $y)
+    };
+}
+
+/// Shorthand for `Size2D::new(width, height)`.
+///
+/// The unit can optionally be specified after a semicolon.
+///
+/// # Example
+///
+/// ```rust
+/// use euclid::{size2, Size2D};
+/// enum Mm {}
+///
+/// let s = size2!(10.0, 20.0);
+/// assert_eq!(s, euclid::default::Size2D::new(10.0, 20.0));
+///
+/// let s = size2!(10, 20; Mm);
+/// assert_eq!(s, Size2D::<i32, Mm>::new(10, 20));
+/// ```
+#[macro_export]
+macro_rules! size2 {
+    ($w:expr, $h:expr $(,)?) => {
+        $crate::default::Size2D::new($w, $h)
+    };
+    ($w:expr, $h:expr; $unit:ty) => {
+        $crate::Size2D::<_, $unit>::new($w, $h)
+    };
+}
+
+/// Shorthand for `Rect::new(Point2D::new(x, y), Size2D::new(width, height))`.
+///
+/// The unit can optionally be specified after a semicolon.
+///
+/// # Example
+///
+/// ```rust
+/// use euclid::{rect, Rect};
+/// enum Mm {}
+///
+/// let r = rect!(0.0, 0.0, 10.0, 20.0);
+/// assert_eq!(r.size.height, 20.0);
+///
+/// let r = rect!(1, 2, 10, 20; Mm);
+/// assert_eq!(r, Rect::<i32, Mm>::new(euclid::point2(1, 2), euclid::size2(10, 20)));
+/// ```
+#[macro_export]
+macro_rules! rect {
+    ($x:expr, $y:expr, $w:expr, $h:expr $(,)?) => {
+        $crate::default::Rect::new(
+            $crate::default::Point2D::new($x, $y),
+            $crate::default::Size2D::new($w, $h),
+        )
+    };
+    ($x:expr, $y:expr, $w:expr, $h:expr; $unit:ty) => {
+        $crate::Rect::<_, $unit>::new($crate::Point2D::new($x, $y), $crate::Size2D::new($w, $h))
+    };
+}
+
+/// Shorthand for `SideOffsets2D::new(top, right, bottom, left)`.
+///
+/// Like the CSS shorthand properties, a single value applies to all four sides.
+/// The unit can optionally be specified after a semicolon.
+///
+/// # Example
+///
+/// ```rust
+/// use euclid::{sides, SideOffsets2D};
+/// enum Mm {}
+///
+/// let s = sides!(1, 2, 3, 4);
+/// assert_eq!(s.left, 4);
+///
+/// let s = sides!(5; Mm);
+/// assert_eq!(s, SideOffsets2D::<i32, Mm>::new(5, 5, 5, 5));
+/// ```
+#[macro_export]
+macro_rules! sides {
+    ($all:expr $(,)?) => {
+        $crate::default::SideOffsets2D::new_all_same($all)
+    };
+    ($all:expr; $unit:ty) => {
+        $crate::SideOffsets2D::<_, $unit>::new_all_same($all)
+    };
+    ($top:expr, $right:expr, $bottom:expr, $left:expr $(,)?) => {
+        $crate::default::SideOffsets2D::new($top, $right, $bottom, $left)
+    };
+    ($top:expr, $right:expr, $bottom:expr, $left:expr; $unit:ty) => {
+        $crate::SideOffsets2D::<_, $unit>::new($top, $right, $bottom, $left)
+    };
+}
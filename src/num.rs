@@ -6,9 +6,32 @@
 // <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
-//! A one-dimensional length, tagged with its units.
+//! Numeric traits used as bounds on the scalar type of the geometry types.
+//!
+//! # Custom scalar types
+//!
+//! The scalar type does not need to be a primitive: fixed-point types such as
+//! [`Au`](crate::Au) or Q-format numbers work as long as they implement the traits
+//! the operations they are used with require. The most common ones are:
+//!
+//! - `Copy` and the arithmetic operators of `core::ops` (`Add`, `Sub`, `Mul`, `Div`
+//!   and `Neg`) with `Output = Self`,
+//! - `num_traits::Zero` and `num_traits::One`, which provide this module's [`Zero`]
+//!   and [`One`], for constructors such as `identity()` and `translation()`,
+//! - `PartialEq` and `PartialOrd`, for comparisons and `inverse()`,
+//! - [`Round`], [`Floor`] and [`Ceil`], for the rounding methods,
+//! - [`Trig`](crate::Trig), for rotations, skews and `Angle` conversions,
+//! - `num_traits::NumCast`, for casts between scalar types.
+//!
+//! With the `half` feature, `half::f16` implements these traits (except `Trig`), so
+//! geometry can be stored in half precision, for example in vertex buffers.
+//!
+//! Only a few methods, for example `Transform2D::decompose`, require
+//! `num_traits::Float`.
 
+use core::fmt;
 use num_traits;
+use num_traits::NumCast;
 
 // Euclid has its own Zero and One traits instead of of using the num_traits equivalents.
 // Unfortunately, num_traits::Zero requires Add, which opens a bag of sad things:
@@ -53,6 +76,16 @@ pub trait Round: Copy {
     #[must_use]
     fn round(self) -> Self;
 }
+/// Defines the nearest integer value to the original value, halves rounding to the
+/// even integer.
+///
+/// Unlike `Round`, which always rounds halves up, this doesn't favor any direction, so
+/// repeatedly rounding scaled values doesn't accumulate a drift.
+pub trait RoundHalfEven: Copy {
+    /// Rounds to the nearest integer value, halves rounding to the even integer.
+    #[must_use]
+    fn round_half_even(self) -> Self;
+}
 /// Defines the biggest integer equal or lower than the original value.
 pub trait Floor: Copy {
     /// Rounds to the biggest integer equal or lower than the original value.
@@ -70,6 +103,52 @@ pub trait Ceil: Copy {
     fn ceil(self) -> Self;
 }
 
+/// How fractional values are snapped to integers by the `cast_with` methods.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Rounds towards negative infinity (see [`Floor`]).
+    Floor,
+    /// Rounds towards positive infinity (see [`Ceil`]).
+    Ceil,
+    /// Rounds to the nearest integer, halves rounding up (see [`Round`]).
+    Round,
+    /// Rounds to the nearest integer, halves rounding to the even integer (see
+    /// [`RoundHalfEven`]).
+    RoundHalfEven,
+    /// Rounds such that the result covers the original value: rectangles get their
+    /// minimum edges floored and their maximum edges ceiled, points are floored and
+    /// sizes are ceiled.
+    RoundOut,
+}
+
+/// Error returned by the `try_cast_detailed` methods when a component cannot be
+/// represented in the target scalar type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CastError {
+    component: &'static str,
+}
+
+impl CastError {
+    /// The name of the first component that failed to cast, for example `"x"` or
+    /// `"size.height"`.
+    pub fn component(&self) -> &'static str {
+        self.component
+    }
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot cast component `{}` to the target type", self.component)
+    }
+}
+
+pub(crate) fn cast_component<T: NumCast, NewT: NumCast>(
+    value: T,
+    component: &'static str,
+) -> Result<NewT, CastError> {
+    NumCast::from(value).ok_or(CastError { component })
+}
+
 macro_rules! num_int {
     ($ty:ty) => {
         impl Round for $ty {
@@ -78,6 +157,12 @@ macro_rules! num_int {
                 self
             }
         }
+        impl RoundHalfEven for $ty {
+            #[inline]
+            fn round_half_even(self) -> $ty {
+                self
+            }
+        }
         impl Floor for $ty {
             #[inline]
             fn floor(self) -> $ty {
@@ -101,6 +186,26 @@ macro_rules! num_float {
                 (self + 0.5).floor()
             }
         }
+        impl RoundHalfEven for $ty {
+            #[inline]
+            fn round_half_even(self) -> $ty {
+                // Unlike `self + 0.5`, the fractional part is computed exactly.
+                let floor = num_traits::Float::floor(self);
+                let fract = self - floor;
+                if fract < 0.5 {
+                    return floor;
+                }
+                if fract > 0.5 {
+                    return floor + 1.0;
+                }
+                let is_even = num_traits::Float::floor(floor * 0.5) * 2.0 == floor;
+                if is_even {
+                    floor
+                } else {
+                    floor + 1.0
+                }
+            }
+        }
         impl Floor for $ty {
             #[inline]
             fn floor(self) -> $ty {
@@ -116,13 +221,48 @@ macro_rules! num_float {
     };
 }
 
+num_int!(i8);
+num_int!(u8);
 num_int!(i16);
 num_int!(u16);
 num_int!(i32);
 num_int!(u32);
 num_int!(i64);
 num_int!(u64);
+num_int!(i128);
+num_int!(u128);
 num_int!(isize);
 num_int!(usize);
+num_int!(crate::au::Au);
 num_float!(f32);
 num_float!(f64);
+
+// Half precision values are rounded in single precision, which represents them exactly.
+#[cfg(feature = "half")]
+impl Round for half::f16 {
+    #[inline]
+    fn round(self) -> half::f16 {
+        half::f16::from_f32(Round::round(self.to_f32()))
+    }
+}
+#[cfg(feature = "half")]
+impl RoundHalfEven for half::f16 {
+    #[inline]
+    fn round_half_even(self) -> half::f16 {
+        half::f16::from_f32(RoundHalfEven::round_half_even(self.to_f32()))
+    }
+}
+#[cfg(feature = "half")]
+impl Floor for half::f16 {
+    #[inline]
+    fn floor(self) -> half::f16 {
+        half::f16::from_f32(Floor::floor(self.to_f32()))
+    }
+}
+#[cfg(feature = "half")]
+impl Ceil for half::f16 {
+    #[inline]
+    fn ceil(self) -> half::f16 {
+        half::f16::from_f32(Ceil::ceil(self.to_f32()))
+    }
+}
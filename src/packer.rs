@@ -0,0 +1,415 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rectangle packing for texture atlases.
+//!
+//! ```rust
+//! use euclid::{rect, size2};
+//! use euclid::packer::RectPacker;
+//!
+//! enum AtlasSpace {}
+//!
+//! let mut atlas = RectPacker::<AtlasSpace>::new(size2(256, 256));
+//!
+//! let glyph = atlas.allocate(size2(10, 12)).unwrap();
+//! assert_eq!(glyph.rect, rect(0, 0, 10, 12));
+//!
+//! let image = atlas.allocate(size2(100, 40)).unwrap();
+//! assert_eq!(image.rect, rect(0, 12, 100, 40));
+//!
+//! atlas.deallocate(glyph.id);
+//! assert_eq!(atlas.stats().allocation_count, 1);
+//! ```
+
+use crate::rect::{rect, Rect};
+use crate::size::Size2D;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A handle to an allocation of a `RectPacker`.
+///
+/// Handles of deallocated rectangles stay invalid when their slot is reused by a later allocation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AllocId {
+    index: usize,
+    generation: u32,
+}
+
+/// An allocation of a `RectPacker`, with the number of times it was freed.
+struct Entry<U> {
+    generation: u32,
+    rect: Option<Rect<u32, U>>,
+}
+
+/// A rectangle allocated by a `RectPacker`.
+pub struct Allocation<U> {
+    pub id: AllocId,
+    pub rect: Rect<u32, U>,
+}
+
+impl<U> Copy for Allocation<U> {}
+
+impl<U> Clone for Allocation<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<U> PartialEq for Allocation<U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.rect == other.rect
+    }
+}
+
+impl<U> Eq for Allocation<U> {}
+
+impl<U> fmt::Debug for Allocation<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Allocation")
+            .field("id", &self.id)
+            .field("rect", &self.rect)
+            .finish()
+    }
+}
+
+/// Occupancy statistics of a `RectPacker`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PackerStats {
+    /// Number of live allocations.
+    pub allocation_count: usize,
+    /// Total area of the live allocations.
+    pub allocated_area: u64,
+    /// Area that isn't allocated.
+    pub free_area: u64,
+    /// The part of `free_area` that is inside of shelves, and is only usable by
+    /// requests that fit in the existing shelves.
+    pub fragmented_area: u64,
+}
+
+impl PackerStats {
+    /// Returns the proportion of the free area that is fragmented, between 0 and 1.
+    pub fn fragmentation(&self) -> f32 {
+        if self.free_area == 0 {
+            return 0.0;
+        }
+
+        self.fragmented_area as f32 / self.free_area as f32
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Slot {
+    x: u32,
+    width: u32,
+    used: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    slots: Vec<Slot>,
+}
+
+/// A shelf packer allocating rectangles inside of a fixed size atlas.
+///
+/// The atlas is split into horizontal shelves, stacked from the top, each of which
+/// is filled from left to right. Requests go to the shortest shelf they fit in, so
+/// that items of similar heights get grouped together. Deallocated space is reused
+/// by later requests, and empty shelves at the bottom of the atlas are reclaimed.
+pub struct RectPacker<U> {
+    size: Size2D<u32, U>,
+    shelves: Vec<Shelf>,
+    allocations: Vec<Entry<U>>,
+    free_list: Vec<usize>,
+    allocated_area: u64,
+    allocation_count: usize,
+}
+
+impl<U> RectPacker<U> {
+    /// Creates an empty packer for an atlas of the given size.
+    pub fn new(size: Size2D<u32, U>) -> Self {
+        RectPacker {
+            size,
+            shelves: Vec::new(),
+            allocations: Vec::new(),
+            free_list: Vec::new(),
+            allocated_area: 0,
+            allocation_count: 0,
+        }
+    }
+
+    /// Returns the size of the atlas.
+    #[inline]
+    pub fn size(&self) -> Size2D<u32, U> {
+        self.size
+    }
+
+    /// Returns true if there are no live allocations.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.allocation_count == 0
+    }
+
+    /// Allocates a rectangle of the requested size, or returns `None` if there is no
+    /// room left for it or if the size is empty.
+    pub fn allocate(&mut self, size: Size2D<u32, U>) -> Option<Allocation<U>> {
+        if size.is_empty() || size.width > self.size.width || size.height > self.size.height {
+            return None;
+        }
+
+        // Avoid putting small items in much taller shelves as long as new shelves can
+        // be opened.
+        let (shelf_index, slot_index) = match self.find_slot(size, Some(size.height.saturating_mul(2))) {
+            Some(found) => found,
+            None => match self.add_shelf(size.height) {
+                Some(shelf_index) => (shelf_index, 0),
+                None => self.find_slot(size, None)?,
+            },
+        };
+
+        let shelf = &mut self.shelves[shelf_index];
+        let slot = shelf.slots[slot_index];
+        if slot.width > size.width {
+            shelf.slots.insert(
+                slot_index + 1,
+                Slot {
+                    x: slot.x + size.width,
+                    width: slot.width - size.width,
+                    used: false,
+                },
+            );
+        }
+        shelf.slots[slot_index] = Slot {
+            x: slot.x,
+            width: size.width,
+            used: true,
+        };
+
+        let r = rect(slot.x, shelf.y, size.width, size.height);
+        let index = match self.free_list.pop() {
+            Some(index) => {
+                self.allocations[index].rect = Some(r);
+                index
+            }
+            None => {
+                self.allocations.push(Entry { generation: 0, rect: Some(r) });
+                self.allocations.len() - 1
+            }
+        };
+        let id = AllocId { index, generation: self.allocations[index].generation };
+        self.allocated_area += size.width as u64 * size.height as u64;
+        self.allocation_count += 1;
+
+        Some(Allocation { id, rect: r })
+    }
+
+    /// Releases an allocation, returning false if it was not live.
+    pub fn deallocate(&mut self, id: AllocId) -> bool {
+        let entry = match self.allocations.get_mut(id.index) {
+            Some(entry) if entry.generation == id.generation => entry,
+            _ => return false,
+        };
+        let r = match entry.rect.take() {
+            Some(r) => r,
+            None => return false,
+        };
+        entry.generation = entry.generation.wrapping_add(1);
+        self.free_list.push(id.index);
+        self.allocated_area -= r.size.width as u64 * r.size.height as u64;
+        self.allocation_count -= 1;
+
+        let shelf = self
+            .shelves
+            .iter_mut()
+            .find(|shelf| shelf.y == r.origin.y)
+            .expect("allocation outside of any shelf");
+        let mut index = shelf
+            .slots
+            .iter()
+            .position(|slot| slot.x == r.origin.x)
+            .expect("allocation outside of any slot");
+        shelf.slots[index].used = false;
+
+        if index + 1 < shelf.slots.len() && !shelf.slots[index + 1].used {
+            shelf.slots[index].width += shelf.slots.remove(index + 1).width;
+        }
+        if index > 0 && !shelf.slots[index - 1].used {
+            index -= 1;
+            shelf.slots[index].width += shelf.slots.remove(index + 1).width;
+        }
+
+        while self.shelves.last().map_or(false, |shelf| shelf.slots.len() == 1 && !shelf.slots[0].used) {
+            self.shelves.pop();
+        }
+
+        true
+    }
+
+    /// Returns the rectangle of a live allocation.
+    pub fn get(&self, id: AllocId) -> Option<Rect<u32, U>> {
+        match self.allocations.get(id.index) {
+            Some(entry) if entry.generation == id.generation => entry.rect,
+            _ => None,
+        }
+    }
+
+    /// Removes all allocations.
+    pub fn clear(&mut self) {
+        self.shelves.clear();
+        self.free_list.clear();
+        // Slots are kept so that their generation invalidates the outstanding ids.
+        for (index, entry) in self.allocations.iter_mut().enumerate().rev() {
+            if entry.rect.take().is_some() {
+                entry.generation = entry.generation.wrapping_add(1);
+            }
+            self.free_list.push(index);
+        }
+        self.allocated_area = 0;
+        self.allocation_count = 0;
+    }
+
+    /// Returns occupancy statistics of the atlas.
+    pub fn stats(&self) -> PackerStats {
+        let total_area = self.size.width as u64 * self.size.height as u64;
+        let shelf_area: u64 = self
+            .shelves
+            .iter()
+            .map(|shelf| shelf.height as u64 * self.size.width as u64)
+            .sum();
+
+        PackerStats {
+            allocation_count: self.allocation_count,
+            allocated_area: self.allocated_area,
+            free_area: total_area - self.allocated_area,
+            fragmented_area: shelf_area - self.allocated_area,
+        }
+    }
+
+    fn find_slot(&self, size: Size2D<u32, U>, max_height: Option<u32>) -> Option<(usize, usize)> {
+        let mut best: Option<(usize, usize)> = None;
+        for (shelf_index, shelf) in self.shelves.iter().enumerate() {
+            if shelf.height < size.height || max_height.map_or(false, |max| shelf.height > max) {
+                continue;
+            }
+            if let Some((best_shelf, _)) = best {
+                if self.shelves[best_shelf].height <= shelf.height {
+                    continue;
+                }
+            }
+            if let Some(slot_index) = shelf
+                .slots
+                .iter()
+                .position(|slot| !slot.used && slot.width >= size.width)
+            {
+                best = Some((shelf_index, slot_index));
+            }
+        }
+
+        best
+    }
+
+    fn add_shelf(&mut self, height: u32) -> Option<usize> {
+        let y = self.shelves.last().map_or(0, |shelf| shelf.y + shelf.height);
+        if self.size.height - y < height {
+            return None;
+        }
+
+        self.shelves.push(Shelf {
+            y,
+            height,
+            slots: vec![Slot {
+                x: 0,
+                width: self.size.width,
+                used: false,
+            }],
+        });
+
+        Some(self.shelves.len() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default::Box2D;
+    use crate::size2;
+    use crate::UnknownUnit;
+
+    fn assert_disjoint(rects: &[Rect<u32, UnknownUnit>]) {
+        for (i, a) in rects.iter().enumerate() {
+            for b in &rects[i + 1..] {
+                assert!(!a.intersects(b), "{:?} and {:?} overlap", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fill_atlas() {
+        let mut packer = RectPacker::<UnknownUnit>::new(size2(100, 100));
+        let bounds = Box2D::from_size(packer.size());
+
+        let mut rects = Vec::new();
+        while let Some(alloc) = packer.allocate(size2(10, 10)) {
+            rects.push(alloc.rect);
+        }
+        assert_eq!(rects.len(), 100);
+        assert!(rects.iter().all(|r| bounds.contains_box(&r.to_box2d())));
+        assert_disjoint(&rects);
+
+        let stats = packer.stats();
+        assert_eq!(stats.allocated_area, 10_000);
+        assert_eq!(stats.free_area, 0);
+        assert_eq!(stats.fragmentation(), 0.0);
+    }
+
+    #[test]
+    fn test_deallocate() {
+        let mut packer = RectPacker::<UnknownUnit>::new(size2(64, 64));
+
+        let a = packer.allocate(size2(32, 16)).unwrap();
+        let b = packer.allocate(size2(32, 16)).unwrap();
+        let c = packer.allocate(size2(20, 30)).unwrap();
+        assert_eq!(b.rect, rect(32, 0, 32, 16));
+        assert_eq!(c.rect, rect(0, 16, 20, 30));
+        assert!(packer.allocate(size2(64, 20)).is_none());
+
+        // Space freed in the first shelf is merged and reused.
+        assert!(packer.deallocate(a.id));
+        assert!(!packer.deallocate(a.id));
+        assert!(packer.deallocate(b.id));
+        let d = packer.allocate(size2(60, 10)).unwrap();
+        assert_eq!(d.rect, rect(0, 0, 60, 10));
+        assert_eq!(packer.get(d.id), Some(d.rect));
+        assert_eq!(packer.get(a.id), None);
+
+        // Stale ids don't release the allocation that reused their slot.
+        assert_eq!(packer.get(b.id), None);
+        assert!(!packer.deallocate(b.id));
+        assert_eq!(packer.get(d.id), Some(d.rect));
+
+        let stats = packer.stats();
+        assert_eq!(stats.allocation_count, 2);
+        assert_eq!(stats.allocated_area, 600 + 600);
+        assert_eq!(stats.fragmented_area, 46 * 64 - 1200);
+
+        // The bottom shelf is reclaimed once empty.
+        assert!(packer.deallocate(c.id));
+        assert_eq!(packer.allocate(size2(64, 48)).unwrap().rect, rect(0, 16, 64, 48));
+
+        packer.clear();
+        assert!(packer.is_empty());
+        let e = packer.allocate(size2(10, 10)).unwrap();
+        assert!(!packer.deallocate(d.id));
+        assert_eq!(packer.get(e.id), Some(e.rect));
+        assert_eq!(packer.allocate(size2(0, 10)), None);
+        assert_eq!(packer.allocate(size2(65, 10)), None);
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::point::Point3D;
+use crate::vector::Vector3D;
+
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Mul, Neg};
+
+/// A plane in 3d space, optionally tagged with a unit.
+///
+/// The plane is the set of points `p` for which `normal.dot(p) + d` is zero. Points
+/// for which it is positive are in front of the plane, on the side the normal points to.
+///
+/// When the normal is normalized, `d` is the signed distance from the plane to the
+/// origin, along the normal.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct Plane3D<T, U> {
+    pub normal: Vector3D<T, U>,
+    pub d: T,
+}
+
+impl<T: Copy, U> Copy for Plane3D<T, U> {}
+
+impl<T: Clone, U> Clone for Plane3D<T, U> {
+    fn clone(&self) -> Self {
+        Plane3D {
+            normal: self.normal.clone(),
+            d: self.d.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for Plane3D<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.normal == other.normal && self.d == other.d
+    }
+}
+
+impl<T: Eq, U> Eq for Plane3D<T, U> {}
+
+impl<T: Hash, U> Hash for Plane3D<T, U> {
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        self.normal.hash(h);
+        self.d.hash(h);
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for Plane3D<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Plane3D")
+            .field("normal", &self.normal)
+            .field("d", &self.d)
+            .finish()
+    }
+}
+
+impl<T, U> Plane3D<T, U> {
+    /// Constructor.
+    #[inline]
+    pub const fn new(normal: Vector3D<T, U>, d: T) -> Self {
+        Plane3D { normal, d }
+    }
+}
+
+impl<T, U> Plane3D<T, U>
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    /// Creates the plane going through a point, facing the direction of `normal`.
+    #[inline]
+    pub fn from_point_and_normal(point: Point3D<T, U>, normal: Vector3D<T, U>) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        Plane3D::new(normal, -normal.dot(point.to_vector()))
+    }
+
+    /// Returns the signed distance from the plane to a point, positive if the point is
+    /// in front of the plane.
+    ///
+    /// The result is only a distance if the normal is normalized, otherwise it is
+    /// scaled by the length of the normal.
+    #[inline]
+    pub fn signed_distance_to_point(&self, p: Point3D<T, U>) -> T {
+        self.normal.dot(p.to_vector()) + self.d
+    }
+}
+
+impl<T: Float, U> Plane3D<T, U> {
+    /// Returns the same plane with a normal of length one.
+    ///
+    /// The result contains NaNs if the normal has a length of zero.
+    #[inline]
+    #[must_use]
+    pub fn normalize(&self) -> Self {
+        let length = self.normal.length();
+        Plane3D::new(self.normal / length, self.d / length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::default::Plane3D;
+    use crate::{point3, vec3};
+
+    #[test]
+    fn test_signed_distance() {
+        let p = Plane3D::from_point_and_normal(point3(0.0, 0.0, 2.0), vec3(0.0, 0.0, 4.0));
+        assert_eq!(p.signed_distance_to_point(point3(5.0, 3.0, 5.0)), 12.0);
+
+        let p = p.normalize();
+        assert_eq!(p, Plane3D::new(vec3(0.0, 0.0, 1.0), -2.0));
+        assert_eq!(p.signed_distance_to_point(point3(5.0, 3.0, 5.0)), 3.0);
+        assert_eq!(p.signed_distance_to_point(point3(5.0, 3.0, 1.0)), -1.0);
+    }
+}
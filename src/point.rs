@@ -7,17 +7,20 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use super::UnknownUnit;
+use super::{DisplayWithUnit, UnitName, UnknownUnit};
 use crate::approxeq::ApproxEq;
 use crate::approxord::{max, min};
+use crate::interpolate::Interpolate;
 use crate::length::Length;
+use crate::grid::{GridTraversal, LinePoints};
 use crate::num::*;
 use crate::scale::Scale;
 use crate::size::{Size2D, Size3D};
 use crate::vector::{vec2, vec3, Vector2D, Vector3D};
-use core::cmp::{Eq, PartialEq};
+use core::cmp::{Eq, Ordering, PartialEq};
 use core::fmt;
 use core::hash::Hash;
+use core::iter::Sum;
 use core::marker::PhantomData;
 use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 #[cfg(feature = "mint")]
@@ -93,6 +96,33 @@ where
         })
     }
 }
+
+#[cfg(feature = "rand")]
+impl<T, U> rand::distributions::Distribution<Point2D<T, U>> for rand::distributions::Standard
+where
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Point2D<T, U> {
+        Point2D::new(rng.gen(), rng.gen())
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<T, U> quickcheck::Arbitrary for Point2D<T, U>
+where
+    T: quickcheck::Arbitrary,
+    U: 'static,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Point2D::new(T::arbitrary(g), T::arbitrary(g))
+    }
+
+    fn shrink(&self) -> alloc::boxed::Box<dyn Iterator<Item = Self>> {
+        let shrunk = quickcheck::Arbitrary::shrink(&(self.x.clone(), self.y.clone()));
+        alloc::boxed::Box::new(shrunk.map(|(x, y)| Point2D::new(x, y)))
+    }
+}
+
 impl<T, U> Eq for Point2D<T, U> where T: Eq {}
 
 impl<T, U> PartialEq for Point2D<T, U>
@@ -104,6 +134,23 @@ where
     }
 }
 
+/// Points are ordered lexicographically, by `x` and then by `y`.
+///
+/// This is only implemented for totally ordered scalars such as integers, and is
+/// meant to give a deterministic order to collections of points rather than a
+/// geometric meaning.
+impl<T: Ord, U> PartialOrd for Point2D<T, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord, U> Ord for Point2D<T, U> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.x.cmp(&other.x).then_with(|| self.y.cmp(&other.y))
+    }
+}
+
 impl<T, U> Hash for Point2D<T, U>
 where
     T: Hash,
@@ -122,6 +169,35 @@ impl<T: fmt::Debug, U> fmt::Debug for Point2D<T, U> {
     }
 }
 
+impl<T: fmt::Display, U> Point2D<T, U> {
+    pub(crate) fn fmt_with_suffix(&self, f: &mut fmt::Formatter, suffix: &str) -> fmt::Result {
+        write!(f, "(")?;
+        fmt::Display::fmt(&self.x, f)?;
+        write!(f, "{}, ", suffix)?;
+        fmt::Display::fmt(&self.y, f)?;
+        write!(f, "{})", suffix)
+    }
+}
+
+impl<T: fmt::Display, U> fmt::Display for Point2D<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_suffix(f, "")
+    }
+}
+
+impl<T, U: UnitName> Point2D<T, U> {
+    /// Returns an adaptor displaying the point with the suffix of its unit after each value.
+    pub fn display_with_unit(&self) -> DisplayWithUnit<'_, Self> {
+        DisplayWithUnit(self)
+    }
+}
+
+impl<'a, T: fmt::Display, U: UnitName> fmt::Display for DisplayWithUnit<'a, Point2D<T, U>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_with_suffix(f, U::SUFFIX)
+    }
+}
+
 impl<T: Default, U> Default for Point2D<T, U> {
     fn default() -> Self {
         Point2D::new(Default::default(), Default::default())
@@ -183,6 +259,74 @@ impl<T, U> Point2D<T, U> {
     }
 }
 
+#[cfg(feature = "slice-cast")]
+#[allow(unsafe_code)]
+impl<T, U> Point2D<T, U> {
+    /// Views a slice of points as a slice of their components, 2 per point.
+    ///
+    /// The points are not copied: this only changes the type of the slice, which is
+    /// possible because `Point2D` is `#[repr(C)]` and only contains components of type `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of components overflows a `usize`, which can only happen
+    /// if `T` is zero-sized.
+    ///
+    /// ```rust
+    /// use euclid::default::Point2D;
+    /// use euclid::point2;
+    ///
+    /// let points: [Point2D<f32>; 2] = [point2(1.0, 2.0), point2(3.0, 4.0)];
+    /// let components = Point2D::slice_as_components(&points);
+    /// assert_eq!(components, &[1.0, 2.0, 3.0, 4.0]);
+    /// assert_eq!(Point2D::slice_from_components(components), Some(&points[..]));
+    /// ```
+    #[inline]
+    pub fn slice_as_components(points: &[Self]) -> &[T] {
+        let len = points.len().checked_mul(2).expect("too many components");
+        // Safety: `Self` has the size of `[T; 2]` and the alignment of `T`, and `_unit`
+        // is zero-sized.
+        unsafe { core::slice::from_raw_parts(points.as_ptr() as *const T, len) }
+    }
+
+    /// Mutable version of `slice_as_components`.
+    #[inline]
+    pub fn slice_as_components_mut(points: &mut [Self]) -> &mut [T] {
+        let len = points.len().checked_mul(2).expect("too many components");
+        // Safety: see `slice_as_components`.
+        unsafe { core::slice::from_raw_parts_mut(points.as_mut_ptr() as *mut T, len) }
+    }
+
+    /// Views a slice of components as a slice of points, without copying.
+    ///
+    /// Returns `None` if the length of `components` is not a multiple of 2.
+    #[inline]
+    pub fn slice_from_components(components: &[T]) -> Option<&[Self]> {
+        if components.len() % 2 != 0 {
+            return None;
+        }
+        // Safety: see `slice_as_components`.
+        Some(unsafe {
+            core::slice::from_raw_parts(components.as_ptr() as *const Self, components.len() / 2)
+        })
+    }
+
+    /// Mutable version of `slice_from_components`.
+    #[inline]
+    pub fn slice_from_components_mut(components: &mut [T]) -> Option<&mut [Self]> {
+        if components.len() % 2 != 0 {
+            return None;
+        }
+        // Safety: see `slice_as_components`.
+        Some(unsafe {
+            core::slice::from_raw_parts_mut(
+                components.as_mut_ptr() as *mut Self,
+                components.len() / 2,
+            )
+        })
+    }
+}
+
 impl<T: Copy, U> Point2D<T, U> {
     /// Create a 3d point from this one, using the specified z value.
     #[inline]
@@ -202,6 +346,26 @@ impl<T: Copy, U> Point2D<T, U> {
         }
     }
 
+    /// Returns this point expressed relative to another origin, that is the point
+    /// minus the given origin, in the same unit.
+    ///
+    /// This is typically used to convert coordinates from a frame to one of its
+    /// descendants, `origin` being the position of the descendant in the frame.
+    ///
+    /// ```rust
+    /// # use euclid::default::Point2D;
+    /// # use euclid::point2;
+    /// let p: Point2D<i32> = point2(15, 20);
+    /// assert_eq!(p.relative_to(point2(10, 5)), point2(5, 15));
+    /// ```
+    #[inline]
+    pub fn relative_to(self, origin: Self) -> Self
+    where
+        T: Sub<Output = T>,
+    {
+        point2(self.x - origin.x, self.y - origin.y)
+    }
+
     /// Swap x and y.
     ///
     /// # Example
@@ -318,6 +482,46 @@ impl<T: Copy, U> Point2D<T, U> {
         point2(self.x.round(), self.y.round())
     }
 
+    /// Rounds each component to the nearest integer value, halves rounding to the even
+    /// integer.
+    ///
+    /// ```rust
+    /// # use euclid::point2;
+    /// enum Mm {}
+    ///
+    /// assert_eq!(point2::<_, Mm>(0.5, -2.5).round_half_even(), point2::<_, Mm>(0.0, -2.0))
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn round_half_even(self) -> Self
+    where
+        T: RoundHalfEven,
+    {
+        point2(self.x.round_half_even(), self.y.round_half_even())
+    }
+
+    /// Rounds each component to the nearest integer value like `round`, and returns the
+    /// rounded point along with the rounding error, that is the vector from the rounded
+    /// point to the original one.
+    ///
+    /// Carrying the error over to the next value to round avoids accumulating it, for
+    /// example when placing a sequence of boxes on the pixel grid.
+    ///
+    /// ```rust
+    /// # use euclid::{point2, vec2};
+    /// # use euclid::default::Point2D;
+    /// let p: Point2D<f32> = point2(1.25, -0.75);
+    /// assert_eq!(p.round_with_error(), (point2(1.0, -1.0), vec2(0.25, 0.25)));
+    /// ```
+    #[inline]
+    pub fn round_with_error(self) -> (Self, Vector2D<T, U>)
+    where
+        T: Round + Sub<Output = T>,
+    {
+        let rounded = self.round();
+        (rounded, self - rounded)
+    }
+
     /// Rounds each component to the smallest integer equal or greater than the original value.
     ///
     /// This behavior is preserved for negative values (unlike the basic cast).
@@ -383,6 +587,16 @@ impl<T: Copy, U> Point2D<T, U> {
     }
 }
 
+impl<T, U> Interpolate<T> for Point2D<T, U>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    #[inline]
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
 impl<T: PartialOrd, U> Point2D<T, U> {
     #[inline]
     pub fn min(self, other: Self) -> Self {
@@ -430,6 +644,46 @@ impl<T: NumCast + Copy, U> Point2D<T, U> {
         }
     }
 
+    /// Fallible cast from one numeric representation to another, preserving the units.
+    ///
+    /// Unlike `try_cast`, the error names the first component that could not be cast.
+    pub fn try_cast_detailed<NewT: NumCast>(self) -> Result<Point2D<NewT, U>, CastError> {
+        Ok(point2(
+            cast_component(self.x, "x")?,
+            cast_component(self.y, "y")?,
+        ))
+    }
+
+    /// Cast from one numeric representation to another, preserving the units and
+    /// rounding the components according to `mode` first.
+    ///
+    /// `RoundingMode::RoundOut` floors the components, consistently with the origin
+    /// of a rounded out rectangle.
+    ///
+    /// ```rust
+    /// # use euclid::point2;
+    /// # use euclid::num::RoundingMode;
+    /// enum Mm {}
+    ///
+    /// let p = point2::<_, Mm>(-0.1, 1.6);
+    /// assert_eq!(p.cast_with::<i32>(RoundingMode::Floor), point2(-1, 1));
+    /// assert_eq!(p.cast_with::<i32>(RoundingMode::Ceil), point2(0, 2));
+    /// assert_eq!(p.cast_with::<i32>(RoundingMode::Round), point2(0, 2));
+    /// ```
+    #[inline]
+    pub fn cast_with<NewT: NumCast>(self, mode: RoundingMode) -> Point2D<NewT, U>
+    where
+        T: Round + RoundHalfEven + Floor + Ceil,
+    {
+        match mode {
+            RoundingMode::Floor | RoundingMode::RoundOut => self.floor(),
+            RoundingMode::Ceil => self.ceil(),
+            RoundingMode::Round => self.round(),
+            RoundingMode::RoundHalfEven => self.round_half_even(),
+        }
+        .cast()
+    }
+
     // Convenience functions for common casts
 
     /// Cast into an `f32` point.
@@ -493,6 +747,97 @@ impl<T: Float, U> Point2D<T, U> {
     }
 }
 
+impl<U> Point2D<i32, U> {
+    /// Returns an iterator over the integer points of the line segment going from this
+    /// point to `other`, both included, using Bresenham's algorithm.
+    ///
+    /// ```rust
+    /// # use euclid::point2;
+    /// # use euclid::default::Point2D;
+    /// let from: Point2D<i32> = point2(0, 0);
+    /// let points: Vec<_> = from.line_to(point2(4, 2)).collect();
+    /// assert_eq!(points, vec![point2(0, 0), point2(1, 1), point2(2, 1), point2(3, 2), point2(4, 2)]);
+    /// ```
+    #[inline]
+    pub fn line_to(self, other: Self) -> LinePoints<U> {
+        LinePoints::new(self, other)
+    }
+}
+
+impl<T: Float, U> Point2D<T, U> {
+    /// Returns an iterator over the coordinates of the cells of a grid that the line
+    /// segment going from this point to `other` crosses, in order.
+    ///
+    /// The grid is aligned on the origin of the space and cell `(i, j)` covers
+    /// `i * cell_size.width` to `(i + 1) * cell_size.width` horizontally, and likewise
+    /// vertically. The cell size must be positive.
+    ///
+    /// ```rust
+    /// # use euclid::{point2, size2};
+    /// # use euclid::default::Point2D;
+    /// let from: Point2D<f32> = point2(0.5, 0.5);
+    /// let cells: Vec<_> = from.cells_to(point2(2.5, 1.5), size2(1.0, 1.0)).collect();
+    /// assert_eq!(cells, vec![point2(0, 0), point2(1, 0), point2(1, 1), point2(2, 1)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a cell coordinate doesn't fit in an `i32`.
+    #[inline]
+    pub fn cells_to(self, other: Self, cell_size: Size2D<T, U>) -> GridTraversal<T> {
+        GridTraversal::new(self, other, cell_size)
+    }
+}
+
+impl<U> Point2D<u32, U> {
+    /// Returns the Morton code (Z-order curve index) of this point, obtained by
+    /// interleaving the bits of `x` (even bits) and `y` (odd bits).
+    ///
+    /// Points that are close to each other tend to have close Morton codes, which
+    /// makes them good keys for tile caches and spatial hashing.
+    ///
+    /// ```rust
+    /// # use euclid::default::Point2D;
+    /// assert_eq!(Point2D::new(0b11, 0b00).to_morton(), 0b0101);
+    /// assert_eq!(Point2D::new(0b00, 0b11).to_morton(), 0b1010);
+    /// assert_eq!(Point2D::from_morton(0b1110), Point2D::new(0b10, 0b11));
+    /// ```
+    #[inline]
+    pub fn to_morton(self) -> u64 {
+        spread_bits(self.x) | (spread_bits(self.y) << 1)
+    }
+
+    /// Creates a point from its Morton code, see `to_morton`.
+    #[inline]
+    pub fn from_morton(code: u64) -> Self {
+        point2(compact_bits(code), compact_bits(code >> 1))
+    }
+}
+
+/// Spreads the bits of `v` over the even bits of the result.
+#[inline]
+fn spread_bits(v: u32) -> u64 {
+    let mut v = v as u64;
+    v = (v | (v << 16)) & 0x0000_FFFF_0000_FFFF;
+    v = (v | (v << 8)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+    v = (v | (v << 1)) & 0x5555_5555_5555_5555;
+    v
+}
+
+/// Gathers the even bits of `v`, the inverse of `spread_bits`.
+#[inline]
+fn compact_bits(v: u64) -> u32 {
+    let mut v = v & 0x5555_5555_5555_5555;
+    v = (v | (v >> 1)) & 0x3333_3333_3333_3333;
+    v = (v | (v >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | (v >> 4)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v >> 8)) & 0x0000_FFFF_0000_FFFF;
+    v = (v | (v >> 16)) & 0x0000_0000_FFFF_FFFF;
+    v as u32
+}
+
 impl<T: Copy + Add<T, Output = T>, U> Point2D<T, U> {
     #[inline]
     pub fn add_size(self, other: &Size2D<T, U>) -> Self {
@@ -549,6 +894,19 @@ impl<T: Copy + Add<T, Output = T>, U> AddAssign<Vector2D<T, U>> for Point2D<T, U
     }
 }
 
+/// Sums the coordinates of the points, for example to compute a centroid.
+impl<T: Add<Output = T> + Zero, U> Sum for Point2D<T, U> {
+    fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |sum, p| point2(sum.x + p.x, sum.y + p.y))
+    }
+}
+
+impl<'a, T: 'a + Add<Output = T> + Copy + Zero, U: 'a> Sum<&'a Self> for Point2D<T, U> {
+    fn sum<I: Iterator<Item=&'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |sum, p| point2(sum.x + p.x, sum.y + p.y))
+    }
+}
+
 impl<T: Sub, U> Sub for Point2D<T, U> {
     type Output = Vector2D<T::Output, U>;
 
@@ -813,6 +1171,37 @@ impl<T: fmt::Debug, U> fmt::Debug for Point3D<T, U> {
     }
 }
 
+impl<T: fmt::Display, U> Point3D<T, U> {
+    pub(crate) fn fmt_with_suffix(&self, f: &mut fmt::Formatter, suffix: &str) -> fmt::Result {
+        write!(f, "(")?;
+        fmt::Display::fmt(&self.x, f)?;
+        write!(f, "{}, ", suffix)?;
+        fmt::Display::fmt(&self.y, f)?;
+        write!(f, "{}, ", suffix)?;
+        fmt::Display::fmt(&self.z, f)?;
+        write!(f, "{})", suffix)
+    }
+}
+
+impl<T: fmt::Display, U> fmt::Display for Point3D<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_suffix(f, "")
+    }
+}
+
+impl<T, U: UnitName> Point3D<T, U> {
+    /// Returns an adaptor displaying the point with the suffix of its unit after each value.
+    pub fn display_with_unit(&self) -> DisplayWithUnit<'_, Self> {
+        DisplayWithUnit(self)
+    }
+}
+
+impl<'a, T: fmt::Display, U: UnitName> fmt::Display for DisplayWithUnit<'a, Point3D<T, U>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_with_suffix(f, U::SUFFIX)
+    }
+}
+
 impl<T: Default, U> Default for Point3D<T, U> {
     fn default() -> Self {
         Point3D::new(Default::default(), Default::default(), Default::default())
@@ -876,6 +1265,64 @@ impl<T, U> Point3D<T, U> {
     }
 }
 
+#[cfg(feature = "slice-cast")]
+#[allow(unsafe_code)]
+impl<T, U> Point3D<T, U> {
+    /// Views a slice of points as a slice of their components, 3 per point.
+    ///
+    /// The points are not copied: this only changes the type of the slice, which is
+    /// possible because `Point3D` is `#[repr(C)]` and only contains components of type `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of components overflows a `usize`, which can only happen
+    /// if `T` is zero-sized.
+    #[inline]
+    pub fn slice_as_components(points: &[Self]) -> &[T] {
+        let len = points.len().checked_mul(3).expect("too many components");
+        // Safety: `Self` has the size of `[T; 3]` and the alignment of `T`, and `_unit`
+        // is zero-sized.
+        unsafe { core::slice::from_raw_parts(points.as_ptr() as *const T, len) }
+    }
+
+    /// Mutable version of `slice_as_components`.
+    #[inline]
+    pub fn slice_as_components_mut(points: &mut [Self]) -> &mut [T] {
+        let len = points.len().checked_mul(3).expect("too many components");
+        // Safety: see `slice_as_components`.
+        unsafe { core::slice::from_raw_parts_mut(points.as_mut_ptr() as *mut T, len) }
+    }
+
+    /// Views a slice of components as a slice of points, without copying.
+    ///
+    /// Returns `None` if the length of `components` is not a multiple of 3.
+    #[inline]
+    pub fn slice_from_components(components: &[T]) -> Option<&[Self]> {
+        if components.len() % 3 != 0 {
+            return None;
+        }
+        // Safety: see `slice_as_components`.
+        Some(unsafe {
+            core::slice::from_raw_parts(components.as_ptr() as *const Self, components.len() / 3)
+        })
+    }
+
+    /// Mutable version of `slice_from_components`.
+    #[inline]
+    pub fn slice_from_components_mut(components: &mut [T]) -> Option<&mut [Self]> {
+        if components.len() % 3 != 0 {
+            return None;
+        }
+        // Safety: see `slice_as_components`.
+        Some(unsafe {
+            core::slice::from_raw_parts_mut(
+                components.as_mut_ptr() as *mut Self,
+                components.len() / 3,
+            )
+        })
+    }
+}
+
 impl<T: Copy, U> Point3D<T, U> {
     /// Cast this point into a vector.
     ///
@@ -890,6 +1337,16 @@ impl<T: Copy, U> Point3D<T, U> {
         }
     }
 
+    /// Returns this point expressed relative to another origin, that is the point
+    /// minus the given origin, in the same unit.
+    #[inline]
+    pub fn relative_to(self, origin: Self) -> Self
+    where
+        T: Sub<Output = T>,
+    {
+        point3(self.x - origin.x, self.y - origin.y, self.z - origin.z)
+    }
+
     /// Returns a 2d point using this point's x and y coordinates
     #[inline]
     pub fn xy(self) -> Point2D<T, U> {
@@ -1022,6 +1479,21 @@ impl<T: Copy, U> Point3D<T, U> {
         point3(self.x.round(), self.y.round(), self.z.round())
     }
 
+    /// Rounds each component to the nearest integer value, halves rounding to the even
+    /// integer.
+    #[inline]
+    #[must_use]
+    pub fn round_half_even(self) -> Self
+    where
+        T: RoundHalfEven,
+    {
+        point3(
+            self.x.round_half_even(),
+            self.y.round_half_even(),
+            self.z.round_half_even(),
+        )
+    }
+
     /// Rounds each component to the smallest integer equal or greater than the original value.
     ///
     /// This behavior is preserved for negative values (unlike the basic cast).
@@ -1091,6 +1563,16 @@ impl<T: Copy, U> Point3D<T, U> {
     }
 }
 
+impl<T, U> Interpolate<T> for Point3D<T, U>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    #[inline]
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
 impl<T: PartialOrd, U> Point3D<T, U> {
     #[inline]
     pub fn min(self, other: Self) -> Self {
@@ -1150,6 +1632,35 @@ impl<T: NumCast + Copy, U> Point3D<T, U> {
         }
     }
 
+    /// Fallible cast from one numeric representation to another, preserving the units.
+    ///
+    /// Unlike `try_cast`, the error names the first component that could not be cast.
+    pub fn try_cast_detailed<NewT: NumCast>(self) -> Result<Point3D<NewT, U>, CastError> {
+        Ok(point3(
+            cast_component(self.x, "x")?,
+            cast_component(self.y, "y")?,
+            cast_component(self.z, "z")?,
+        ))
+    }
+
+    /// Cast from one numeric representation to another, preserving the units and
+    /// rounding the components according to `mode` first.
+    ///
+    /// `RoundingMode::RoundOut` floors the components, as for 2d points.
+    #[inline]
+    pub fn cast_with<NewT: NumCast>(self, mode: RoundingMode) -> Point3D<NewT, U>
+    where
+        T: Round + RoundHalfEven + Floor + Ceil,
+    {
+        match mode {
+            RoundingMode::Floor | RoundingMode::RoundOut => self.floor(),
+            RoundingMode::Ceil => self.ceil(),
+            RoundingMode::Round => self.round(),
+            RoundingMode::RoundHalfEven => self.round_half_even(),
+        }
+        .cast()
+    }
+
     // Convenience functions for common casts
 
     /// Cast into an `f32` point.
@@ -1278,6 +1789,19 @@ impl<T: Copy + Add<T, Output = T>, U> AddAssign<Vector3D<T, U>> for Point3D<T, U
     }
 }
 
+/// Sums the coordinates of the points, for example to compute a centroid.
+impl<T: Add<Output = T> + Zero, U> Sum for Point3D<T, U> {
+    fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |sum, p| point3(sum.x + p.x, sum.y + p.y, sum.z + p.z))
+    }
+}
+
+impl<'a, T: 'a + Add<Output = T> + Copy + Zero, U: 'a> Sum<&'a Self> for Point3D<T, U> {
+    fn sum<I: Iterator<Item=&'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |sum, p| point3(sum.x + p.x, sum.y + p.y, sum.z + p.z))
+    }
+}
+
 impl<T: Sub, U> Sub for Point3D<T, U> {
     type Output = Vector3D<T::Output, U>;
 
@@ -1506,11 +2030,41 @@ pub const fn point3<T, U>(x: T, y: T, z: T) -> Point3D<T, U> {
 #[cfg(test)]
 mod point2d {
     use crate::default::Point2D;
-    use crate::point2;
+    use crate::{point2, size2};
 
     #[cfg(feature = "mint")]
     use mint;
 
+    #[test]
+    pub fn test_fmt() {
+        let p: Point2D<f32> = Point2D::new(1.0, -2.5);
+        assert_eq!(format!("{}", p), "(1, -2.5)");
+        assert_eq!(format!("{:.2}", p), "(1.00, -2.50)");
+        assert_eq!(format!("{:.1?}", p), "(1.0, -2.5)");
+        assert_eq!(format!("{:.1}", crate::point3::<f32, crate::UnknownUnit>(0.25, 1.0, 2.0)), "(0.2, 1.0, 2.0)");
+    }
+
+    #[cfg(feature = "quickcheck")]
+    quickcheck::quickcheck! {
+        fn prop_point_round_trip(p: Point2D<i32>) -> bool {
+            p.to_vector().to_point() == p && p.yx().yx() == p && Point2D::from(p.to_array()) == p
+        }
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    pub fn test_half() {
+        use crate::approxeq::ApproxEq;
+        use half::f16;
+
+        let p: Point2D<f16> = point2(f16::from_f32(1.5), f16::from_f32(-0.25));
+        assert_eq!(p.round(), point2(f16::from_f32(2.0), f16::from_f32(0.0)));
+        assert_eq!(p.floor(), point2(f16::from_f32(1.0), f16::from_f32(-1.0)));
+        assert_eq!(p.ceil(), point2(f16::from_f32(2.0), f16::from_f32(0.0)));
+        assert!(p.approx_eq(&point2(f16::from_f32(1.5001), f16::from_f32(-0.2501))));
+        assert_eq!(Point2D::<f16>::zero() + p.to_vector(), p);
+    }
+
     #[test]
     pub fn test_min() {
         let p1 = Point2D::new(1.0, 3.0);
@@ -1571,6 +2125,142 @@ mod point2d {
         assert_eq!(p1.distance_to(p2), 2.0);
     }
 
+    #[test]
+    pub fn test_line_to() {
+        let p: Point2D<i32> = point2(2, 2);
+        assert_eq!(p.line_to(p).collect::<Vec<_>>(), vec![p]);
+        assert_eq!(
+            p.line_to(point2(2, -1)).collect::<Vec<_>>(),
+            vec![point2(2, 2), point2(2, 1), point2(2, 0), point2(2, -1)]
+        );
+        assert_eq!(
+            p.line_to(point2(-1, 1)).collect::<Vec<_>>(),
+            vec![point2(2, 2), point2(1, 2), point2(0, 1), point2(-1, 1)]
+        );
+
+        // Lines are 8-connected and have one point per step along the major axis.
+        let points: Vec<Point2D<i32>> = p.line_to(point2(-5, 20)).collect();
+        assert_eq!(points.len(), 19);
+        assert_eq!(points.last(), Some(&point2(-5, 20)));
+        for w in points.windows(2) {
+            assert!((w[1].x - w[0].x).abs() <= 1 && w[1].y - w[0].y == 1);
+        }
+
+        // The distance between the end points doesn't fit in an `i32`.
+        let min: Point2D<i32> = point2(i32::MIN, i32::MIN);
+        let mut points = min.line_to(point2(i32::MAX, i32::MAX));
+        assert_eq!(points.next(), Some(min));
+        assert_eq!(points.next(), Some(point2(i32::MIN + 1, i32::MIN + 1)));
+        let points: Vec<Point2D<i32>> = point2(i32::MAX - 1, 0).line_to(point2(i32::MAX, 2)).collect();
+        assert_eq!(points, vec![point2(i32::MAX - 1, 0), point2(i32::MAX, 1), point2(i32::MAX, 2)]);
+        assert_eq!(
+            format!("{:?}", p.line_to(p)),
+            "LinePoints { current: (2, 2), end: (2, 2), delta: (0, 0), step: (0, 0), error: 0, done: false }"
+        );
+    }
+
+    #[test]
+    pub fn test_cells_to() {
+        let p: Point2D<f32> = point2(-0.5, 0.5);
+        let cells: Vec<_> = p.cells_to(point2(-0.5, -2.5), size2(1.0, 1.0)).collect();
+        assert_eq!(cells, vec![point2(-1, 0), point2(-1, -1), point2(-1, -2), point2(-1, -3)]);
+
+        let cells: Vec<_> = p.cells_to(p, size2(1.0, 1.0)).collect();
+        assert_eq!(cells, vec![point2(-1, 0)]);
+
+        let p: Point2D<f64> = point2(5.0, 1.0);
+        let cells: Vec<_> = p.cells_to(point2(-5.0, 3.0), size2(4.0, 4.0)).collect();
+        assert_eq!(cells, vec![point2(1, 0), point2(0, 0), point2(-1, 0), point2(-2, 0)]);
+
+        // The cells at the ends of the `i32` range.
+        let p: Point2D<f64> = point2(i32::MIN as f64 + 0.5, 0.5);
+        let mut cells = p.cells_to(point2(i32::MAX as f64 + 0.5, 0.5), size2(1.0, 1.0));
+        assert_eq!(cells.next(), Some(point2(i32::MIN, 0)));
+        assert_eq!(cells.next(), Some(point2(i32::MIN + 1, 0)));
+        let p: Point2D<f64> = point2(i32::MAX as f64 - 0.5, 0.5);
+        let cells: Vec<_> = p.cells_to(point2(i32::MAX as f64 + 0.5, 0.5), size2(1.0, 1.0)).collect();
+        assert_eq!(cells, vec![point2(i32::MAX - 1, 0), point2(i32::MAX, 0)]);
+    }
+
+    #[test]
+    pub fn test_morton() {
+        let p: Point2D<u32> = point2(u32::MAX, 0);
+        assert_eq!(p.to_morton(), 0x5555_5555_5555_5555);
+        assert_eq!(Point2D::new(0, u32::MAX).to_morton(), 0xAAAA_AAAA_AAAA_AAAA);
+
+        for &(x, y) in &[(0, 0), (1, 2), (12345, 67890), (u32::MAX, 7), (0xDEAD_BEEF, 0xCAFE_F00D)] {
+            let p: Point2D<u32> = point2(x, y);
+            assert_eq!(Point2D::from_morton(p.to_morton()), p);
+        }
+
+        // Z-order: the four cells of a 2x2 block are consecutive.
+        let codes: Vec<u64> = [(2, 2), (3, 2), (2, 3), (3, 3)]
+            .iter()
+            .map(|&(x, y)| Point2D::new(x, y).to_morton())
+            .collect();
+        assert_eq!(codes, vec![12, 13, 14, 15]);
+    }
+
+    #[test]
+    pub fn test_try_cast_detailed() {
+        let p: Point2D<f32> = point2(1.5, -3.0);
+        assert_eq!(p.try_cast_detailed::<i32>(), Ok(point2(1, -3)));
+
+        let err = p.try_cast_detailed::<u32>().unwrap_err();
+        assert_eq!(err.component(), "y");
+
+        let p: Point2D<f32> = point2(f32::NAN, f32::NAN);
+        let err = p.try_cast_detailed::<i32>().unwrap_err();
+        assert_eq!(err.component(), "x");
+    }
+
+    #[test]
+    pub fn test_round_half_even() {
+        use crate::num::RoundingMode;
+
+        let round = |x: f32, y: f32| point2::<f32, crate::UnknownUnit>(x, y).round_half_even();
+        assert_eq!(round(0.5, 1.5), point2(0.0, 2.0));
+        assert_eq!(round(2.5, -2.5), point2(2.0, -2.0));
+        assert_eq!(round(-0.5, -1.5), point2(0.0, -2.0));
+        assert_eq!(round(2.4, -2.6), point2(2.0, -3.0));
+        // The largest f32 below 0.5, which `floor(x + 0.5)` rounds to 1.
+        assert_eq!(round(0.49999997, 8388609.0), point2(0.0, 8388609.0));
+        assert!(round(f32::NAN, 0.0).x.is_nan());
+
+        let p: Point2D<f64> = point2(0.5, 2.5);
+        assert_eq!(p.cast_with::<i32>(RoundingMode::RoundHalfEven), point2(0, 2));
+        assert_eq!(p.cast_with::<i32>(RoundingMode::Round), point2(1, 3));
+    }
+
+    #[cfg(feature = "slice-cast")]
+    #[test]
+    pub fn test_slice_components() {
+        let mut points: Vec<Point2D<i32>> = vec![point2(1, 2), point2(3, 4), point2(5, 6)];
+        assert_eq!(Point2D::slice_as_components(&points), &[1, 2, 3, 4, 5, 6]);
+
+        for c in Point2D::slice_as_components_mut(&mut points) {
+            *c *= 10;
+        }
+        assert_eq!(points[2], point2(50, 60));
+
+        let mut components = [1.0f32, 2.0, 3.0, 4.0];
+        let view = Point2D::slice_from_components_mut(&mut components).unwrap();
+        view[1].x = 0.0;
+        assert_eq!(components, [1.0, 2.0, 0.0, 4.0]);
+
+        assert_eq!(Point2D::<f32>::slice_from_components(&components[1..]), None);
+        assert_eq!(Point2D::<f32>::slice_from_components(&[]), Some(&[][..]));
+    }
+
+    #[cfg(feature = "slice-cast")]
+    #[test]
+    #[should_panic(expected = "too many components")]
+    pub fn test_slice_components_overflow() {
+        // Slices of zero-sized points can be longer than half the address space.
+        let points = [point2::<(), crate::UnknownUnit>((), ()); usize::MAX];
+        Point2D::slice_as_components(&points);
+    }
+
     mod ops {
         use crate::default::Point2D;
         use crate::scale::Scale;
@@ -1582,6 +2272,18 @@ mod point2d {
         pub type Point2DMm<T> = crate::Point2D<T, Mm>;
         pub type Point2DCm<T> = crate::Point2D<T, Cm>;
 
+        #[test]
+        pub fn test_sum() {
+            let points = [
+                Point2D::new(0.0, 1.0),
+                Point2D::new(1.0, 2.0),
+                Point2D::new(2.0, 6.0),
+            ];
+            let sum: Point2D<f32> = points.iter().sum();
+            assert_eq!(sum, Point2D::new(3.0, 9.0));
+            assert_eq!(points.iter().copied().sum::<Point2D<f32>>() / 3.0, Point2D::new(1.0, 3.0));
+        }
+
         #[test]
         pub fn test_neg() {
             assert_eq!(-Point2D::new(1.0, 2.0), Point2D::new(-1.0, -2.0));
@@ -1772,6 +2474,18 @@ mod point3d {
     #[cfg(feature = "mint")]
     use mint;
 
+    #[test]
+    pub fn test_casts() {
+        use crate::num::RoundingMode;
+
+        let p: Point3D<f32> = Point3D::new(-0.5, 1.6, 3e10);
+        assert_eq!(p.relative_to(Point3D::new(1.0, 1.0, 1.0)), Point3D::new(-1.5, 0.6, 3e10 - 1.0));
+        assert_eq!(p.try_cast_detailed::<i32>().unwrap_err().component(), "z");
+        let p = Point3D::new(-0.5, 1.6, 2.2);
+        assert_eq!(p.cast_with::<i32>(RoundingMode::Floor), point3(-1, 1, 2));
+        assert_eq!(p.cast_with::<i32>(RoundingMode::Ceil), point3(0, 2, 3));
+    }
+
     #[test]
     pub fn test_min() {
         let p1 = Point3D::new(1.0, 3.0, 5.0);
@@ -1841,6 +2555,16 @@ mod point3d {
         assert_eq!(p1, p2);
     }
 
+    #[cfg(feature = "slice-cast")]
+    #[test]
+    pub fn test_slice_components() {
+        let points: [Point3D<f64>; 2] = [point3(1.0, 2.0, 3.0), point3(4.0, 5.0, 6.0)];
+        let components = Point3D::slice_as_components(&points);
+        assert_eq!(components, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(Point3D::slice_from_components(components), Some(&points[..]));
+        assert_eq!(Point3D::slice_from_components(&components[..4]), None);
+    }
+
     mod ops {
         use crate::default::Point3D;
         use crate::scale::Scale;
@@ -1852,6 +2576,17 @@ mod point3d {
         pub type Point3DMm<T> = crate::Point3D<T, Mm>;
         pub type Point3DCm<T> = crate::Point3D<T, Cm>;
 
+        #[test]
+        pub fn test_sum() {
+            let points = [
+                Point3D::new(0.0, 1.0, 2.0),
+                Point3D::new(1.0, 2.0, 3.0),
+                Point3D::new(2.0, 3.0, 4.0),
+            ];
+            let sum = Point3D::new(3.0, 6.0, 9.0);
+            assert_eq!(points.iter().sum::<Point3D<_>>(), sum);
+        }
+
         #[test]
         pub fn test_neg() {
             assert_eq!(-Point3D::new(1.0, 2.0, 3.0), Point3D::new(-1.0, -2.0, -3.0));
@@ -0,0 +1,323 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Points stored as a structure of arrays.
+//!
+//! Bulk operations on many points, like transforming thousands of glyph positions, are
+//! limited by memory bandwidth. Storing the x and y components in separate arrays lets
+//! these loops read and write contiguous data that the compiler can vectorize.
+//!
+//! ```rust
+//! use euclid::point_buffer::Point2DBuffer;
+//! use euclid::default::Transform2D;
+//! use euclid::{point2, size2, UnknownUnit};
+//!
+//! let mut points: Point2DBuffer<f32, UnknownUnit> =
+//!     vec![point2(0.0, 0.0), point2(10.0, 5.0)].into_iter().collect();
+//! points.apply_transform(&Transform2D::translation(1.0, 2.0));
+//! assert_eq!(points.get(1), Some(point2(11.0, 7.0)));
+//! assert_eq!(points.bounding_rect().size, size2(10.0, 5.0));
+//! ```
+
+use crate::box2d::Box2D;
+use crate::num::Zero;
+use crate::point::{point2, Point2D};
+use crate::rect::Rect;
+use crate::transform2d::Transform2D;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::ops::{Add, Mul, Sub};
+
+/// A list of 2d points, with the x and y components in separate arrays.
+pub struct Point2DBuffer<T, U> {
+    xs: Vec<T>,
+    ys: Vec<T>,
+    _unit: PhantomData<U>,
+}
+
+impl<T: Clone, U> Clone for Point2DBuffer<T, U> {
+    fn clone(&self) -> Self {
+        Point2DBuffer {
+            xs: self.xs.clone(),
+            ys: self.ys.clone(),
+            _unit: PhantomData,
+        }
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for Point2DBuffer<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.xs == other.xs && self.ys == other.ys
+    }
+}
+
+impl<T: Eq, U> Eq for Point2DBuffer<T, U> {}
+
+impl<T: fmt::Debug, U> fmt::Debug for Point2DBuffer<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Point2DBuffer")
+            .field("xs", &self.xs)
+            .field("ys", &self.ys)
+            .finish()
+    }
+}
+
+impl<T, U> Default for Point2DBuffer<T, U> {
+    fn default() -> Self {
+        Point2DBuffer::new()
+    }
+}
+
+impl<T, U> Point2DBuffer<T, U> {
+    /// Creates an empty buffer.
+    #[inline]
+    pub fn new() -> Self {
+        Point2DBuffer {
+            xs: Vec::new(),
+            ys: Vec::new(),
+            _unit: PhantomData,
+        }
+    }
+
+    /// Creates an empty buffer with space for `capacity` points.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Point2DBuffer {
+            xs: Vec::with_capacity(capacity),
+            ys: Vec::with_capacity(capacity),
+            _unit: PhantomData,
+        }
+    }
+
+    /// Creates a buffer from the arrays of x and y components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arrays don't have the same length.
+    pub fn from_components(xs: Vec<T>, ys: Vec<T>) -> Self {
+        assert_eq!(xs.len(), ys.len(), "components must have the same length");
+        Point2DBuffer {
+            xs,
+            ys,
+            _unit: PhantomData,
+        }
+    }
+
+    /// Returns the arrays of x and y components.
+    #[inline]
+    pub fn into_components(self) -> (Vec<T>, Vec<T>) {
+        (self.xs, self.ys)
+    }
+
+    /// The x components of the points.
+    #[inline]
+    pub fn xs(&self) -> &[T] {
+        &self.xs
+    }
+
+    /// The y components of the points.
+    #[inline]
+    pub fn ys(&self) -> &[T] {
+        &self.ys
+    }
+
+    /// The number of points.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    /// Returns true if the buffer contains no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+
+    /// Appends a point.
+    #[inline]
+    pub fn push(&mut self, p: Point2D<T, U>) {
+        self.xs.push(p.x);
+        self.ys.push(p.y);
+    }
+
+    /// Removes all points.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.xs.clear();
+        self.ys.clear();
+    }
+}
+
+impl<T: Copy, U> Point2DBuffer<T, U> {
+    /// Returns the point at `index`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<Point2D<T, U>> {
+        Some(point2(*self.xs.get(index)?, self.ys[index]))
+    }
+
+    /// Iterates over the points.
+    pub fn iter(&self) -> impl Iterator<Item = Point2D<T, U>> + '_ {
+        self.xs.iter().zip(self.ys.iter()).map(|(&x, &y)| point2(x, y))
+    }
+}
+
+impl<T, U> Point2DBuffer<T, U>
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    /// Transforms all points in place, like `Transform2D::transform_point`.
+    pub fn apply_transform(&mut self, transform: &Transform2D<T, U, U>) {
+        let m = transform;
+        for (x, y) in self.xs.iter_mut().zip(self.ys.iter_mut()) {
+            let (px, py) = (*x, *y);
+            *x = px * m.m11 + py * m.m21 + m.m31;
+            *y = px * m.m12 + py * m.m22 + m.m32;
+        }
+    }
+
+    /// Returns the points transformed into the destination space of `transform`.
+    pub fn transformed<Dst>(&self, transform: &Transform2D<T, U, Dst>) -> Point2DBuffer<T, Dst> {
+        let m = transform;
+        let (xs, ys) = self
+            .xs
+            .iter()
+            .zip(self.ys.iter())
+            .map(|(&x, &y)| (x * m.m11 + y * m.m21 + m.m31, x * m.m12 + y * m.m22 + m.m32))
+            .unzip();
+
+        Point2DBuffer {
+            xs,
+            ys,
+            _unit: PhantomData,
+        }
+    }
+}
+
+impl<T, U> Point2DBuffer<T, U>
+where
+    T: Copy + Zero + PartialOrd,
+{
+    /// Returns the smallest box containing all points, like `Box2D::from_points`.
+    ///
+    /// Returns an empty box at the origin if the buffer is empty.
+    pub fn bounding_box(&self) -> Box2D<T, U> {
+        let (min_x, max_x) = match min_max(&self.xs) {
+            Some(range) => range,
+            None => return Box2D::zero(),
+        };
+        let (min_y, max_y) = min_max(&self.ys).unwrap();
+
+        Box2D::new(point2(min_x, min_y), point2(max_x, max_y))
+    }
+
+    /// Returns the smallest rectangle containing all points.
+    pub fn bounding_rect(&self) -> Rect<T, U>
+    where
+        T: Sub<Output = T>,
+    {
+        self.bounding_box().to_rect()
+    }
+}
+
+fn min_max<T: Copy + PartialOrd>(values: &[T]) -> Option<(T, T)> {
+    let (&first, rest) = values.split_first()?;
+    let (mut min, mut max) = (first, first);
+    for &v in rest {
+        if v < min {
+            min = v;
+        }
+        if v > max {
+            max = v;
+        }
+    }
+
+    Some((min, max))
+}
+
+impl<T, U> FromIterator<Point2D<T, U>> for Point2DBuffer<T, U> {
+    fn from_iter<I: IntoIterator<Item = Point2D<T, U>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut buffer = Point2DBuffer::with_capacity(iter.size_hint().0);
+        for p in iter {
+            buffer.push(p);
+        }
+
+        buffer
+    }
+}
+
+impl<T, U> Extend<Point2D<T, U>> for Point2DBuffer<T, U> {
+    fn extend<I: IntoIterator<Item = Point2D<T, U>>>(&mut self, iter: I) {
+        for p in iter {
+            self.push(p);
+        }
+    }
+}
+
+impl<T, U> From<&[Point2D<T, U>]> for Point2DBuffer<T, U>
+where
+    T: Copy,
+{
+    fn from(points: &[Point2D<T, U>]) -> Self {
+        points.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default;
+    use crate::{point2, rect, UnknownUnit};
+
+    #[test]
+    fn test_push_and_iter() {
+        let mut buffer: Point2DBuffer<i32, UnknownUnit> = Point2DBuffer::new();
+        assert!(buffer.is_empty());
+        buffer.push(point2(1, 2));
+        buffer.extend(vec![point2(3, 4), point2(5, 6)]);
+
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.xs(), &[1, 3, 5]);
+        assert_eq!(buffer.ys(), &[2, 4, 6]);
+        assert_eq!(buffer.get(1), Some(point2(3, 4)));
+        assert_eq!(buffer.get(3), None);
+
+        let points: Vec<default::Point2D<i32>> = buffer.iter().collect();
+        assert_eq!(Point2DBuffer::from(&points[..]), buffer);
+
+        let (xs, ys) = buffer.clone().into_components();
+        assert_eq!(Point2DBuffer::from_components(xs, ys), buffer);
+    }
+
+    #[test]
+    fn test_transform() {
+        let points: Vec<default::Point2D<f32>> = vec![point2(0.0, 0.0), point2(1.0, 2.0), point2(-3.0, 0.5)];
+        let m = default::Transform2D::rotation(crate::Angle::degrees(30.0))
+            .then_scale(2.0, 3.0)
+            .then_translate(crate::vec2(5.0, -1.0));
+
+        let mut buffer: Point2DBuffer<f32, UnknownUnit> = points.iter().copied().collect();
+        let transformed = buffer.transformed(&m);
+        buffer.apply_transform(&m);
+        assert_eq!(buffer, transformed);
+        for (p, q) in points.iter().zip(buffer.iter()) {
+            assert_eq!(m.transform_point(*p), q);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let buffer: Point2DBuffer<i32, UnknownUnit> = vec![point2(3, -1), point2(-2, 4), point2(1, 1)].into_iter().collect();
+        assert_eq!(buffer.bounding_box(), default::Box2D::new(point2(-2, -1), point2(3, 4)));
+        assert_eq!(buffer.bounding_rect(), rect(-2, -1, 5, 5));
+        assert_eq!(Point2DBuffer::<i32, UnknownUnit>::new().bounding_box(), default::Box2D::zero());
+    }
+}
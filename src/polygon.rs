@@ -0,0 +1,275 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Polygons and polylines.
+//!
+//! ```rust
+//! use euclid::point2;
+//! use euclid::polygon::Polygon2D;
+//! use euclid::default::Point2D;
+//!
+//! // An L shape.
+//! let points: Vec<Point2D<i32>> = vec![
+//!     point2(0, 0), point2(20, 0), point2(20, 10),
+//!     point2(10, 10), point2(10, 20), point2(0, 20),
+//! ];
+//! let polygon = Polygon2D::new(points);
+//! assert!(polygon.contains(point2(5, 15)));
+//! assert!(!polygon.contains(point2(15, 15)));
+//! ```
+//!
+//! Polylines are represented by slices of points, see [`simplify_polyline`].
+
+use crate::num::Zero;
+use crate::point::Point2D;
+use crate::segment::LineSegment2D;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Mul, Sub};
+use num_traits::Float;
+
+/// A closed polygon, optionally tagged with a unit.
+///
+/// The last point is implicitly connected to the first one. The polygon can be
+/// concave and self-intersecting.
+pub struct Polygon2D<T, U> {
+    pub points: Vec<Point2D<T, U>>,
+}
+
+impl<T: Clone, U> Clone for Polygon2D<T, U> {
+    fn clone(&self) -> Self {
+        Polygon2D {
+            points: self.points.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for Polygon2D<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.points == other.points
+    }
+}
+
+impl<T: Eq, U> Eq for Polygon2D<T, U> {}
+
+impl<T: fmt::Debug, U> fmt::Debug for Polygon2D<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Polygon2D").field(&self.points).finish()
+    }
+}
+
+impl<T, U> Polygon2D<T, U> {
+    /// Constructor.
+    #[inline]
+    pub fn new(points: Vec<Point2D<T, U>>) -> Self {
+        Polygon2D { points }
+    }
+
+    /// Returns the edges of the polygon, as pairs of points, including the one going
+    /// from the last point back to the first.
+    pub fn edges(&self) -> impl Iterator<Item = (&Point2D<T, U>, &Point2D<T, U>)> {
+        let next = self.points.iter().skip(1).chain(self.points.first());
+        self.points.iter().zip(next)
+    }
+}
+
+impl<T, U> Polygon2D<T, U>
+where
+    T: Copy + Zero + PartialOrd + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Returns true if the point is inside of the polygon or on its boundary.
+    ///
+    /// The inside is determined with the non-zero winding rule. With integer
+    /// coordinates the test is exact, including for points on edges and vertices,
+    /// and for colinear edges.
+    pub fn contains(&self, p: Point2D<T, U>) -> bool {
+        let zero = T::zero();
+        let mut winding = 0i32;
+        for (&a, &b) in self.edges() {
+            // Positive if p is to the left of the edge, when going from a to b with
+            // the y axis pointing up.
+            let side = (b - a).cross(p - a);
+            if side == zero && on_segment_bounds(a, b, p) {
+                return true;
+            }
+
+            if a.y <= p.y {
+                if b.y > p.y && side > zero {
+                    winding += 1;
+                }
+            } else if b.y <= p.y && side < zero {
+                winding -= 1;
+            }
+        }
+
+        winding != 0
+    }
+}
+
+/// Returns true if `p` is within the bounding box of the segment `a`-`b`.
+fn on_segment_bounds<T: PartialOrd, U>(a: Point2D<T, U>, b: Point2D<T, U>, p: Point2D<T, U>) -> bool {
+    let within = |v: &T, a: &T, b: &T| (a <= v && v <= b) || (b <= v && v <= a);
+    within(&p.x, &a.x, &b.x) && within(&p.y, &a.y, &b.y)
+}
+
+impl<T, U> From<Vec<Point2D<T, U>>> for Polygon2D<T, U> {
+    fn from(points: Vec<Point2D<T, U>>) -> Self {
+        Polygon2D::new(points)
+    }
+}
+
+/// Simplifies a polyline with the Douglas-Peucker algorithm.
+///
+/// Returns a subset of the points, always including the first and the last, such
+/// that every removed point is within `tolerance` of the simplified polyline.
+///
+/// ```rust
+/// use euclid::point2;
+/// use euclid::polygon::simplify_polyline;
+/// use euclid::default::Point2D;
+///
+/// let trace: [Point2D<f32>; 5] = [
+///     point2(0.0, 0.0), point2(1.0, 0.1), point2(2.0, -0.1), point2(3.0, 5.0), point2(4.0, 6.0),
+/// ];
+/// let simplified = simplify_polyline(&trace, 0.5);
+/// assert_eq!(simplified, vec![point2(0.0, 0.0), point2(2.0, -0.1), point2(3.0, 5.0), point2(4.0, 6.0)]);
+/// ```
+pub fn simplify_polyline<T: Float, U>(points: &[Point2D<T, U>], tolerance: T) -> Vec<Point2D<T, U>> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    // Ranges of points whose end points are kept, with the points in between left
+    // to decide. A stack avoids deep recursion on long polylines.
+    let mut ranges = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = ranges.pop() {
+        let segment = LineSegment2D::new(points[first], points[last]);
+        let mut max_distance = T::zero();
+        let mut farthest = first;
+        for (i, &p) in points.iter().enumerate().take(last).skip(first + 1) {
+            let distance = segment.distance_to_point(p);
+            if distance > max_distance {
+                max_distance = distance;
+                farthest = i;
+            }
+        }
+
+        if max_distance > tolerance {
+            keep[farthest] = true;
+            ranges.push((first, farthest));
+            ranges.push((farthest, last));
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&p, keep)| if keep { Some(p) } else { None })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point2;
+
+    type Polygon = Polygon2D<i32, crate::UnknownUnit>;
+
+    #[test]
+    fn test_contains() {
+        // A square with an extra vertex in the middle of its bottom edge.
+        let square = Polygon::new(vec![
+            point2(0, 0),
+            point2(5, 0),
+            point2(10, 0),
+            point2(10, 10),
+            point2(0, 10),
+        ]);
+        assert!(square.contains(point2(5, 5)));
+        assert!(square.contains(point2(0, 0)));
+        assert!(square.contains(point2(3, 0)));
+        assert!(square.contains(point2(10, 7)));
+        assert!(square.contains(point2(4, 10)));
+        assert!(!square.contains(point2(-1, 0)));
+        assert!(!square.contains(point2(11, 0)));
+        assert!(!square.contains(point2(5, 11)));
+        assert!(!square.contains(point2(15, 5)));
+
+        // The same square, in the other orientation.
+        let mut points = square.points.clone();
+        points.reverse();
+        let reversed = Polygon::new(points);
+        assert!(reversed.contains(point2(5, 5)));
+        assert!(!reversed.contains(point2(15, 5)));
+    }
+
+    #[test]
+    fn test_contains_concave() {
+        // A "U" shape, whose notch is at the height of vertices.
+        let u = Polygon::new(vec![
+            point2(0, 0),
+            point2(30, 0),
+            point2(30, 30),
+            point2(20, 30),
+            point2(20, 10),
+            point2(10, 10),
+            point2(10, 30),
+            point2(0, 30),
+        ]);
+        assert!(u.contains(point2(5, 20)));
+        assert!(u.contains(point2(25, 20)));
+        assert!(u.contains(point2(15, 5)));
+        assert!(u.contains(point2(15, 10)));
+        assert!(!u.contains(point2(15, 20)));
+        assert!(!u.contains(point2(15, 30)));
+        assert!(!u.contains(point2(-5, 10)));
+        assert!(!u.contains(point2(35, 30)));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert!(!Polygon::new(vec![]).contains(point2(0, 0)));
+        assert!(Polygon::new(vec![point2(1, 1)]).contains(point2(1, 1)));
+
+        let line = Polygon::new(vec![point2(0, 0), point2(10, 10)]);
+        assert!(line.contains(point2(5, 5)));
+        assert!(!line.contains(point2(5, 6)));
+    }
+
+    #[test]
+    fn test_simplify_polyline() {
+        let line: Vec<crate::default::Point2D<f64>> =
+            (0..10).map(|i| point2(i as f64, 2.0 * i as f64)).collect();
+        assert_eq!(simplify_polyline(&line, 0.01), vec![line[0], line[9]]);
+        assert_eq!(simplify_polyline(&line[..2], 0.01), line[..2].to_vec());
+
+        let zigzag: Vec<crate::default::Point2D<f64>> =
+            (0..9).map(|i| point2(i as f64, if i % 2 == 0 { 0.0 } else { 1.0 })).collect();
+        assert_eq!(simplify_polyline(&zigzag, 1.5), vec![zigzag[0], zigzag[8]]);
+        assert_eq!(simplify_polyline(&zigzag, 0.5), zigzag);
+
+        // Every removed point is within the tolerance of the result.
+        let curve: Vec<crate::default::Point2D<f64>> =
+            (0..100).map(|i| point2(i as f64 * 0.1, (i as f64 * 0.1).sin())).collect();
+        let simplified = simplify_polyline(&curve, 0.05);
+        assert!(simplified.len() < 20);
+        for p in &curve {
+            let distance = simplified
+                .windows(2)
+                .map(|w| LineSegment2D::new(w[0], w[1]).distance_to_point(*p))
+                .fold(f64::INFINITY, f64::min);
+            assert!(distance <= 0.05);
+        }
+    }
+}
@@ -0,0 +1,548 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::approxeq::ApproxEq;
+use crate::num::{One, Zero};
+use crate::point::Point2D;
+use crate::transform2d::Transform2D;
+use crate::transform3d::Transform3D;
+use core::cmp::{Eq, PartialEq};
+use core::fmt;
+use core::hash::Hash;
+use core::marker::PhantomData;
+use core::ops::{Add, Div, Mul, Sub};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A 2d projective transform represented by a column-major 3 by 3 matrix.
+///
+/// Unlike `Transform2D`, the last column is not implicitly `0 0 1`, which makes it
+/// possible to express 2d perspective warps such as mapping a texture onto an
+/// arbitrary quadrilateral.
+///
+/// ```text
+///  | m11 m12 m13 |   |x|   |x'|
+///  | m21 m22 m23 | x |y| = |y'|
+///  | m31 m32 m33 |   |1|   |w |
+/// ```
+///
+/// Transformed points are divided by `w`. The translation terms are m31 and m32.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct ProjectiveTransform2D<T, Src, Dst> {
+    pub m11: T, pub m12: T, pub m13: T,
+    pub m21: T, pub m22: T, pub m23: T,
+    pub m31: T, pub m32: T, pub m33: T,
+    #[doc(hidden)]
+    pub _unit: PhantomData<(Src, Dst)>,
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T, Src, Dst> arbitrary::Arbitrary<'a> for ProjectiveTransform2D<T, Src, Dst>
+where
+    T: arbitrary::Arbitrary<'a>,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self>
+    {
+        let (m11, m12, m13, m21, m22, m23, m31, m32, m33) = arbitrary::Arbitrary::arbitrary(u)?;
+        Ok(ProjectiveTransform2D {
+            m11, m12, m13,
+            m21, m22, m23,
+            m31, m32, m33,
+            _unit: PhantomData,
+        })
+    }
+}
+
+impl<T: Copy, Src, Dst> Copy for ProjectiveTransform2D<T, Src, Dst> {}
+
+impl<T: Clone, Src, Dst> Clone for ProjectiveTransform2D<T, Src, Dst> {
+    fn clone(&self) -> Self {
+        ProjectiveTransform2D {
+            m11: self.m11.clone(),
+            m12: self.m12.clone(),
+            m13: self.m13.clone(),
+            m21: self.m21.clone(),
+            m22: self.m22.clone(),
+            m23: self.m23.clone(),
+            m31: self.m31.clone(),
+            m32: self.m32.clone(),
+            m33: self.m33.clone(),
+            _unit: PhantomData,
+        }
+    }
+}
+
+impl<T, Src, Dst> Eq for ProjectiveTransform2D<T, Src, Dst> where T: Eq {}
+
+impl<T, Src, Dst> PartialEq for ProjectiveTransform2D<T, Src, Dst>
+    where T: PartialEq
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.m11 == other.m11 && self.m12 == other.m12 && self.m13 == other.m13 &&
+        self.m21 == other.m21 && self.m22 == other.m22 && self.m23 == other.m23 &&
+        self.m31 == other.m31 && self.m32 == other.m32 && self.m33 == other.m33
+    }
+}
+
+impl<T, Src, Dst> Hash for ProjectiveTransform2D<T, Src, Dst>
+    where T: Hash
+{
+    fn hash<H: core::hash::Hasher>(&self, h: &mut H) {
+        self.m11.hash(h);
+        self.m12.hash(h);
+        self.m13.hash(h);
+        self.m21.hash(h);
+        self.m22.hash(h);
+        self.m23.hash(h);
+        self.m31.hash(h);
+        self.m32.hash(h);
+        self.m33.hash(h);
+    }
+}
+
+impl<T, Src, Dst> ProjectiveTransform2D<T, Src, Dst> {
+    /// Create a transform specifying its components in using the column-major-column-vector
+    /// matrix notation.
+    ///
+    /// For example, the translation terms m31 and m32 are the seventh and eighth parameters.
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(
+        m11: T, m12: T, m13: T,
+        m21: T, m22: T, m23: T,
+        m31: T, m32: T, m33: T,
+    ) -> Self {
+        ProjectiveTransform2D {
+            m11, m12, m13,
+            m21, m22, m23,
+            m31, m32, m33,
+            _unit: PhantomData,
+        }
+    }
+
+    /// Returns true is this transform is approximately equal to the other one, using
+    /// T's default epsilon value.
+    #[inline]
+    pub fn approx_eq(&self, other: &Self) -> bool
+    where T : ApproxEq<T> {
+        <Self as ApproxEq<T>>::approx_eq(self, other)
+    }
+
+    /// Returns true is this transform is approximately equal to the other one, using
+    /// a provided epsilon value.
+    #[inline]
+    pub fn approx_eq_eps(&self, other: &Self, eps: &T) -> bool
+    where T : ApproxEq<T> {
+        <Self as ApproxEq<T>>::approx_eq_eps(self, other, eps)
+    }
+}
+
+impl<T: Copy, Src, Dst> ProjectiveTransform2D<T, Src, Dst> {
+    /// Returns an array containing this transform's terms, in the same order as
+    /// they are specified in `ProjectiveTransform2D::new`.
+    #[inline]
+    pub fn to_array(&self) -> [T; 9] {
+        [
+            self.m11, self.m12, self.m13,
+            self.m21, self.m22, self.m23,
+            self.m31, self.m32, self.m33,
+        ]
+    }
+
+    /// Equivalent to `to_array` with elements packed in rows.
+    #[inline]
+    pub fn to_arrays(&self) -> [[T; 3]; 3] {
+        [
+            [self.m11, self.m12, self.m13],
+            [self.m21, self.m22, self.m23],
+            [self.m31, self.m32, self.m33],
+        ]
+    }
+
+    /// Create a transform providing its components via an array of 9 elements
+    /// instead of as individual parameters.
+    #[inline]
+    pub fn from_array(array: [T; 9]) -> Self {
+        Self::new(
+            array[0], array[1], array[2],
+            array[3], array[4], array[5],
+            array[6], array[7], array[8],
+        )
+    }
+
+    /// Equivalent to `from_array` with elements packed in rows.
+    #[inline]
+    pub fn from_arrays(array: [[T; 3]; 3]) -> Self {
+        Self::new(
+            array[0][0], array[0][1], array[0][2],
+            array[1][0], array[1][1], array[1][2],
+            array[2][0], array[2][1], array[2][2],
+        )
+    }
+
+    /// Returns the same transform with a different source unit.
+    #[inline]
+    pub fn with_source<NewSrc>(&self) -> ProjectiveTransform2D<T, NewSrc, Dst> {
+        ProjectiveTransform2D::from_array(self.to_array())
+    }
+
+    /// Returns the same transform with a different destination unit.
+    #[inline]
+    pub fn with_destination<NewDst>(&self) -> ProjectiveTransform2D<T, Src, NewDst> {
+        ProjectiveTransform2D::from_array(self.to_array())
+    }
+
+    /// Create a 3D transform applying this transform to the x and y coordinates and
+    /// leaving z untouched.
+    #[allow(clippy::just_underscores_and_digits)]
+    pub fn to_3d(&self) -> Transform3D<T, Src, Dst>
+    where
+        T: Zero + One,
+    {
+        let _0 = || T::zero();
+        let _1 = || T::one();
+
+        Transform3D::new(
+            self.m11, self.m12, _0(), self.m13,
+            self.m21, self.m22, _0(), self.m23,
+            _0(),     _0(),     _1(), _0(),
+            self.m31, self.m32, _0(), self.m33,
+        )
+    }
+
+    /// Create a 2D projective transform from a 3D transform, dropping the terms that
+    /// involve the z axis.
+    ///
+    /// Points of the z = 0 plane are transformed the same way by both transforms, once
+    /// projected to 2D.
+    pub fn from_3d(m: &Transform3D<T, Src, Dst>) -> Self {
+        Self::new(
+            m.m11, m.m12, m.m14,
+            m.m21, m.m22, m.m24,
+            m.m41, m.m42, m.m44,
+        )
+    }
+
+    /// Create a projective transform from an affine one.
+    pub fn from_affine(m: &Transform2D<T, Src, Dst>) -> Self
+    where
+        T: Zero + One,
+    {
+        Self::new(
+            m.m11, m.m12, T::zero(),
+            m.m21, m.m22, T::zero(),
+            m.m31, m.m32, T::one(),
+        )
+    }
+
+    /// Returns true if this transform has no perspective component.
+    pub fn is_affine(&self) -> bool
+    where
+        T: Zero + PartialEq,
+    {
+        self.m13 == T::zero() && self.m23 == T::zero() && self.m33 != T::zero()
+    }
+
+    /// Returns the equivalent affine transform, or `None` if this transform has a
+    /// perspective component.
+    pub fn to_affine(&self) -> Option<Transform2D<T, Src, Dst>>
+    where
+        T: Zero + PartialEq + Div<Output = T>,
+    {
+        if !self.is_affine() {
+            return None;
+        }
+
+        let w = self.m33;
+        Some(Transform2D::new(
+            self.m11 / w, self.m12 / w,
+            self.m21 / w, self.m22 / w,
+            self.m31 / w, self.m32 / w,
+        ))
+    }
+}
+
+impl<T, Src, Dst> ProjectiveTransform2D<T, Src, Dst>
+where
+    T: Zero + One,
+{
+    /// Create an identity matrix.
+    #[inline]
+    #[allow(clippy::just_underscores_and_digits)]
+    pub fn identity() -> Self {
+        let _0 = || T::zero();
+        let _1 = || T::one();
+
+        Self::new(
+            _1(), _0(), _0(),
+            _0(), _1(), _0(),
+            _0(), _0(), _1(),
+        )
+    }
+
+    /// Intentional not public, because it checks for exact equivalence
+    /// while most consumers will probably want some sort of approximate
+    /// equivalence to deal with floating-point errors.
+    fn is_identity(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        *self == Self::identity()
+    }
+}
+
+/// Methods for combining generic transformations
+impl<T, Src, Dst> ProjectiveTransform2D<T, Src, Dst>
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    /// Returns the multiplication of the two matrices such that mat's transformation
+    /// applies after self's transformation.
+    #[must_use]
+    pub fn then<NewDst>(
+        &self,
+        mat: &ProjectiveTransform2D<T, Dst, NewDst>,
+    ) -> ProjectiveTransform2D<T, Src, NewDst> {
+        ProjectiveTransform2D::new(
+            self.m11 * mat.m11 + self.m12 * mat.m21 + self.m13 * mat.m31,
+            self.m11 * mat.m12 + self.m12 * mat.m22 + self.m13 * mat.m32,
+            self.m11 * mat.m13 + self.m12 * mat.m23 + self.m13 * mat.m33,
+
+            self.m21 * mat.m11 + self.m22 * mat.m21 + self.m23 * mat.m31,
+            self.m21 * mat.m12 + self.m22 * mat.m22 + self.m23 * mat.m32,
+            self.m21 * mat.m13 + self.m22 * mat.m23 + self.m23 * mat.m33,
+
+            self.m31 * mat.m11 + self.m32 * mat.m21 + self.m33 * mat.m31,
+            self.m31 * mat.m12 + self.m32 * mat.m22 + self.m33 * mat.m32,
+            self.m31 * mat.m13 + self.m32 * mat.m23 + self.m33 * mat.m33,
+        )
+    }
+
+    /// Returns the given point transformed by this transform, or `None` if it is
+    /// mapped to or behind the line at infinity.
+    #[inline]
+    pub fn transform_point(&self, p: Point2D<T, Src>) -> Option<Point2D<T, Dst>>
+    where
+        T: Div<Output = T> + Zero + PartialOrd,
+    {
+        let w = p.x * self.m13 + p.y * self.m23 + self.m33;
+        if w > T::zero() {
+            let x = p.x * self.m11 + p.y * self.m21 + self.m31;
+            let y = p.x * self.m12 + p.y * self.m22 + self.m32;
+
+            Some(Point2D::new(x / w, y / w))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, Src, Dst> ProjectiveTransform2D<T, Src, Dst>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>
+        + PartialEq + Zero + One,
+{
+    /// Computes and returns the determinant of this transform.
+    pub fn determinant(&self) -> T {
+        self.m11 * (self.m22 * self.m33 - self.m23 * self.m32)
+            - self.m12 * (self.m21 * self.m33 - self.m23 * self.m31)
+            + self.m13 * (self.m21 * self.m32 - self.m22 * self.m31)
+    }
+
+    /// Returns whether it is possible to compute the inverse transform.
+    #[inline]
+    pub fn is_invertible(&self) -> bool {
+        self.determinant() != Zero::zero()
+    }
+
+    /// Returns the inverse transform if possible.
+    #[must_use]
+    pub fn inverse(&self) -> Option<ProjectiveTransform2D<T, Dst, Src>> {
+        let det = self.determinant();
+        if det == Zero::zero() {
+            return None;
+        }
+
+        let inv_det = T::one() / det;
+        Some(ProjectiveTransform2D::new(
+            inv_det * (self.m22 * self.m33 - self.m23 * self.m32),
+            inv_det * (self.m13 * self.m32 - self.m12 * self.m33),
+            inv_det * (self.m12 * self.m23 - self.m13 * self.m22),
+
+            inv_det * (self.m23 * self.m31 - self.m21 * self.m33),
+            inv_det * (self.m11 * self.m33 - self.m13 * self.m31),
+            inv_det * (self.m13 * self.m21 - self.m11 * self.m23),
+
+            inv_det * (self.m21 * self.m32 - self.m22 * self.m31),
+            inv_det * (self.m12 * self.m31 - self.m11 * self.m32),
+            inv_det * (self.m11 * self.m22 - self.m12 * self.m21),
+        ))
+    }
+
+    /// Returns the transform mapping the unit square to the given quadrilateral.
+    ///
+    /// The corners `(0, 0)`, `(1, 0)`, `(1, 1)` and `(0, 1)` are mapped to `quad[0]`,
+    /// `quad[1]`, `quad[2]` and `quad[3]` respectively. Returns `None` if three of the
+    /// corners of the quadrilateral are aligned.
+    #[allow(clippy::just_underscores_and_digits)]
+    pub fn from_unit_square_to_quad(quad: &[Point2D<T, Dst>; 4]) -> Option<Self> {
+        let _0: T = Zero::zero();
+        let _1: T = One::one();
+        let [p0, p1, p2, p3] = *quad;
+
+        let sx = p0.x - p1.x + p2.x - p3.x;
+        let sy = p0.y - p1.y + p2.y - p3.y;
+        let (g, h) = if sx == _0 && sy == _0 {
+            // The quadrilateral is a parallelogram, the mapping is affine.
+            (_0, _0)
+        } else {
+            let dx1 = p1.x - p2.x;
+            let dx2 = p3.x - p2.x;
+            let dy1 = p1.y - p2.y;
+            let dy2 = p3.y - p2.y;
+            let den = dx1 * dy2 - dx2 * dy1;
+            if den == _0 {
+                return None;
+            }
+
+            ((sx * dy2 - dx2 * sy) / den, (dx1 * sy - sx * dy1) / den)
+        };
+
+        let m = Self::new(
+            p1.x - p0.x + g * p1.x, p1.y - p0.y + g * p1.y, g,
+            p3.x - p0.x + h * p3.x, p3.y - p0.y + h * p3.y, h,
+            p0.x,                   p0.y,                   _1,
+        );
+
+        if m.is_invertible() {
+            Some(m)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the transform mapping each corner of the `from` quadrilateral to the
+    /// corresponding corner of the `to` quadrilateral, or `None` if either of them is
+    /// degenerate.
+    pub fn from_quad_to_quad(from: &[Point2D<T, Src>; 4], to: &[Point2D<T, Dst>; 4]) -> Option<Self> {
+        let from = ProjectiveTransform2D::<T, UnitSquare, Src>::from_unit_square_to_quad(from)?;
+        let to = ProjectiveTransform2D::<T, UnitSquare, Dst>::from_unit_square_to_quad(to)?;
+
+        Some(from.inverse()?.then(&to))
+    }
+}
+
+/// The space of the unit square used as an intermediate step by `from_quad_to_quad`.
+enum UnitSquare {}
+
+impl<T: Copy + Zero + One, Src, Dst> From<Transform2D<T, Src, Dst>> for ProjectiveTransform2D<T, Src, Dst> {
+    fn from(m: Transform2D<T, Src, Dst>) -> Self {
+        Self::from_affine(&m)
+    }
+}
+
+impl <T, Src, Dst> Default for ProjectiveTransform2D<T, Src, Dst>
+    where T: Zero + One
+{
+    /// Returns the [identity transform](#method.identity).
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T: ApproxEq<T>, Src, Dst> ApproxEq<T> for ProjectiveTransform2D<T, Src, Dst> {
+    #[inline]
+    fn approx_epsilon() -> T { T::approx_epsilon() }
+
+    /// Returns true is this transform is approximately equal to the other one, using
+    /// a provided epsilon value.
+    fn approx_eq_eps(&self, other: &Self, eps: &T) -> bool {
+        self.m11.approx_eq_eps(&other.m11, eps) && self.m12.approx_eq_eps(&other.m12, eps) &&
+        self.m13.approx_eq_eps(&other.m13, eps) && self.m21.approx_eq_eps(&other.m21, eps) &&
+        self.m22.approx_eq_eps(&other.m22, eps) && self.m23.approx_eq_eps(&other.m23, eps) &&
+        self.m31.approx_eq_eps(&other.m31, eps) && self.m32.approx_eq_eps(&other.m32, eps) &&
+        self.m33.approx_eq_eps(&other.m33, eps)
+    }
+}
+
+impl<T, Src, Dst> fmt::Debug for ProjectiveTransform2D<T, Src, Dst>
+where T: Copy + fmt::Debug +
+         PartialEq +
+         One + Zero {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_identity() {
+            write!(f, "[I]")
+        } else {
+            self.to_array().fmt(f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{default, point2, vec2};
+
+    type Mf32 = default::ProjectiveTransform2D<f32>;
+
+    #[test]
+    pub fn test_affine() {
+        let affine = default::Transform2D::scale(2.0, 3.0).then_translate(vec2(1.0, -1.0));
+        let m: Mf32 = affine.into();
+        assert!(m.is_affine());
+        assert_eq!(m.to_affine(), Some(affine));
+        assert_eq!(m.transform_point(point2(1.0, 1.0)), Some(point2(3.0, 2.0)));
+
+        let scaled_w = Mf32::from_array([2.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 2.0]);
+        assert_eq!(scaled_w.to_affine(), Some(default::Transform2D::identity()));
+        assert_eq!(Mf32::from_3d(&affine.to_3d()), m);
+    }
+
+    #[test]
+    pub fn test_inverse() {
+        let m = Mf32::new(
+            1.0, 0.5, 0.001,
+            -0.5, 2.0, 0.002,
+            10.0, 20.0, 1.0,
+        );
+        let inv = m.inverse().unwrap();
+        assert!(m.then(&inv).approx_eq_eps(&Mf32::identity(), &0.00001));
+        assert!(inv.then(&m).approx_eq_eps(&Mf32::identity(), &0.00001));
+
+        let p = point2(3.0, 4.0);
+        let q = m.transform_point(p).unwrap();
+        assert!(inv.transform_point(q).unwrap().approx_eq_eps(&p, &point2(0.0001, 0.0001)));
+
+        assert!(Mf32::from_array([0.0; 9]).inverse().is_none());
+    }
+
+    #[test]
+    pub fn test_quad_to_quad() {
+        let from = [point2(0.0, 0.0), point2(100.0, 0.0), point2(100.0, 100.0), point2(0.0, 100.0)];
+        let to = [point2(10.0, 10.0), point2(90.0, 20.0), point2(80.0, 70.0), point2(20.0, 90.0)];
+        let m = Mf32::from_quad_to_quad(&from, &to).unwrap();
+        assert!(!m.is_affine());
+        for (a, b) in from.iter().zip(to.iter()) {
+            assert!(m.transform_point(*a).unwrap().approx_eq_eps(b, &point2(0.001, 0.001)));
+        }
+
+        // The 3D version transforms points the same way.
+        let p = point2(25.0, 60.0);
+        let p2 = m.transform_point(p).unwrap();
+        let p3 = m.to_3d().transform_point2d(p).unwrap();
+        assert!(p2.approx_eq(&p3));
+
+        let degenerate = [point2(0.0, 0.0), point2(1.0, 1.0), point2(2.0, 2.0), point2(0.0, 1.0)];
+        assert!(Mf32::from_quad_to_quad(&from, &degenerate).is_none());
+    }
+}
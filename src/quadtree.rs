@@ -0,0 +1,344 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A quadtree for spatial indexing of rectangles.
+//!
+//! ```rust
+//! use euclid::{point2, rect};
+//! use euclid::default::{Box2D, Rect};
+//! use euclid::quadtree::Quadtree;
+//!
+//! let mut tree = Quadtree::new(Box2D::new(point2(0.0, 0.0), point2(100.0, 100.0)));
+//! let button = tree.insert(rect(10.0, 10.0, 20.0, 10.0), "button");
+//! tree.insert(rect(50.0, 50.0, 40.0, 40.0), "image");
+//!
+//! let hits: Vec<_> = tree.query_point(point2(15.0, 15.0)).into_iter().map(|(_, v)| *v).collect();
+//! assert_eq!(hits, vec!["button"]);
+//!
+//! assert_eq!(tree.remove(button), Some("button"));
+//! assert!(tree.query_point(point2(15.0, 15.0)).is_empty());
+//! ```
+
+use crate::box2d::Box2D;
+use crate::num::{One, Zero};
+use crate::point::{point2, Point2D};
+use crate::rect::Rect;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::{Add, Div, Sub};
+
+const MAX_ITEMS_PER_NODE: usize = 8;
+const MAX_DEPTH: u32 = 8;
+
+/// A handle to an item of a `Quadtree`, returned by `Quadtree::insert`.
+///
+/// Handles of removed items stay invalid when their slot is reused by a later insertion.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QuadtreeItemId {
+    index: usize,
+    generation: u32,
+}
+
+/// An entry of a `Quadtree`, with the number of times it was freed.
+struct Slot<T, V, U> {
+    generation: u32,
+    entry: Option<(Box2D<T, U>, V)>,
+}
+
+struct Node<T, U> {
+    bounds: Box2D<T, U>,
+    items: Vec<QuadtreeItemId>,
+    children: Option<Box<[Node<T, U>; 4]>>,
+}
+
+/// A region quadtree storing values keyed by rectangles.
+///
+/// Each item is stored in the deepest node whose bounds fully contain its rectangle.
+/// Items that don't fit in the bounds of the tree are kept at the root, so any
+/// rectangle can be inserted, but queries are most efficient when the bounds cover
+/// the indexed area.
+pub struct Quadtree<T, V, U> {
+    root: Node<T, U>,
+    slots: Vec<Slot<T, V, U>>,
+    free_list: Vec<usize>,
+    len: usize,
+}
+
+impl<T, V, U> Quadtree<T, V, U>
+where
+    T: Copy + Zero + One + PartialOrd + Add<Output = T> + Sub<Output = T> + Div<Output = T>,
+{
+    /// Creates an empty quadtree covering the given bounds.
+    pub fn new(bounds: Box2D<T, U>) -> Self {
+        Quadtree {
+            root: Node::new(bounds),
+            slots: Vec::new(),
+            free_list: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of items in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the tree contains no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a value with its rectangle and returns a handle to it.
+    pub fn insert(&mut self, rect: Rect<T, U>, value: V) -> QuadtreeItemId {
+        let b = rect.to_box2d();
+        let index = match self.free_list.pop() {
+            Some(index) => {
+                self.slots[index].entry = Some((b, value));
+                index
+            }
+            None => {
+                self.slots.push(Slot { generation: 0, entry: Some((b, value)) });
+                self.slots.len() - 1
+            }
+        };
+        let id = QuadtreeItemId { index, generation: self.slots[index].generation };
+        self.len += 1;
+
+        self.root.insert(id, &b, &self.slots, 0);
+
+        id
+    }
+
+    /// Removes an item from the tree, returning its value if it was present.
+    ///
+    /// Returns `None` if the item was already removed, even if its slot was reused.
+    pub fn remove(&mut self, id: QuadtreeItemId) -> Option<V> {
+        let slot = match self.slots.get_mut(id.index) {
+            Some(slot) if slot.generation == id.generation => slot,
+            _ => return None,
+        };
+        let (b, value) = slot.entry.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.root.remove(id, &b);
+        self.free_list.push(id.index);
+        self.len -= 1;
+
+        Some(value)
+    }
+
+    /// Returns the rectangle and value of an item, or `None` if it was removed.
+    pub fn get(&self, id: QuadtreeItemId) -> Option<(Rect<T, U>, &V)> {
+        match self.slots.get(id.index) {
+            Some(slot) if slot.generation == id.generation => {
+                slot.entry.as_ref().map(|(b, value)| (b.to_rect(), value))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the items whose rectangle contains the point, following the semantics
+    /// of `Rect::contains`.
+    pub fn query_point(&self, p: Point2D<T, U>) -> Vec<(QuadtreeItemId, &V)> {
+        let mut result = Vec::new();
+        self.root.query(
+            &|bounds| bounds.contains(p),
+            &mut |id| {
+                if let Some((ref b, ref value)) = self.slots[id.index].entry {
+                    if b.contains(p) {
+                        result.push((id, value));
+                    }
+                }
+            },
+        );
+
+        result
+    }
+
+    /// Returns the items whose rectangle intersects the given one, following the
+    /// semantics of `Rect::intersects`.
+    pub fn query_rect(&self, rect: &Rect<T, U>) -> Vec<(QuadtreeItemId, &V)> {
+        let query = rect.to_box2d();
+        let mut result = Vec::new();
+        self.root.query(
+            &|bounds| bounds.intersects(&query),
+            &mut |id| {
+                if let Some((ref b, ref value)) = self.slots[id.index].entry {
+                    if b.intersects(&query) {
+                        result.push((id, value));
+                    }
+                }
+            },
+        );
+
+        result
+    }
+}
+
+impl<T, U> Node<T, U>
+where
+    T: Copy + Zero + One + PartialOrd + Add<Output = T> + Sub<Output = T> + Div<Output = T>,
+{
+    fn new(bounds: Box2D<T, U>) -> Self {
+        Node {
+            bounds,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    fn insert<V>(
+        &mut self,
+        id: QuadtreeItemId,
+        b: &Box2D<T, U>,
+        slots: &[Slot<T, V, U>],
+        depth: u32,
+    ) {
+        if let Some(ref mut children) = self.children {
+            if let Some(child) = children.iter_mut().find(|child| fits(&child.bounds, b)) {
+                child.insert(id, b, slots, depth + 1);
+                return;
+            }
+        }
+
+        self.items.push(id);
+
+        if self.children.is_none() && self.items.len() > MAX_ITEMS_PER_NODE && depth < MAX_DEPTH {
+            self.split(slots, depth);
+        }
+    }
+
+    fn split<V>(&mut self, slots: &[Slot<T, V, U>], depth: u32) {
+        let two = T::one() + T::one();
+        let min = self.bounds.min;
+        let max = self.bounds.max;
+        let center = point2((min.x + max.x) / two, (min.y + max.y) / two);
+
+        self.children = Some(Box::new([
+            Node::new(Box2D::new(min, center)),
+            Node::new(Box2D::new(point2(center.x, min.y), point2(max.x, center.y))),
+            Node::new(Box2D::new(point2(min.x, center.y), point2(center.x, max.y))),
+            Node::new(Box2D::new(center, max)),
+        ]));
+
+        let items = core::mem::take(&mut self.items);
+        for id in items {
+            if let Some((ref b, _)) = slots[id.index].entry {
+                self.insert(id, b, slots, depth);
+            }
+        }
+    }
+
+    fn remove(&mut self, id: QuadtreeItemId, b: &Box2D<T, U>) -> bool {
+        if let Some(index) = self.items.iter().position(|item| *item == id) {
+            self.items.swap_remove(index);
+            return true;
+        }
+
+        match self.children {
+            Some(ref mut children) => children
+                .iter_mut()
+                .filter(|child| fits(&child.bounds, b))
+                .any(|child| child.remove(id, b)),
+            None => false,
+        }
+    }
+
+    fn query<F, C>(&self, visit: &F, callback: &mut C)
+    where
+        F: Fn(&Box2D<T, U>) -> bool,
+        C: FnMut(QuadtreeItemId),
+    {
+        for id in &self.items {
+            callback(*id);
+        }
+
+        if let Some(ref children) = self.children {
+            for child in children.iter() {
+                if visit(&child.bounds) {
+                    child.query(visit, callback);
+                }
+            }
+        }
+    }
+}
+
+/// Like `Box2D::contains_box` but also placing empty boxes by their position.
+fn fits<T: PartialOrd, U>(outer: &Box2D<T, U>, inner: &Box2D<T, U>) -> bool {
+    outer.min.x <= inner.min.x
+        && inner.max.x <= outer.max.x
+        && outer.min.y <= inner.min.y
+        && inner.max.y <= outer.max.y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default;
+    use crate::rect;
+
+    fn sorted<V>(items: Vec<(QuadtreeItemId, &V)>) -> Vec<QuadtreeItemId> {
+        let mut ids: Vec<_> = items.into_iter().map(|(id, _)| id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_many_items() {
+        let bounds: default::Box2D<f32> = Box2D::new(point2(0.0, 0.0), point2(64.0, 64.0));
+        let mut tree = Quadtree::new(bounds);
+
+        let mut ids = Vec::new();
+        for y in 0..8 {
+            for x in 0..8 {
+                let r = rect(x as f32 * 8.0, y as f32 * 8.0, 8.0, 8.0);
+                ids.push(tree.insert(r, x + y * 8));
+            }
+        }
+        let big = tree.insert(rect(-10.0, -10.0, 100.0, 100.0), 100);
+        assert_eq!(tree.len(), 65);
+        assert!(tree.root.children.is_some());
+
+        assert_eq!(sorted(tree.query_point(point2(17.0, 9.0))), vec![ids[10], big]);
+        assert_eq!(sorted(tree.query_point(point2(16.0, 8.0))), vec![ids[10], big]);
+        assert_eq!(
+            sorted(tree.query_rect(&rect(12.0, 4.0, 8.0, 2.0))),
+            vec![ids[1], ids[2], big]
+        );
+
+        for id in &ids {
+            assert!(tree.remove(*id).is_some());
+        }
+        assert_eq!(tree.remove(ids[0]), None);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.query_point(point2(17.0, 9.0)), vec![(big, &100)]);
+    }
+
+    #[test]
+    fn test_reuse_ids() {
+        let bounds: default::Box2D<i32> = Box2D::new(point2(0, 0), point2(10, 10));
+        let mut tree = Quadtree::new(bounds);
+
+        let a = tree.insert(rect(0, 0, 1, 1), 'a');
+        assert_eq!(tree.remove(a), Some('a'));
+        assert!(tree.is_empty());
+
+        let b = tree.insert(rect(5, 5, 1, 1), 'b');
+        assert_eq!(tree.get(b), Some((rect(5, 5, 1, 1), &'b')));
+        assert!(tree.query_point(point2(0, 0)).is_empty());
+
+        // The slot of `a` is reused by `b`, but the stale handle doesn't reach it.
+        assert_ne!(a, b);
+        assert_eq!(tree.get(a), None);
+        assert_eq!(tree.remove(a), None);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.remove(b), Some('b'));
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::box3d::Box3D;
+use crate::plane::Plane3D;
+use crate::point::Point3D;
+use crate::sphere::Sphere;
+use crate::transform3d::Transform3D;
+use crate::vector::Vector3D;
+
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Mul};
+
+/// A half-line in 3d space, starting at `origin` and going in the direction of
+/// `direction`, optionally tagged with a unit.
+///
+/// The points of the ray are `origin + direction * t` for `t >= 0`. The intersection
+/// methods return the `t` of the first intersection. The direction doesn't need to be
+/// normalized, but `t` is only a distance if it is.
+///
+/// ```rust
+/// use euclid::default::{Box3D, Ray3D};
+/// use euclid::{point3, vec3};
+///
+/// let ray = Ray3D::new(point3(0.0, 0.0, 10.0), vec3(0.0, 0.0, -1.0));
+/// let layer = Box3D::new(point3(-5.0, -5.0, 0.0), point3(5.0, 5.0, 2.0));
+/// assert_eq!(ray.intersect_box3d(&layer), Some(8.0));
+/// assert_eq!(ray.point_at(8.0), point3(0.0, 0.0, 2.0));
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct Ray3D<T, U> {
+    pub origin: Point3D<T, U>,
+    pub direction: Vector3D<T, U>,
+}
+
+impl<T: Copy, U> Copy for Ray3D<T, U> {}
+
+impl<T: Clone, U> Clone for Ray3D<T, U> {
+    fn clone(&self) -> Self {
+        Ray3D {
+            origin: self.origin.clone(),
+            direction: self.direction.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for Ray3D<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.origin == other.origin && self.direction == other.direction
+    }
+}
+
+impl<T: Eq, U> Eq for Ray3D<T, U> {}
+
+impl<T: Hash, U> Hash for Ray3D<T, U> {
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        self.origin.hash(h);
+        self.direction.hash(h);
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for Ray3D<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ray3D")
+            .field("origin", &self.origin)
+            .field("direction", &self.direction)
+            .finish()
+    }
+}
+
+impl<T, U> Ray3D<T, U> {
+    /// Constructor.
+    #[inline]
+    pub const fn new(origin: Point3D<T, U>, direction: Vector3D<T, U>) -> Self {
+        Ray3D { origin, direction }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>, U> Ray3D<T, U> {
+    /// Returns the point at `origin + direction * t`.
+    #[inline]
+    pub fn point_at(&self, t: T) -> Point3D<T, U> {
+        self.origin + self.direction * t
+    }
+}
+
+impl<T: Float, U> Ray3D<T, U> {
+    /// Returns the ray going through the transformed points of this ray, or `None` if
+    /// its origin can't be transformed.
+    ///
+    /// This supports perspective transforms, for example to express a ray in the
+    /// local space of a transformed layer using the inverse of its transform.
+    pub fn transform<Dst>(&self, m: &Transform3D<T, U, Dst>) -> Option<Ray3D<T, Dst>> {
+        let h0 = m.transform_point3d_homogeneous(self.origin);
+        let origin = h0.to_point3d()?;
+        let h1 = m.transform_point3d_homogeneous(self.origin + self.direction);
+        let direction = match h1.to_point3d() {
+            Some(p) => p - origin,
+            // The second point is at infinity (w = 0) or behind the viewer (w < 0), where
+            // its projection would point the wrong way. Use the tangent of the projected
+            // ray at its origin instead, which is `(h1.xyz - origin * h1.w) / h0.w`.
+            None => (Vector3D::new(h1.x, h1.y, h1.z) - origin.to_vector() * h1.w) / h0.w,
+        };
+
+        Some(Ray3D::new(origin, direction))
+    }
+
+    /// Returns the `t` of the intersection with a plane, or `None` if the ray is
+    /// parallel to the plane or points away from it.
+    pub fn intersect_plane(&self, plane: &Plane3D<T, U>) -> Option<T> {
+        let denominator = plane.normal.dot(self.direction);
+        if denominator == T::zero() {
+            return None;
+        }
+
+        let t = -plane.signed_distance_to_point(self.origin) / denominator;
+        if t >= T::zero() {
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `t` at which the ray enters the box, or `None` if it misses it.
+    ///
+    /// If the origin is inside of the box, this is where the ray exits it. Boxes with
+    /// a negative volume are never hit.
+    pub fn intersect_box3d(&self, b: &Box3D<T, U>) -> Option<T> {
+        if b.is_negative() {
+            return None;
+        }
+
+        // Intersect the ranges of t for which the ray is between each pair of faces.
+        let mut near = T::neg_infinity();
+        let mut far = T::infinity();
+        let axes = [
+            (self.origin.x, self.direction.x, b.min.x, b.max.x),
+            (self.origin.y, self.direction.y, b.min.y, b.max.y),
+            (self.origin.z, self.direction.z, b.min.z, b.max.z),
+        ];
+        for &(origin, direction, min, max) in &axes {
+            if direction == T::zero() {
+                // The ray is parallel to the faces, and either always or never
+                // between them.
+                if origin < min || origin > max {
+                    return None;
+                }
+                continue;
+            }
+
+            let t1 = (min - origin) / direction;
+            let t2 = (max - origin) / direction;
+            near = near.max(t1.min(t2));
+            far = far.min(t1.max(t2));
+        }
+
+        if far < near || far < T::zero() {
+            None
+        } else if near >= T::zero() {
+            Some(near)
+        } else {
+            Some(far)
+        }
+    }
+
+    /// Returns the `t` at which the ray enters the sphere, or `None` if it misses it.
+    ///
+    /// If the origin is inside of the sphere, this is where the ray exits it.
+    #[inline]
+    pub fn intersect_sphere(&self, sphere: &Sphere<T, U>) -> Option<T> {
+        sphere.ray_intersection(self.origin, self.direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::default::{Box3D, Plane3D, Ray3D, Sphere, Transform3D};
+    use crate::{point3, vec3};
+
+    #[test]
+    fn test_intersect_plane() {
+        let ray = Ray3D::new(point3(1.0, 2.0, 3.0), vec3(0.0, 0.0, -2.0));
+        let plane = Plane3D::new(vec3(0.0, 0.0, 1.0), 1.0);
+        assert_eq!(ray.intersect_plane(&plane), Some(2.0));
+        assert_eq!(ray.point_at(2.0), point3(1.0, 2.0, -1.0));
+
+        let parallel = Ray3D::new(point3(1.0, 2.0, 3.0), vec3(1.0, 0.0, 0.0));
+        assert_eq!(parallel.intersect_plane(&plane), None);
+        let away = Ray3D::new(point3(1.0, 2.0, 3.0), vec3(0.0, 0.0, 1.0));
+        assert_eq!(away.intersect_plane(&plane), None);
+    }
+
+    #[test]
+    fn test_intersect_box3d() {
+        let b = Box3D::new(point3(0.0, 0.0, 0.0), point3(2.0, 2.0, 2.0));
+
+        let ray = Ray3D::new(point3(-2.0, 1.0, 1.0), vec3(1.0, 0.0, 0.0));
+        assert_eq!(ray.intersect_box3d(&b), Some(2.0));
+        let diagonal = Ray3D::new(point3(-1.0, -1.0, -1.0), vec3(1.0, 1.0, 1.0));
+        assert_eq!(diagonal.intersect_box3d(&b), Some(1.0));
+        let inside = Ray3D::new(point3(1.0, 1.0, 1.0), vec3(0.0, -1.0, 0.0));
+        assert_eq!(inside.intersect_box3d(&b), Some(1.0));
+        let on_face = Ray3D::new(point3(0.0, -1.0, 1.0), vec3(0.0, 1.0, 0.0));
+        assert_eq!(on_face.intersect_box3d(&b), Some(1.0));
+
+        let miss = Ray3D::new(point3(-2.0, 3.0, 1.0), vec3(1.0, 0.0, 0.0));
+        assert_eq!(miss.intersect_box3d(&b), None);
+        let behind = Ray3D::new(point3(-2.0, 1.0, 1.0), vec3(-1.0, 0.0, 0.0));
+        assert_eq!(behind.intersect_box3d(&b), None);
+        let negative = Box3D::new(point3(2.0, 0.0, 0.0), point3(0.0, 2.0, 2.0));
+        assert_eq!(ray.intersect_box3d(&negative), None);
+    }
+
+    #[test]
+    fn test_intersect_sphere() {
+        let ray = Ray3D::new(point3(0.0, 0.0, 10.0), vec3(0.0, 0.0, -1.0));
+        assert_eq!(ray.intersect_sphere(&Sphere::new(point3(0.0, 0.0, 0.0), 1.0)), Some(9.0));
+        assert_eq!(ray.intersect_sphere(&Sphere::new(point3(3.0, 0.0, 0.0), 1.0)), None);
+    }
+
+    #[test]
+    fn test_transform() {
+        let ray = Ray3D::new(point3(1.0, 0.0, 5.0), vec3(0.0, 0.0, -1.0));
+        let m = Transform3D::scale(2.0, 2.0, 2.0).then_translate(vec3(0.0, 1.0, 0.0));
+        let transformed = ray.transform(&m).unwrap();
+        assert_eq!(transformed, Ray3D::new(point3(2.0, 1.0, 10.0), vec3(0.0, 0.0, -2.0)));
+
+        // Picking a layer in its local space gives the same t.
+        let layer = Box3D::new(point3(0.0, -1.0, -1.0), point3(2.0, 1.0, 1.0));
+        let local = transformed.transform(&m.inverse().unwrap()).unwrap();
+        assert_eq!(local, ray);
+        assert_eq!(local.intersect_box3d(&layer), Some(4.0));
+    }
+
+    #[test]
+    fn test_transform_perspective() {
+        let m = Transform3D::perspective(1.0);
+
+        // Both points are in front of the viewer.
+        let ray = Ray3D::new(point3(1.0, 0.0, 0.0), vec3(0.0, 0.0, 0.5));
+        assert_eq!(ray.transform(&m), Some(Ray3D::new(point3(1.0, 0.0, 0.0), vec3(1.0, 0.0, 1.0))));
+
+        // The second point is at infinity, then behind the viewer: its projection at
+        // (-1, 0, -2) would flip the direction.
+        for &dz in &[1.0, 2.0] {
+            let ray = Ray3D::new(point3(1.0, 0.0, 0.0), vec3(0.0, 0.0, dz));
+            let transformed = ray.transform(&m).unwrap();
+            assert_eq!(transformed.origin, point3(1.0, 0.0, 0.0));
+            assert_eq!(transformed.direction, vec3(dz, 0.0, dz));
+
+            // Points just past the origin project onto the transformed ray.
+            let p = m.transform_point3d(ray.point_at(0.01)).unwrap() - transformed.origin;
+            assert!(p.cross(transformed.direction).length() < 1e-12);
+            assert!(p.dot(transformed.direction) > 0.0);
+        }
+
+        let behind = Ray3D::new(point3(0.0, 0.0, 2.0), vec3(1.0, 0.0, 0.0));
+        assert_eq!(behind.transform(&m), None);
+    }
+}
@@ -7,24 +7,33 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use super::UnknownUnit;
+use super::{DisplayWithUnit, UnitName, UnknownUnit};
 use crate::box2d::Box2D;
+use crate::grid::{ceil_div, floor_div, GridCells};
+use crate::interpolate::Interpolate;
 use crate::num::*;
 use crate::point::Point2D;
 use crate::scale::Scale;
+use crate::segment::LineSegment2D;
 use crate::side_offsets::SideOffsets2D;
 use crate::size::Size2D;
 use crate::vector::Vector2D;
 
-use num_traits::{NumCast, Float};
+use num_traits::{Bounded, CheckedMul, Euclid, NumCast, Float};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "std")]
+use alloc::vec;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
 use core::borrow::Borrow;
-use core::cmp::PartialOrd;
+use core::cmp::{Ordering, PartialOrd};
 use core::fmt;
 use core::hash::{Hash, Hasher};
-use core::ops::{Add, Div, DivAssign, Mul, MulAssign, Range, Sub};
+#[cfg(feature = "std")]
+use core::iter::once;
+use core::ops::{Add, Div, DivAssign, Mul, MulAssign, Neg, Range, Sub};
 
 /// A 2d Rectangle optionally tagged with a unit.
 ///
@@ -69,6 +78,32 @@ where
     }
 }
 
+#[cfg(feature = "rand")]
+impl<T, U> rand::distributions::Distribution<Rect<T, U>> for rand::distributions::Standard
+where
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Rect<T, U> {
+        rect(rng.gen(), rng.gen(), rng.gen(), rng.gen())
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<T, U> quickcheck::Arbitrary for Rect<T, U>
+where
+    T: quickcheck::Arbitrary,
+    U: 'static,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Rect::new(quickcheck::Arbitrary::arbitrary(g), quickcheck::Arbitrary::arbitrary(g))
+    }
+
+    fn shrink(&self) -> alloc::boxed::Box<dyn Iterator<Item = Self>> {
+        let shrunk = quickcheck::Arbitrary::shrink(&(self.origin.clone(), self.size.clone()));
+        alloc::boxed::Box::new(shrunk.map(|(origin, size)| Rect::new(origin, size)))
+    }
+}
+
 impl<T: Hash, U> Hash for Rect<T, U> {
     fn hash<H: Hasher>(&self, h: &mut H) {
         self.origin.hash(h);
@@ -92,6 +127,41 @@ impl<T: PartialEq, U> PartialEq for Rect<T, U> {
 
 impl<T: Eq, U> Eq for Rect<T, U> {}
 
+/// Rectangles are partially ordered by containment: `a <= b` means that `b`
+/// contains `a` (see `contains_rect`), and rectangles that don't contain each
+/// other are not comparable.
+///
+/// Distinct empty rectangles contain each other, so they are not comparable
+/// either. Use `cmp_by_area` or `cmp_by_top_left` for a total order.
+///
+/// ```rust
+/// use euclid::default::Rect;
+/// use euclid::rect;
+///
+/// let parent: Rect<i32> = rect(0, 0, 100, 100);
+/// let child = rect(10, 10, 20, 20);
+/// assert!(child <= parent);
+/// assert!(child < parent);
+/// assert!(!(parent <= child));
+/// assert_eq!(child.partial_cmp(&rect(50, 50, 100, 100)), None);
+/// ```
+impl<T, U> PartialOrd for Rect<T, U>
+where
+    T: Copy + Zero + PartialOrd + Add<T, Output = T>,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+
+        match (other.contains_rect(self), self.contains_rect(other)) {
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            _ => None,
+        }
+    }
+}
+
 impl<T: fmt::Debug, U> fmt::Debug for Rect<T, U> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Rect(")?;
@@ -102,6 +172,33 @@ impl<T: fmt::Debug, U> fmt::Debug for Rect<T, U> {
     }
 }
 
+impl<T: fmt::Display, U> Rect<T, U> {
+    pub(crate) fn fmt_with_suffix(&self, f: &mut fmt::Formatter, suffix: &str) -> fmt::Result {
+        self.size.fmt_with_suffix(f, suffix)?;
+        write!(f, " at ")?;
+        self.origin.fmt_with_suffix(f, suffix)
+    }
+}
+
+impl<T: fmt::Display, U> fmt::Display for Rect<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_suffix(f, "")
+    }
+}
+
+impl<T, U: UnitName> Rect<T, U> {
+    /// Returns an adaptor displaying the rectangle with the suffix of its unit after each value.
+    pub fn display_with_unit(&self) -> DisplayWithUnit<'_, Self> {
+        DisplayWithUnit(self)
+    }
+}
+
+impl<'a, T: fmt::Display, U: UnitName> fmt::Display for DisplayWithUnit<'a, Rect<T, U>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_with_suffix(f, U::SUFFIX)
+    }
+}
+
 impl<T: Default, U> Default for Rect<T, U> {
     fn default() -> Self {
         Rect::new(Default::default(), Default::default())
@@ -136,6 +233,34 @@ where
     }
 }
 
+impl<T, U> Rect<T, U>
+where
+    T: Copy + PartialOrd + Sub<T, Output = T>,
+{
+    /// Creates a rect from its minimum and maximum corners, or returns `None` if the
+    /// maximum corner is smaller than the minimum corner on either axis.
+    ///
+    /// Corners that are equal on an axis give an empty rectangle.
+    ///
+    /// ```rust
+    /// use euclid::default::Rect;
+    /// use euclid::{point2, rect};
+    ///
+    /// assert_eq!(Rect::from_min_max(point2(1, 2), point2(4, 8)), Some(rect(1, 2, 3, 6)));
+    /// assert_eq!(Rect::from_min_max(point2(1, 2), point2(4, 2)), Some(rect(1, 2, 3, 0)));
+    /// assert_eq!(Rect::from_min_max(point2(1, 2), point2(0, 8)), None);
+    /// ```
+    #[inline]
+    pub fn from_min_max(min: Point2D<T, U>, max: Point2D<T, U>) -> Option<Self> {
+        // Written to also reject NaN coordinates.
+        if !(max.x >= min.x && max.y >= min.y) {
+            return None;
+        }
+
+        Some(Rect::new(min, (max - min).to_size()))
+    }
+}
+
 impl<T, U> Rect<T, U>
 where
     T: Copy + Add<T, Output = T>,
@@ -204,6 +329,40 @@ where
             max: self.max(),
         }
     }
+
+    /// Returns the top, right, bottom and left edges of the rectangle, in that order.
+    ///
+    /// The edges go around the rectangle clockwise (in a y-down coordinate system),
+    /// each one starting where the previous one ends.
+    pub fn edges(&self) -> [LineSegment2D<T, U>; 4] {
+        let min = self.min();
+        let max = self.max();
+        let top_right = Point2D::new(max.x, min.y);
+        let bottom_left = Point2D::new(min.x, max.y);
+
+        [
+            LineSegment2D::new(min, top_right),
+            LineSegment2D::new(top_right, max),
+            LineSegment2D::new(max, bottom_left),
+            LineSegment2D::new(bottom_left, min),
+        ]
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<T, U> Rect<T, U>
+where
+    T: rand::distributions::uniform::SampleUniform + PartialOrd + Copy + Add<T, Output = T>,
+{
+    /// Returns a point sampled uniformly inside of this rectangle, following the
+    /// semantics of `contains` for points on the edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle is empty.
+    pub fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Point2D<T, U> {
+        self.to_box2d().sample(rng)
+    }
 }
 
 impl<T, U> Rect<T, U>
@@ -218,18 +377,126 @@ where
         self.to_box2d().contains(p)
     }
 
+    /// Returns `true` if the interiors of the two rectangles overlap. Rectangles that
+    /// only share an edge or a corner don't intersect (see `intersects_inclusive`).
     #[inline]
     pub fn intersects(&self, other: &Self) -> bool {
         self.to_box2d().intersects(&other.to_box2d())
     }
+
+    /// Returns `true` if the two rectangles intersect or touch.
+    ///
+    /// Unlike `intersects`, rectangles that only share an edge or a corner are
+    /// considered intersecting. See `Box2D::intersects_inclusive`.
+    #[inline]
+    pub fn intersects_inclusive(&self, other: &Self) -> bool {
+        self.to_box2d().intersects_inclusive(&other.to_box2d())
+    }
+
+    /// Returns `true` if the two rectangles only share (part of) an edge or a corner.
+    #[inline]
+    pub fn touches(&self, other: &Self) -> bool {
+        self.to_box2d().touches(&other.to_box2d())
+    }
+
+    /// Returns the point of this rectangle that is closest to `p`, that is `p` itself
+    /// if it is inside of the rectangle or else the nearest point on its edges.
+    ///
+    /// ```rust
+    /// # use euclid::{point2, rect};
+    /// # use euclid::default::Rect;
+    /// let r: Rect<i32> = rect(0, 0, 10, 10);
+    /// assert_eq!(r.closest_point_to(point2(5, 5)), point2(5, 5));
+    /// assert_eq!(r.closest_point_to(point2(-5, 20)), point2(0, 10));
+    /// ```
+    #[inline]
+    pub fn closest_point_to(&self, p: Point2D<T, U>) -> Point2D<T, U> {
+        p.clamp(self.min(), self.max())
+    }
 }
 
 impl<T, U> Rect<T, U>
 where
     T: Copy + PartialOrd + Add<T, Output = T> + Sub<T, Output = T>,
 {
+    /// Returns `true` if the point is inside of this rectangle or at most `eps` away
+    /// from it on each axis, edges included. See `Box2D::contains_eps`.
+    #[inline]
+    pub fn contains_eps(&self, p: Point2D<T, U>, eps: T) -> bool {
+        self.to_box2d().contains_eps(p, eps)
+    }
+
+    /// Returns `true` if the two rectangles intersect after moving the edges of this
+    /// rectangle outward by `eps`. See `Box2D::intersects_eps`.
+    #[inline]
+    pub fn intersects_eps(&self, other: &Self, eps: T) -> bool {
+        self.to_box2d().intersects_eps(&other.to_box2d(), eps)
+    }
+}
+
+impl<T, U> Rect<T, U>
+where
+    T: Copy + PartialOrd + Add<T, Output = T> + Sub<T, Output = T>,
+{
+    /// Returns true if the size of this rectangle is not negative, all of its
+    /// components are finite and its maximum edges can be represented.
+    ///
+    /// Rectangles produced by corrupt computations (negative sizes, overflowing
+    /// values or NaNs) are not well formed. Note that empty rectangles
+    /// can be well formed.
+    ///
+    /// ```rust
+    /// use euclid::default::Rect;
+    /// use euclid::rect;
+    ///
+    /// let empty: Rect<f32> = rect(0.0, 0.0, 10.0, 0.0);
+    /// assert!(empty.is_well_formed());
+    ///
+    /// let negative: Rect<f32> = rect(0.0, 0.0, -1.0, 10.0);
+    /// assert!(!negative.is_well_formed());
+    ///
+    /// let nan: Rect<f32> = rect(0.0, f32::NAN, 1.0, 1.0);
+    /// assert!(!nan.is_well_formed());
+    /// ```
+    pub fn is_well_formed(&self) -> bool
+    where
+        T: Bounded,
+    {
+        // Checking the maximum edges against the largest value, rather than computing
+        // them, doesn't overflow for integer scalars.
+        self.has_valid_components()
+            && self.origin.x <= T::max_value() - self.size.width
+            && self.origin.y <= T::max_value() - self.size.height
+    }
+
+    /// Returns true if the origin is finite and the size is not negative.
+    #[allow(clippy::eq_op)]
+    fn has_valid_components(&self) -> bool {
+        // `v - v` is zero for finite values and NaN for infinities and NaNs, which
+        // avoids requiring `Float` and works for integer and fixed-point scalars.
+        let finite = |v: T| v - v == v - v;
+        let non_negative = |v: T| v >= v - v;
+
+        finite(self.origin.x)
+            && finite(self.origin.y)
+            && non_negative(self.size.width)
+            && non_negative(self.size.height)
+    }
+
+    /// With the `debug-checks` feature, asserts in debug builds that the origin of this
+    /// rectangle is finite and its size is not negative.
+    #[inline]
+    #[cfg_attr(not(feature = "debug-checks"), allow(unused_variables))]
+    fn debug_check_well_formed(&self, method: &str) {
+        #[cfg(feature = "debug-checks")]
+        debug_assert!(self.has_valid_components(), "Rect::{}: the rectangle is not well formed", method);
+    }
+
     #[inline]
     pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.debug_check_well_formed("intersection");
+        other.debug_check_well_formed("intersection");
+
         let box2d = self.to_box2d().intersection_unchecked(&other.to_box2d());
 
         if box2d.is_empty() {
@@ -238,12 +505,110 @@ where
 
         Some(box2d.to_rect())
     }
+
+    /// Computes the intersection of two rectangles, edges included, returning `None`
+    /// if they do not intersect.
+    ///
+    /// Unlike `intersection`, rectangles that only touch have a zero-area intersection.
+    ///
+    /// ```rust
+    /// use euclid::default::Rect;
+    /// use euclid::rect;
+    ///
+    /// let a: Rect<i32> = rect(0, 0, 10, 10);
+    /// let b = rect(10, 0, 10, 10);
+    /// assert_eq!(a.intersection(&b), None);
+    /// assert_eq!(a.intersection_inclusive(&b), Some(rect(10, 0, 0, 10)));
+    /// ```
+    #[inline]
+    pub fn intersection_inclusive(&self, other: &Self) -> Option<Self> {
+        self.debug_check_well_formed("intersection_inclusive");
+        other.debug_check_well_formed("intersection_inclusive");
+
+        self.to_box2d()
+            .intersection_inclusive(&other.to_box2d())
+            .map(|b| b.to_rect())
+    }
+
+    /// Computes the intersection of two rectangles, telling apart rectangles that are
+    /// disjoint from rectangles that only touch.
+    ///
+    /// ```rust
+    /// use euclid::default::Rect;
+    /// use euclid::{rect, RectIntersection};
+    ///
+    /// let a: Rect<i32> = rect(0, 0, 10, 10);
+    /// assert_eq!(a.intersection_detailed(&rect(5, 5, 10, 10)), RectIntersection::Overlapping(rect(5, 5, 5, 5)));
+    /// assert_eq!(a.intersection_detailed(&rect(10, 5, 10, 10)), RectIntersection::Degenerate(rect(10, 5, 0, 5)));
+    /// assert_eq!(a.intersection_detailed(&rect(11, 5, 10, 10)), RectIntersection::Disjoint);
+    /// ```
+    pub fn intersection_detailed(&self, other: &Self) -> RectIntersection<T, U> {
+        self.debug_check_well_formed("intersection_detailed");
+        other.debug_check_well_formed("intersection_detailed");
+
+        match self.to_box2d().intersection_inclusive(&other.to_box2d()) {
+            None => RectIntersection::Disjoint,
+            Some(b) if b.is_empty() => RectIntersection::Degenerate(b.to_rect()),
+            Some(b) => RectIntersection::Overlapping(b.to_rect()),
+        }
+    }
+}
+
+impl<T, U> Rect<T, U>
+where
+    T: Copy + Zero + PartialOrd + Add<T, Output = T> + Sub<T, Output = T> + Neg<Output = T>,
+{
+    /// Returns the smallest translation that moves this rectangle out of `other`, or
+    /// `None` if the two rectangles don't intersect.
+    ///
+    /// The translation is always along a single axis, the horizontal one being picked
+    /// when both are equally short.
+    ///
+    /// ```rust
+    /// # use euclid::{rect, vec2};
+    /// # use euclid::default::Rect;
+    /// let a: Rect<i32> = rect(0, 0, 10, 10);
+    /// let b: Rect<i32> = rect(8, 3, 10, 10);
+    /// assert_eq!(a.overlap_vector(&b), Some(vec2(-2, 0)));
+    /// assert_eq!(a.translate(vec2(-2, 0)).intersects(&b), false);
+    /// ```
+    pub fn overlap_vector(&self, other: &Self) -> Option<Vector2D<T, U>> {
+        if !self.intersects(other) {
+            return None;
+        }
+
+        let abs = |v: T| if v < T::zero() { -v } else { v };
+        let shortest = |a: T, b: T| if abs(b) < abs(a) { b } else { a };
+
+        let dx = shortest(other.min_x() - self.max_x(), other.max_x() - self.min_x());
+        let dy = shortest(other.min_y() - self.max_y(), other.max_y() - self.min_y());
+
+        Some(if abs(dy) < abs(dx) {
+            Vector2D::new(T::zero(), dy)
+        } else {
+            Vector2D::new(dx, T::zero())
+        })
+    }
 }
 
 impl<T, U> Rect<T, U>
 where
     T: Copy + Add<T, Output = T> + Sub<T, Output = T>,
 {
+    /// Inflates the rectangle by `width` on the left and right sides, and by `height`
+    /// on the top and bottom sides.
+    ///
+    /// To inflate each side by a different amount, use `outer_rect`:
+    ///
+    /// ```rust
+    /// use euclid::default::{Rect, SideOffsets2D};
+    /// use euclid::rect;
+    ///
+    /// let r: Rect<i32> = rect(10, 10, 100, 50);
+    /// assert_eq!(r.inflate(5, 2), rect(5, 8, 110, 54));
+    /// // A shadow offset towards the bottom right.
+    /// assert_eq!(r.outer_rect(SideOffsets2D::new(0, 8, 8, 0)), rect(10, 10, 108, 58));
+    /// ```
     #[inline]
     #[must_use]
     pub fn inflate(&self, width: T, height: T) -> Self {
@@ -255,6 +620,15 @@ where
             ),
         )
     }
+
+    /// Returns this rectangle expressed relative to another origin, with the same size.
+    ///
+    /// See `Point2D::relative_to`.
+    #[inline]
+    #[must_use]
+    pub fn relative_to(&self, origin: Point2D<T, U>) -> Self {
+        Rect::new(self.origin.relative_to(origin), self.size)
+    }
 }
 
 impl<T, U> Rect<T, U>
@@ -316,6 +690,29 @@ where
     }
 }
 
+impl<T, U> Rect<T, U>
+where
+    T: Copy + One + Add<T, Output = T> + Sub<T, Output = T> + Mul<T, Output = T>,
+{
+    /// Returns the area affected by blurring the content of this rectangle with a
+    /// gaussian blur of standard deviation `sigma`.
+    ///
+    /// This is this rectangle inflated by `3 * sigma` on all sides, see
+    /// `SideOffsets2D::blur_outset`.
+    ///
+    /// ```rust
+    /// # use euclid::rect;
+    /// # use euclid::default::Rect;
+    /// let r: Rect<f32> = rect(10.0, 10.0, 100.0, 50.0);
+    /// assert_eq!(r.inflate_for_blur(2.0), rect(4.0, 4.0, 112.0, 62.0));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn inflate_for_blur(&self, sigma: T) -> Self {
+        self.outer_rect(SideOffsets2D::blur_outset(sigma))
+    }
+}
+
 impl<T, U> Rect<T, U>
 where
     T: Copy + Zero + PartialOrd + Sub<T, Output = T>,
@@ -352,6 +749,16 @@ where
     }
 }
 
+impl<T, U> Interpolate<T> for Rect<T, U>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    #[inline]
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
 impl<T, U> Rect<T, U>
 where
     T: Copy + One + Add<Output = T> + Div<Output = T>,
@@ -362,12 +769,174 @@ where
     }
 }
 
+/// The intersection of two rectangles, as returned by `Rect::intersection_detailed`.
+pub enum RectIntersection<T, U> {
+    /// The rectangles don't intersect, not even along an edge or at a corner.
+    Disjoint,
+    /// The rectangles only share (part of) an edge or a corner, or one of them has a
+    /// zero area. The intersection has a zero area.
+    Degenerate(Rect<T, U>),
+    /// The interiors of the rectangles overlap.
+    Overlapping(Rect<T, U>),
+}
+
+impl<T, U> RectIntersection<T, U> {
+    /// Returns the intersection, including a zero-area one, or `None` if the rectangles
+    /// are disjoint.
+    #[inline]
+    pub fn rect(self) -> Option<Rect<T, U>> {
+        match self {
+            RectIntersection::Disjoint => None,
+            RectIntersection::Degenerate(r) | RectIntersection::Overlapping(r) => Some(r),
+        }
+    }
+}
+
+impl<T: Copy, U> Copy for RectIntersection<T, U> {}
+
+impl<T: Clone, U> Clone for RectIntersection<T, U> {
+    fn clone(&self) -> Self {
+        match self {
+            RectIntersection::Disjoint => RectIntersection::Disjoint,
+            RectIntersection::Degenerate(r) => RectIntersection::Degenerate(r.clone()),
+            RectIntersection::Overlapping(r) => RectIntersection::Overlapping(r.clone()),
+        }
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for RectIntersection<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (RectIntersection::Disjoint, RectIntersection::Disjoint) => true,
+            (RectIntersection::Degenerate(a), RectIntersection::Degenerate(b)) => a == b,
+            (RectIntersection::Overlapping(a), RectIntersection::Overlapping(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<T: Eq, U> Eq for RectIntersection<T, U> {}
+
+impl<T: fmt::Debug, U> fmt::Debug for RectIntersection<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RectIntersection::Disjoint => write!(f, "Disjoint"),
+            RectIntersection::Degenerate(r) => f.debug_tuple("Degenerate").field(r).finish(),
+            RectIntersection::Overlapping(r) => f.debug_tuple("Overlapping").field(r).finish(),
+        }
+    }
+}
+
+/// Alignment of a rectangle along one axis of a container, used by `Rect::align_inside`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Align {
+    /// Aligns the minimum edges (left or top).
+    Start,
+    /// Centers the rectangle.
+    Center,
+    /// Aligns the maximum edges (right or bottom).
+    End,
+}
+
+impl<T, U> Rect<T, U>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Div<Output = T>,
+{
+    /// Returns this rectangle moved such that it is aligned inside of `container`
+    /// according to the horizontal and vertical alignments. The size is preserved.
+    ///
+    /// If this rectangle is bigger than the container it overflows it on the side(s)
+    /// opposite to the alignment.
+    ///
+    /// ```rust
+    /// # use euclid::{rect, Align};
+    /// # use euclid::default::Rect;
+    /// let container: Rect<i32> = rect(10, 10, 100, 50);
+    /// let r: Rect<i32> = rect(0, 0, 20, 10);
+    /// assert_eq!(r.align_inside(&container, Align::End, Align::Start), rect(90, 10, 20, 10));
+    /// assert_eq!(r.align_inside(&container, Align::Center, Align::Center), rect(50, 30, 20, 10));
+    /// ```
+    pub fn align_inside(&self, container: &Self, horizontal: Align, vertical: Align) -> Self {
+        let two = T::one() + T::one();
+        let align = |start: T, available: T, align: Align| match align {
+            Align::Start => start,
+            Align::Center => start + available / two,
+            Align::End => start + available,
+        };
+
+        Rect::new(
+            Point2D::new(
+                align(container.origin.x, container.size.width - self.size.width, horizontal),
+                align(container.origin.y, container.size.height - self.size.height, vertical),
+            ),
+            self.size,
+        )
+    }
+}
+
+/// The corner of a rectangle that stays in place when resizing it with
+/// `Rect::with_aspect_ratio`.
+///
+/// This assumes y oriented downward: the top edge is the one with the smallest y.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AnchorCorner {
+    /// The corner with the smallest x and y.
+    TopLeft,
+    /// The corner with the largest x and the smallest y.
+    TopRight,
+    /// The corner with the smallest x and the largest y.
+    BottomLeft,
+    /// The corner with the largest x and y.
+    BottomRight,
+}
+
+impl<T, U> Rect<T, U>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    /// Returns the largest rectangle inside of this one that has the given aspect ratio
+    /// (width divided by height) and shares the `anchor` corner with it.
+    ///
+    /// Either the width or the height is kept and the other one shrinks. To center the
+    /// result instead, for example when letterboxing a viewport, use `align_inside`.
+    ///
+    /// ```rust
+    /// # use euclid::{rect, AnchorCorner};
+    /// # use euclid::default::Rect;
+    /// let window: Rect<f32> = rect(0.0, 0.0, 800.0, 400.0);
+    /// assert_eq!(window.with_aspect_ratio(1.0, AnchorCorner::TopRight), rect(400.0, 0.0, 400.0, 400.0));
+    /// assert_eq!(window.with_aspect_ratio(4.0, AnchorCorner::BottomLeft), rect(0.0, 200.0, 800.0, 200.0));
+    /// ```
+    #[must_use]
+    pub fn with_aspect_ratio(&self, ratio: T, anchor: AnchorCorner) -> Self {
+        let width = self.size.height * ratio;
+        let size = if width <= self.size.width {
+            Size2D::new(width, self.size.height)
+        } else {
+            Size2D::new(self.size.width, self.size.width / ratio)
+        };
+
+        let max = self.max();
+        let origin = match anchor {
+            AnchorCorner::TopLeft => self.origin,
+            AnchorCorner::TopRight => Point2D::new(max.x - size.width, self.origin.y),
+            AnchorCorner::BottomLeft => Point2D::new(self.origin.x, max.y - size.height),
+            AnchorCorner::BottomRight => max - size.to_vector(),
+        };
+
+        Rect::new(origin, size)
+    }
+}
+
 impl<T, U> Rect<T, U>
 where
     T: Copy + PartialOrd + Add<T, Output = T> + Sub<T, Output = T> + Zero,
 {
     #[inline]
     pub fn union(&self, other: &Self) -> Self {
+        self.debug_check_well_formed("union");
+        other.debug_check_well_formed("union");
+
         self.to_box2d().union(&other.to_box2d()).to_rect()
     }
 }
@@ -385,11 +954,251 @@ impl<T, U> Rect<T, U> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<T, U> Rect<T, U>
+where
+    T: Copy + Zero + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    /// Replaces each rectangle of the list with its intersection with this one, and
+    /// removes the rectangles that don't intersect it.
+    ///
+    /// This gives the same result as calling `intersection` on each rectangle, in a
+    /// single pass over the list that keeps the order of the rectangles.
+    ///
+    /// ```rust
+    /// use euclid::default::Rect;
+    /// use euclid::rect;
+    ///
+    /// let clip: Rect<i32> = rect(0, 0, 100, 100);
+    /// let mut bounds = vec![rect(-10, 10, 20, 20), rect(200, 0, 10, 10), rect(50, 90, 10, 20)];
+    /// clip.intersect_all(&mut bounds);
+    /// assert_eq!(bounds, vec![rect(0, 10, 10, 20), rect(50, 90, 10, 10)]);
+    /// ```
+    pub fn intersect_all(&self, rects: &mut Vec<Self>) {
+        self.debug_check_well_formed("intersect_all");
+
+        let clip = self.to_box2d();
+        let mut kept = 0;
+        for i in 0..rects.len() {
+            let b = clip.intersection_unchecked(&rects[i].to_box2d());
+            if !b.is_empty() {
+                rects[kept] = b.to_rect();
+                kept += 1;
+            }
+        }
+        rects.truncate(kept);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, U> Rect<T, U>
+where
+    T: Copy + Zero + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + NumCast,
+{
+    /// Returns the fraction of the area of this rectangle that is covered by the union
+    /// of the given rectangles, between 0 and 1.
+    ///
+    /// Overlaps between the occluders are only counted once. Empty rectangles,
+    /// including this one, are considered to have no area, so an empty rectangle has a
+    /// coverage of 0.
+    ///
+    /// ```rust
+    /// use euclid::default::Rect;
+    /// use euclid::rect;
+    ///
+    /// let layer: Rect<i32> = rect(0, 0, 10, 10);
+    /// let occluders = [rect(0, 0, 10, 5), rect(0, 0, 5, 10), rect(50, 50, 10, 10)];
+    /// assert_eq!(layer.coverage_by(&occluders), 0.75);
+    /// ```
+    pub fn coverage_by(&self, rects: &[Self]) -> f32 {
+        let area = self.to_box2d();
+        if area.is_empty() {
+            return 0.0;
+        }
+
+        let clipped: Vec<Box2D<T, U>> = rects
+            .iter()
+            .map(|r| r.to_box2d())
+            .filter(|b| !b.is_empty())
+            .filter_map(|b| b.intersection(&area))
+            .collect();
+
+        let covered: f32 = NumCast::from(union_area(&clipped)).unwrap_or(0.0);
+        let total: f32 = NumCast::from(area.area()).unwrap_or(0.0);
+        if total > 0.0 {
+            (covered / total).min(1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Returns the area covered by the union of the rectangles, overlapping parts being
+/// counted once.
+///
+/// Empty rectangles, including those with a negative or NaN size, are ignored. The
+/// area is computed with a sweep line over the vertical edges of the rectangles,
+/// which avoids subtracting the rectangles from each other.
+///
+/// ```rust
+/// use euclid::default::Rect;
+/// use euclid::{area_of_union, rect};
+///
+/// let rects: [Rect<i32>; 3] = [rect(0, 0, 10, 10), rect(5, 5, 10, 10), rect(100, 0, 1, 1)];
+/// assert_eq!(area_of_union(&rects), 176);
+/// ```
+#[cfg(feature = "std")]
+pub fn area_of_union<T, U>(rects: &[Rect<T, U>]) -> T
+where
+    T: Copy + Zero + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    let boxes: Vec<Box2D<T, U>> = rects.iter().map(|r| r.to_box2d()).collect();
+    union_area(&boxes)
+}
+
+/// Returns the area of the union of the boxes, with a sweep over the vertical edges.
+///
+/// The vertical edges are visited from left to right. Each left edge adds the vertical
+/// range of its box to a segment tree over the y coordinates of the boxes, and each right
+/// edge removes it. Between two edges, the covered area grows by the covered length of
+/// the tree times the distance between the edges, so the whole sweep is O(n log n).
+#[cfg(feature = "std")]
+fn union_area<T, U>(boxes: &[Box2D<T, U>]) -> T
+where
+    T: Copy + Zero + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    let cmp = |a: &T, b: &T| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+    let boxes: Vec<&Box2D<T, U>> = boxes.iter().filter(|b| !b.is_empty()).collect();
+    if boxes.is_empty() {
+        return T::zero();
+    }
+
+    let mut ys: Vec<T> = boxes.iter().flat_map(|b| once(b.min.y).chain(once(b.max.y))).collect();
+    ys.sort_by(cmp);
+    ys.dedup();
+    let index = |y: T| ys.binary_search_by(|v| cmp(v, &y)).unwrap();
+
+    let mut edges = Vec::with_capacity(boxes.len() * 2);
+    for b in &boxes {
+        let (y0, y1) = (index(b.min.y), index(b.max.y));
+        edges.push((b.min.x, y0, y1, 1));
+        edges.push((b.max.x, y0, y1, -1));
+    }
+    edges.sort_by(|a, b| cmp(&a.0, &b.0));
+
+    let mut tree = CoverageTree::new(&ys);
+    let mut area = T::zero();
+    let mut previous_x = edges[0].0;
+    for &(x, y0, y1, delta) in &edges {
+        area = area + tree.covered_length() * (x - previous_x);
+        previous_x = x;
+        tree.add(y0, y1, delta);
+    }
+
+    area
+}
+
+/// A segment tree over the intervals between consecutive sorted coordinates, counting
+/// how many ranges cover each node and storing the length covered under each node.
+#[cfg(feature = "std")]
+struct CoverageTree<'a, T> {
+    coords: &'a [T],
+    counts: Vec<i32>,
+    covered: Vec<T>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, T> CoverageTree<'a, T>
+where
+    T: Copy + Zero + Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates an empty tree over at least two coordinates.
+    fn new(coords: &'a [T]) -> Self {
+        let nodes = 4 * (coords.len() - 1);
+        CoverageTree {
+            coords,
+            counts: vec![0; nodes],
+            covered: vec![T::zero(); nodes],
+        }
+    }
+
+    /// The length covered by at least one range.
+    fn covered_length(&self) -> T {
+        self.covered[1]
+    }
+
+    /// Adds `delta` to the number of ranges covering `coords[lo]..coords[hi]`.
+    fn add(&mut self, lo: usize, hi: usize, delta: i32) {
+        let last = self.coords.len() - 1;
+        self.update(1, 0, last, lo, hi, delta);
+    }
+
+    fn update(&mut self, node: usize, start: usize, end: usize, lo: usize, hi: usize, delta: i32) {
+        if hi <= start || end <= lo {
+            return;
+        }
+
+        if lo <= start && end <= hi {
+            self.counts[node] += delta;
+        } else {
+            // Partially covered nodes span at least two intervals.
+            let mid = (start + end) / 2;
+            self.update(2 * node, start, mid, lo, hi, delta);
+            self.update(2 * node + 1, mid, end, lo, hi, delta);
+        }
+
+        self.covered[node] = if self.counts[node] > 0 {
+            self.coords[end] - self.coords[start]
+        } else if end - start == 1 {
+            T::zero()
+        } else {
+            self.covered[2 * node] + self.covered[2 * node + 1]
+        };
+    }
+}
+
 impl<T: Copy + Mul<T, Output = T>, U> Rect<T, U> {
     #[inline]
     pub fn area(&self) -> T {
         self.size.area()
     }
+
+    /// Returns the area of the rectangle, or `None` if it overflows.
+    #[inline]
+    pub fn checked_area(&self) -> Option<T>
+    where
+        T: CheckedMul,
+    {
+        self.size.checked_area()
+    }
+}
+
+/// Comparators, to be used with functions such as `slice::sort_by`.
+impl<T: Copy + Ord, U> Rect<T, U> {
+    /// Compares the areas of two rectangles.
+    ///
+    /// ```rust
+    /// use euclid::default::Rect;
+    /// use euclid::rect;
+    ///
+    /// let mut rects: Vec<Rect<i32>> = vec![rect(0, 0, 10, 10), rect(5, 5, 2, 3), rect(1, 1, 4, 4)];
+    /// rects.sort_by(Rect::cmp_by_area);
+    /// assert_eq!(rects, [rect(5, 5, 2, 3), rect(1, 1, 4, 4), rect(0, 0, 10, 10)]);
+    /// ```
+    #[inline]
+    pub fn cmp_by_area(&self, other: &Self) -> Ordering
+    where
+        T: Mul<Output = T>,
+    {
+        self.area().cmp(&other.area())
+    }
+
+    /// Compares the top-left corners of two rectangles in reading order: by `y`,
+    /// then by `x`.
+    #[inline]
+    pub fn cmp_by_top_left(&self, other: &Self) -> Ordering {
+        self.origin.y.cmp(&other.origin.y).then_with(|| self.origin.x.cmp(&other.origin.x))
+    }
 }
 
 impl<T: Copy + Zero + PartialOrd, U> Rect<T, U> {
@@ -497,6 +1306,14 @@ impl<T: Copy, U> Rect<T, U> {
     pub fn cast_unit<V>(&self) -> Rect<T, V> {
         Rect::new(self.origin.cast_unit(), self.size.cast_unit())
     }
+
+    /// Swap the x and y axes, that is mirror the rectangle around the `x = y`
+    /// diagonal.
+    #[inline]
+    #[must_use]
+    pub fn transposed(&self) -> Self {
+        Rect::new(self.origin.yx(), self.size.transposed())
+    }
 }
 
 impl<T: NumCast + Copy, U> Rect<T, U> {
@@ -522,6 +1339,49 @@ impl<T: NumCast + Copy, U> Rect<T, U> {
         }
     }
 
+    /// Fallible cast from one numeric representation to another, preserving the units.
+    ///
+    /// Unlike `try_cast`, the error names the first component that could not be cast.
+    pub fn try_cast_detailed<NewT: NumCast>(&self) -> Result<Rect<NewT, U>, CastError> {
+        Ok(rect(
+            cast_component(self.origin.x, "origin.x")?,
+            cast_component(self.origin.y, "origin.y")?,
+            cast_component(self.size.width, "size.width")?,
+            cast_component(self.size.height, "size.height")?,
+        ))
+    }
+
+    /// Cast from one numeric representation to another, preserving the units and
+    /// rounding the edges according to `mode` first.
+    ///
+    /// The edges rather than the origin and size are rounded, so that `Round` behaves
+    /// like `round()` and `RoundOut` like `round_out()`.
+    ///
+    /// ```rust
+    /// # use euclid::rect;
+    /// # use euclid::num::RoundingMode;
+    /// enum Mm {}
+    ///
+    /// let r = rect::<_, Mm>(0.5, 0.2, 2.0, 1.6);
+    /// assert_eq!(r.cast_with::<i32>(RoundingMode::Floor), rect(0, 0, 2, 1));
+    /// assert_eq!(r.cast_with::<i32>(RoundingMode::Round), rect(1, 0, 2, 2));
+    /// assert_eq!(r.cast_with::<i32>(RoundingMode::RoundOut), rect(0, 0, 3, 2));
+    /// ```
+    pub fn cast_with<NewT: NumCast>(&self, mode: RoundingMode) -> Rect<NewT, U>
+    where
+        T: Round + RoundHalfEven + Floor + Ceil + Add<Output = T> + Sub<Output = T>,
+    {
+        let b = self.to_box2d();
+        let b = match mode {
+            RoundingMode::Floor => Box2D::new(b.min.floor(), b.max.floor()),
+            RoundingMode::Ceil => Box2D::new(b.min.ceil(), b.max.ceil()),
+            RoundingMode::Round => b.round(),
+            RoundingMode::RoundHalfEven => b.round_half_even(),
+            RoundingMode::RoundOut => b.round_out(),
+        };
+        b.to_rect().cast()
+    }
+
     // Convenience functions for common casts
 
     /// Cast into an `f32` rectangle.
@@ -587,12 +1447,243 @@ impl<T: NumCast + Copy, U> Rect<T, U> {
     }
 }
 
+impl<T, U> Rect<T, U>
+where
+    T: Copy + Zero + One + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Euclid,
+{
+    /// Returns an iterator over the coordinates of the cells of a grid that this
+    /// rectangle intersects.
+    ///
+    /// The grid is aligned on the origin of the space and cell `(i, j)` covers
+    /// `i * cell_size.width` to `(i + 1) * cell_size.width` horizontally, and likewise
+    /// vertically. The cell size must be positive.
+    ///
+    /// ```rust
+    /// # use euclid::{point2, rect, size2};
+    /// # use euclid::default::Rect;
+    /// let r: Rect<i32> = rect(-5, 0, 20, 10);
+    /// let cells: Vec<_> = r.cells_intersected(size2(10, 10)).collect();
+    /// assert_eq!(cells, vec![point2(-1, 0), point2(0, 0), point2(1, 0)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a cell coordinate doesn't fit in an `i32`.
+    pub fn cells_intersected(&self, cell_size: Size2D<T, U>) -> GridCells
+    where
+        T: NumCast,
+    {
+        let b = self.to_box2d();
+        if b.is_empty() {
+            return GridCells::new(Box2D::zero());
+        }
+
+        let cell = |v: T| -> i32 { NumCast::from(v).expect("grid cell coordinate out of range") };
+        GridCells::new(Box2D::new(
+            Point2D::new(
+                cell(floor_div(b.min.x, cell_size.width)),
+                cell(floor_div(b.min.y, cell_size.height)),
+            ),
+            Point2D::new(
+                cell(ceil_div(b.max.x, cell_size.width)),
+                cell(ceil_div(b.max.y, cell_size.height)),
+            ),
+        ))
+    }
+
+    /// Returns this rectangle with its edges snapped to the lines of a grid aligned on
+    /// the origin of the space, according to the rounding mode.
+    ///
+    /// The cell size must be positive.
+    ///
+    /// ```rust
+    /// # use euclid::{rect, size2};
+    /// # use euclid::default::Rect;
+    /// # use euclid::num::RoundingMode;
+    /// let r: Rect<i32> = rect(-5, 3, 20, 10);
+    /// assert_eq!(r.snap_to_grid(size2(8, 8), RoundingMode::RoundOut), rect(-8, 0, 24, 16));
+    /// assert_eq!(r.snap_to_grid(size2(8, 8), RoundingMode::Floor), rect(-8, 0, 16, 8));
+    /// ```
+    pub fn snap_to_grid(&self, cell_size: Size2D<T, U>, mode: RoundingMode) -> Self {
+        let two = T::one() + T::one();
+        let snap = |v: T, cell: T, is_max: bool| -> T {
+            let n = match mode {
+                RoundingMode::Floor => floor_div(v, cell),
+                RoundingMode::Ceil => ceil_div(v, cell),
+                RoundingMode::Round => floor_div(v + cell / two, cell),
+                RoundingMode::RoundHalfEven => {
+                    let n = floor_div(v + cell / two, cell);
+                    // Ties are rounded up above, go back down if that's odd.
+                    let is_tie = (n * cell - v) * two == cell;
+                    if is_tie && floor_div(n, two) * two != n {
+                        n - T::one()
+                    } else {
+                        n
+                    }
+                }
+                RoundingMode::RoundOut if is_max => ceil_div(v, cell),
+                RoundingMode::RoundOut => floor_div(v, cell),
+            };
+            n * cell
+        };
+
+        let b = self.to_box2d();
+        Box2D::new(
+            Point2D::new(
+                snap(b.min.x, cell_size.width, false),
+                snap(b.min.y, cell_size.height, false),
+            ),
+            Point2D::new(
+                snap(b.max.x, cell_size.width, true),
+                snap(b.max.y, cell_size.height, true),
+            ),
+        )
+        .to_rect()
+    }
+
+    /// Inflates this rectangle like `inflate` and then snaps its edges outward to the
+    /// lines of a square grid of step `grid`, aligned on the origin of the space.
+    ///
+    /// The result always contains the inflated rectangle. This is useful for example
+    /// to expand the area affected by a blur to whole tiles. The grid step must be
+    /// positive.
+    ///
+    /// ```rust
+    /// # use euclid::rect;
+    /// # use euclid::default::Rect;
+    /// let r: Rect<f32> = rect(10.0, 20.0, 30.0, 30.0);
+    /// assert_eq!(r.inflate_round_to(5.0, 5.0, 16.0), rect(0.0, 0.0, 48.0, 64.0));
+    /// ```
+    #[must_use]
+    pub fn inflate_round_to(&self, width: T, height: T, grid: T) -> Self {
+        self.inflate(width, height)
+            .snap_to_grid(Size2D::new(grid, grid), RoundingMode::RoundOut)
+    }
+}
+
+impl<U> Rect<u32, U> {
+    /// Returns the smallest rectangle containing this one whose edges are on the lines
+    /// of a grid of `tile_size` tiles, aligned on the origin of the space.
+    ///
+    /// This is `snap_to_grid` with `RoundingMode::RoundOut`, using bit masks. Both
+    /// components of `tile_size` must be powers of two.
+    ///
+    /// ```rust
+    /// # use euclid::{rect, size2};
+    /// # use euclid::default::Rect;
+    /// let r: Rect<u32> = rect(100, 30, 200, 20);
+    /// assert_eq!(r.round_out_to_tiles(size2(256, 16)), rect(0, 16, 512, 48));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an edge of the result doesn't fit in an `u32`.
+    pub fn round_out_to_tiles(&self, tile_size: Size2D<u32, U>) -> Self {
+        debug_assert!(tile_size.is_power_of_two());
+        let align_out = |start: u32, length: u32, tile: u32| -> (u32, u32) {
+            let mask = tile - 1;
+            let end = start
+                .checked_add(length)
+                .and_then(|end| end.checked_add(mask))
+                .expect("tile-aligned rectangle out of range")
+                & !mask;
+            let start = start & !mask;
+            (start, end - start)
+        };
+
+        let (x, width) = align_out(self.origin.x, self.size.width, tile_size.width);
+        let (y, height) = align_out(self.origin.y, self.size.height, tile_size.height);
+        rect(x, y, width, height)
+    }
+}
+
 impl<T: Float, U> Rect<T, U> {
     /// Returns true if all members are finite.
     #[inline]
     pub fn is_finite(self) -> bool {
         self.origin.is_finite() && self.size.is_finite()
     }
+
+    /// Returns the distance between `p` and the closest point of this rectangle,
+    /// which is zero if `p` is inside of the rectangle.
+    #[inline]
+    pub fn distance_to_point(&self, p: Point2D<T, U>) -> T {
+        (p - self.closest_point_to(p)).length()
+    }
+
+    /// Returns the part of `segment` that is inside of this rectangle, edges included,
+    /// or `None` if the segment doesn't touch the rectangle.
+    ///
+    /// The returned segment goes in the same direction as `segment`. Ends of the segment
+    /// that are inside of the rectangle are returned unchanged.
+    ///
+    /// Returns `None` if the rectangle has a negative size or if any of the coordinates
+    /// is NaN.
+    ///
+    /// ```rust
+    /// # use euclid::{point2, rect};
+    /// # use euclid::default::{LineSegment2D, Rect};
+    /// let r: Rect<f32> = rect(0.0, 0.0, 10.0, 10.0);
+    /// let s = LineSegment2D::new(point2(-5.0, 5.0), point2(5.0, 15.0));
+    /// assert_eq!(r.clip_segment(&s), Some(LineSegment2D::new(point2(0.0, 10.0), point2(0.0, 10.0))));
+    ///
+    /// let s = LineSegment2D::new(point2(5.0, 5.0), point2(20.0, 5.0));
+    /// assert_eq!(r.clip_segment(&s), Some(LineSegment2D::new(point2(5.0, 5.0), point2(10.0, 5.0))));
+    /// ```
+    pub fn clip_segment(&self, segment: &LineSegment2D<T, U>) -> Option<LineSegment2D<T, U>> {
+        let min = self.min();
+        let max = self.max();
+        if !(min.x <= max.x && min.y <= max.y) {
+            return None;
+        }
+        let (a, b) = (segment.from, segment.to);
+        if a.x.is_nan() || a.y.is_nan() || b.x.is_nan() || b.y.is_nan() {
+            return None;
+        }
+
+        // Liang-Barsky: the segment is `from + d * t` for t in [0, 1]. Each edge of the
+        // rectangle is a half-plane `p * t <= q` that narrows down the range of t.
+        let from = segment.from;
+        let d = segment.to_vector();
+        let (mut t0, mut t1) = (T::zero(), T::one());
+        let half_planes = [
+            (-d.x, from.x - min.x),
+            (d.x, max.x - from.x),
+            (-d.y, from.y - min.y),
+            (d.y, max.y - from.y),
+        ];
+        for &(p, q) in &half_planes {
+            // Infinite coordinates can still give NaN differences.
+            if p.is_nan() || q.is_nan() {
+                return None;
+            }
+            if p == T::zero() {
+                // Parallel to the edge, and on the outside of it.
+                if q < T::zero() {
+                    return None;
+                }
+                continue;
+            }
+
+            let t = q / p;
+            if p < T::zero() {
+                if t > t1 {
+                    return None;
+                }
+                t0 = t0.max(t);
+            } else {
+                if t < t0 {
+                    return None;
+                }
+                t1 = t1.min(t);
+            }
+        }
+
+        let clipped_from = if t0 == T::zero() { from } else { from + d * t0 };
+        let clipped_to = if t1 == T::one() { segment.to } else { from + d * t1 };
+
+        Some(LineSegment2D::new(clipped_from, clipped_to))
+    }
 }
 
 impl<T: Floor + Ceil + Round + Add<T, Output = T> + Sub<T, Output = T>, U> Rect<T, U> {
@@ -655,6 +1746,19 @@ where
     }
 }
 
+impl<T, U> From<(T, T, T, T)> for Rect<T, U> {
+    /// Creates a rectangle from an `(x, y, width, height)` tuple.
+    fn from((x, y, w, h): (T, T, T, T)) -> Self {
+        rect(x, y, w, h)
+    }
+}
+
+impl<T, U> From<(Point2D<T, U>, Size2D<T, U>)> for Rect<T, U> {
+    fn from((origin, size): (Point2D<T, U>, Size2D<T, U>)) -> Self {
+        Rect::new(origin, size)
+    }
+}
+
 /// Shorthand for `Rect::new(Point2D::new(x, y), Size2D::new(w, h))`.
 pub const fn rect<T, U>(x: T, y: T, w: T, h: T) -> Rect<T, U> {
     Rect::new(Point2D::new(x, y), Size2D::new(w, h))
@@ -662,9 +1766,71 @@ pub const fn rect<T, U>(x: T, y: T, w: T, h: T) -> Rect<T, U> {
 
 #[cfg(test)]
 mod tests {
-    use crate::default::{Point2D, Rect, Size2D};
+    use crate::default::{LineSegment2D, Point2D, Rect, Size2D};
     use crate::side_offsets::SideOffsets2D;
-    use crate::{point2, rect, size2, vec2};
+    use crate::{point2, rect, size2, vec2, RectIntersection};
+
+    #[test]
+    fn test_transposed() {
+        let r: Rect<i32> = Rect::new(Point2D::new(1, 2), Size2D::new(30, 40));
+        let t = r.transposed();
+        assert_eq!(t, Rect::new(Point2D::new(2, 1), Size2D::new(40, 30)));
+        assert_eq!(t.to_box2d(), r.to_box2d().transposed());
+        assert_eq!(t.transposed(), r);
+    }
+
+    #[cfg(feature = "quickcheck")]
+    quickcheck::quickcheck! {
+        // Small components, so that the corners don't overflow.
+        fn prop_rect_box2d_round_trip(r: Rect<i16>) -> bool {
+            let r: Rect<i32> = r.cast();
+            r.to_box2d().to_rect() == r && r.transposed().transposed() == r
+        }
+    }
+
+    #[test]
+    fn test_cmp() {
+        let a = Rect::new(Point2D::new(5, 0), Size2D::new(2, 9));
+        let b = Rect::new(Point2D::new(0, 3), Size2D::new(4, 4));
+        let c = Rect::new(Point2D::new(1, 3), Size2D::new(1, 1));
+
+        let mut rects = [a, b, c];
+        rects.sort_by(Rect::cmp_by_area);
+        assert_eq!(rects, [c, b, a]);
+        rects.sort_by(Rect::cmp_by_top_left);
+        assert_eq!(rects, [a, b, c]);
+
+        assert!(b.origin < c.origin);
+        assert!(a.size < b.size);
+        assert_eq!(b.size.max(c.size), b.size);
+    }
+
+    #[test]
+    fn test_partial_ord() {
+        let parent = Rect::new(Point2D::new(0.0, 0.0), Size2D::new(10.0, 10.0));
+        let child = Rect::new(Point2D::new(2.0, 2.0), Size2D::new(8.0, 3.0));
+        let other = Rect::new(Point2D::new(5.0, 5.0), Size2D::new(10.0, 10.0));
+        let empty = Rect::new(Point2D::new(20.0, 20.0), Size2D::new(0.0, 5.0));
+
+        assert!(child < parent);
+        assert!(parent > child);
+        assert!(parent <= parent);
+        assert_eq!(parent.partial_cmp(&parent), Some(core::cmp::Ordering::Equal));
+        assert_eq!(child.partial_cmp(&other), None);
+        assert!(empty < parent);
+        assert_eq!(empty.partial_cmp(&Rect::zero()), None);
+
+        let nan = Rect::new(Point2D::new(f64::NAN, 0.0), Size2D::new(1.0, 1.0));
+        assert_eq!(nan.partial_cmp(&nan), None);
+    }
+
+    #[test]
+    fn test_fmt() {
+        let r: Rect<f32> = Rect::new(Point2D::new(10.0, 20.5), Size2D::new(100.0, 50.25));
+        assert_eq!(format!("{}", r), "100x50.25 at (10, 20.5)");
+        assert_eq!(format!("{:.1}", r), "100.0x50.2 at (10.0, 20.5)");
+        assert_eq!(format!("{:.1?}", r), "Rect(100.0x50.2 at (10.0, 20.5))");
+    }
 
     #[test]
     fn test_translate() {
@@ -839,6 +2005,16 @@ mod tests {
         assert_eq!(outer_rect.inner_rect(offsets), inner_rect);
     }
 
+    #[test]
+    fn test_inflate_for_blur() {
+        let r: Rect<f32> = rect(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(r.inflate_for_blur(0.0), r);
+        assert_eq!(r.inflate_for_blur(0.5), rect(-1.5, -1.5, 13.0, 13.0));
+        assert_eq!(r.inflate_for_blur(0.5), r.outer_rect(SideOffsets2D::blur_outset(0.5)));
+        let outset: SideOffsets2D<i32, crate::UnknownUnit> = SideOffsets2D::blur_outset(4);
+        assert_eq!(outset, SideOffsets2D::new_all_same(12));
+    }
+
     #[test]
     fn test_min_max_x_y() {
         let p = Rect::new(Point2D::new(0u32, 0u32), Size2D::new(50u32, 40u32));
@@ -904,6 +2080,212 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_coverage_by() {
+        let layer: Rect<f32> = rect(0.0, 0.0, 100.0, 100.0);
+        assert_eq!(layer.coverage_by(&[]), 0.0);
+        assert_eq!(layer.coverage_by(&[rect(-10.0, -10.0, 200.0, 200.0)]), 1.0);
+
+        // Overlapping occluders are only counted once.
+        let quarters = [
+            rect(0.0, 0.0, 50.0, 50.0),
+            rect(0.0, 0.0, 50.0, 50.0),
+            rect(25.0, 25.0, 50.0, 50.0),
+        ];
+        assert_eq!(layer.coverage_by(&quarters), 0.4375);
+
+        // A ring around a hole.
+        let ring = [
+            rect(0.0, 0.0, 100.0, 10.0),
+            rect(0.0, 90.0, 100.0, 10.0),
+            rect(0.0, 0.0, 10.0, 100.0),
+            rect(90.0, 0.0, 10.0, 100.0),
+        ];
+        assert_eq!(layer.coverage_by(&ring), 0.36);
+
+        let empty: Rect<f32> = rect(0.0, 0.0, 0.0, 10.0);
+        assert_eq!(empty.coverage_by(&ring), 0.0);
+        assert_eq!(layer.coverage_by(&[rect(10.0, 10.0, -5.0, 5.0), rect(0.0, 0.0, f32::NAN, 5.0)]), 0.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_intersect_all() {
+        let clip: Rect<f32> = rect(0.0, 0.0, 100.0, 100.0);
+        let rects: Vec<Rect<f32>> = vec![
+            rect(-10.0, -10.0, 20.0, 20.0),
+            rect(100.0, 0.0, 10.0, 10.0),
+            rect(20.0, 20.0, 10.0, 10.0),
+            rect(50.0, 50.0, 0.0, 10.0),
+            rect(90.0, 95.0, 50.0, 50.0),
+        ];
+        let expected: Vec<Rect<f32>> = rects.iter().filter_map(|r| clip.intersection(r)).collect();
+
+        let mut clipped = rects;
+        clip.intersect_all(&mut clipped);
+        assert_eq!(clipped, expected);
+        assert_eq!(clipped.len(), 3);
+
+        let mut none = clipped.clone();
+        rect(500.0, 500.0, 1.0, 1.0).intersect_all(&mut none);
+        assert!(none.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_area_of_union() {
+        use super::area_of_union;
+
+        let none: [Rect<f64>; 0] = [];
+        assert_eq!(area_of_union(&none), 0.0);
+
+        // A cross made of two bars, the center being counted once.
+        let cross: [Rect<f64>; 2] = [rect(0.0, 10.0, 30.0, 10.0), rect(10.0, 0.0, 10.0, 30.0)];
+        assert_eq!(area_of_union(&cross), 500.0);
+
+        // Nested, duplicated, touching and empty rectangles.
+        let rects: [Rect<i32>; 6] = [
+            rect(0, 0, 10, 10),
+            rect(2, 2, 3, 3),
+            rect(0, 0, 10, 10),
+            rect(10, 0, 5, 10),
+            rect(3, 3, 0, 10),
+            rect(0, 0, -5, -5),
+        ];
+        assert_eq!(area_of_union(&rects), 150);
+
+        // Compare with counting the covered cells of a grid.
+        let scattered: [Rect<i32>; 4] =
+            [rect(0, 0, 7, 3), rect(5, 1, 4, 6), rect(2, 5, 6, 2), rect(1, 2, 2, 2)];
+        let cells = (0..10)
+            .flat_map(|x| (0..10).map(move |y| point2(x, y)))
+            .filter(|&p| scattered.iter().any(|r| r.contains(p)))
+            .count();
+        assert_eq!(area_of_union(&scattered), cells as i32);
+    }
+
+    /// The O(n² log n) version of `union_area`: between two consecutive vertical edges,
+    /// the covered area of the slab is its width times the length of the union of the
+    /// vertical ranges of the boxes spanning it.
+    #[cfg(feature = "std")]
+    fn brute_force_union_area<T, U>(boxes: &[crate::Box2D<T, U>]) -> T
+    where
+        T: Copy + crate::num::Zero + PartialOrd + core::ops::Add<Output = T>
+            + core::ops::Sub<Output = T> + core::ops::Mul<Output = T>,
+    {
+        let cmp = |a: &T, b: &T| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal);
+        let boxes: Vec<&crate::Box2D<T, U>> = boxes.iter().filter(|b| !b.is_empty()).collect();
+
+        let mut xs: Vec<T> = boxes
+            .iter()
+            .flat_map(|b| core::iter::once(b.min.x).chain(core::iter::once(b.max.x)))
+            .collect();
+        xs.sort_by(cmp);
+        xs.dedup();
+
+        let mut area = T::zero();
+        let mut ranges = Vec::new();
+        for slab in xs.windows(2) {
+            let (x0, x1) = (slab[0], slab[1]);
+            ranges.clear();
+            ranges.extend(
+                boxes
+                    .iter()
+                    .filter(|b| b.min.x <= x0 && b.max.x >= x1)
+                    .map(|b| (b.min.y, b.max.y)),
+            );
+            ranges.sort_by(|a, b| cmp(&a.0, &b.0));
+
+            let mut covered = T::zero();
+            let mut current: Option<(T, T)> = None;
+            for &(y0, y1) in &ranges {
+                current = match current {
+                    Some((start, end)) if y0 <= end => Some((start, if y1 > end { y1 } else { end })),
+                    Some((start, end)) => {
+                        covered = covered + (end - start);
+                        Some((y0, y1))
+                    }
+                    None => Some((y0, y1)),
+                };
+            }
+            if let Some((start, end)) = current {
+                covered = covered + (end - start);
+            }
+
+            area = area + covered * (x1 - x0);
+        }
+
+        area
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_area_of_union_many() {
+        use super::area_of_union;
+
+        // A simple linear congruential generator, to get reproducible rects.
+        let mut seed = 12345u32;
+        let mut next = |n: i32| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            ((seed >> 16) % n as u32) as i32
+        };
+
+        for count in &[1, 2, 10, 50, 300] {
+            let rects: Vec<Rect<i32>> = (0..*count)
+                .map(|_| rect(next(200) - 50, next(200) - 50, next(60), next(60)))
+                .collect();
+            let boxes: Vec<crate::default::Box2D<i32>> = rects.iter().map(|r| r.to_box2d()).collect();
+            assert_eq!(area_of_union(&rects), brute_force_union_area(&boxes));
+
+            let rects: Vec<Rect<f64>> = rects.iter().map(|r| r.cast::<f64>().scale(0.25, 0.5)).collect();
+            let boxes: Vec<crate::default::Box2D<f64>> = rects.iter().map(|r| r.to_box2d()).collect();
+            assert_eq!(area_of_union(&rects), brute_force_union_area(&boxes));
+        }
+    }
+
+    #[test]
+    fn test_intersection_detailed() {
+        let a: Rect<f32> = rect(0.0, 0.0, 10.0, 10.0);
+        let corner = a.intersection_detailed(&rect(10.0, 10.0, 5.0, 5.0));
+        assert_eq!(corner, RectIntersection::Degenerate(rect(10.0, 10.0, 0.0, 0.0)));
+        assert_eq!(corner.rect(), Some(rect(10.0, 10.0, 0.0, 0.0)));
+
+        let inside = a.intersection_detailed(&rect(2.0, 2.0, 1.0, 1.0));
+        assert_eq!(inside, RectIntersection::Overlapping(rect(2.0, 2.0, 1.0, 1.0)));
+
+        // A zero-area rectangle inside of the other one.
+        let line = a.intersection_detailed(&rect(2.0, 2.0, 0.0, 5.0));
+        assert_eq!(line, RectIntersection::Degenerate(rect(2.0, 2.0, 0.0, 5.0)));
+
+        let far = a.intersection_detailed(&rect(20.0, 0.0, 5.0, 5.0));
+        assert_eq!(far, RectIntersection::Disjoint);
+        assert_eq!(far.rect(), None);
+    }
+
+    #[test]
+    fn test_from_min_max() {
+        let r: Option<Rect<f32>> = Rect::from_min_max(point2(-1.0, -2.0), point2(1.0, 2.0));
+        assert_eq!(r, Some(rect(-1.0, -2.0, 2.0, 4.0)));
+        let r: Option<Rect<f32>> = Rect::from_min_max(point2(0.0, 0.0), point2(1.0, -0.5));
+        assert_eq!(r, None);
+        let r: Option<Rect<f32>> = Rect::from_min_max(point2(0.0, 0.0), point2(f32::NAN, 1.0));
+        assert_eq!(r, None);
+    }
+
+    #[test]
+    fn test_round_integers() {
+        let r: Rect<u8> = rect(1u8, 2, 3, 4);
+        assert_eq!(r.round_out(), r);
+        assert_eq!(r.round_in(), r);
+        assert_eq!(r.cast::<i128>().round(), rect(1i128, 2, 3, 4));
+
+        let big: Rect<i128> = rect(i128::MAX - 10, 0, 5, u32::MAX as i128 * 2);
+        assert_eq!(big.round_out(), big);
+        assert_eq!(big.try_cast::<i64>(), None);
+        assert_eq!(r.cast::<f64>().try_cast::<u8>(), Some(r));
+    }
+
     #[test]
     fn test_center() {
         let r: Rect<i32> = rect(-2, 5, 4, 10);
@@ -914,10 +2296,298 @@ mod tests {
     }
 
     #[test]
+    fn test_from_tuple() {
+        let r: Rect<i32> = Rect::from((1, 2, 3, 4));
+        assert_eq!(r, rect(1, 2, 3, 4));
+
+        let r: Rect<i32> = (point2(1, 2), size2(3, 4)).into();
+        assert_eq!(r, rect(1, 2, 3, 4));
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let r: Rect<f32> = rect(10.0, 20.0, 5.0, 5.0);
+        let parent_origin = point2(4.0, 30.0);
+
+        let relative = r.relative_to(parent_origin);
+        assert_eq!(relative, rect(6.0, -10.0, 5.0, 5.0));
+        assert_eq!(relative.translate(parent_origin.to_vector()), r);
+    }
+
+    #[test]
+    fn test_edges() {
+        let r: Rect<i32> = rect(1, 2, 3, 4);
+        let edges = r.edges();
+
+        assert_eq!(edges[0], LineSegment2D::new(point2(1, 2), point2(4, 2)));
+        assert_eq!(edges[1], LineSegment2D::new(point2(4, 2), point2(4, 6)));
+        assert_eq!(edges[2], LineSegment2D::new(point2(4, 6), point2(1, 6)));
+        assert_eq!(edges[3], LineSegment2D::new(point2(1, 6), point2(1, 2)));
+    }
+
+    #[test]
+    fn test_clip_segment() {
+        let r: Rect<f64> = rect(0.0, 0.0, 10.0, 20.0);
+        let clip = |from: Point2D<f64>, to: Point2D<f64>| r.clip_segment(&LineSegment2D::new(from, to));
+        let segment = |from: Point2D<f64>, to: Point2D<f64>| Some(LineSegment2D::new(from, to));
+
+        // Inside, crossing, or starting or ending outside, in both directions.
+        let inside = (point2(1.0, 2.0), point2(9.0, 3.0));
+        assert_eq!(clip(inside.0, inside.1), segment(inside.0, inside.1));
+        assert_eq!(clip(point2(-5.0, 10.0), point2(15.0, 10.0)), segment(point2(0.0, 10.0), point2(10.0, 10.0)));
+        assert_eq!(clip(point2(15.0, 10.0), point2(-5.0, 10.0)), segment(point2(10.0, 10.0), point2(0.0, 10.0)));
+        assert_eq!(clip(point2(5.0, -10.0), point2(5.0, 5.0)), segment(point2(5.0, 0.0), point2(5.0, 5.0)));
+        assert_eq!(clip(point2(-10.0, -20.0), point2(20.0, 40.0)), segment(point2(0.0, 0.0), point2(10.0, 20.0)));
+
+        // Along an edge, and through a corner.
+        assert_eq!(clip(point2(10.0, -5.0), point2(10.0, 25.0)), segment(point2(10.0, 0.0), point2(10.0, 20.0)));
+        assert_eq!(clip(point2(5.0, 25.0), point2(15.0, 15.0)), segment(point2(10.0, 20.0), point2(10.0, 20.0)));
+
+        // Outside: beside the rectangle, or crossing the line of an edge but not the rectangle.
+        assert_eq!(clip(point2(11.0, -5.0), point2(11.0, 25.0)), None);
+        assert_eq!(clip(point2(-5.0, 5.0), point2(-1.0, 15.0)), None);
+        assert_eq!(clip(point2(6.0, 25.0), point2(16.0, 15.0)), None);
+        assert_eq!(clip(point2(-2.0, 1.0), point2(1.0, -2.0)), None);
+
+        // Degenerate segments and rectangles.
+        assert_eq!(clip(point2(3.0, 3.0), point2(3.0, 3.0)), segment(point2(3.0, 3.0), point2(3.0, 3.0)));
+        assert_eq!(clip(point2(30.0, 3.0), point2(30.0, 3.0)), None);
+        let flat: Rect<f64> = rect(0.0, 5.0, 10.0, 0.0);
+        assert_eq!(
+            flat.clip_segment(&LineSegment2D::new(point2(5.0, 0.0), point2(5.0, 10.0))),
+            segment(point2(5.0, 5.0), point2(5.0, 5.0)),
+        );
+        let negative: Rect<f64> = rect(0.0, 0.0, -10.0, 10.0);
+        assert_eq!(negative.clip_segment(&LineSegment2D::new(point2(-5.0, 5.0), point2(5.0, 5.0))), None);
+        assert_eq!(clip(point2(f64::NAN, 3.0), point2(5.0, 3.0)), None);
+        assert_eq!(clip(point2(5.0, 3.0), point2(f64::NAN, 3.0)), None);
+        assert_eq!(clip(point2(5.0, 3.0), point2(5.0, f64::NAN)), None);
+        assert_eq!(clip(point2(f64::INFINITY, 3.0), point2(f64::INFINITY, 3.0)), None);
+    }
+
+    #[test]
+    fn test_closest_point_to() {
+        let r: Rect<f32> = rect(10.0, 10.0, 20.0, 10.0);
+
+        assert_eq!(r.closest_point_to(point2(15.0, 12.0)), point2(15.0, 12.0));
+        assert_eq!(r.closest_point_to(point2(0.0, 15.0)), point2(10.0, 15.0));
+        assert_eq!(r.closest_point_to(point2(40.0, 40.0)), point2(30.0, 20.0));
+
+        assert_eq!(r.distance_to_point(point2(15.0, 12.0)), 0.0);
+        assert_eq!(r.distance_to_point(point2(20.0, 0.0)), 10.0);
+        assert_eq!(r.distance_to_point(point2(33.0, 24.0)), 5.0);
+    }
+
+    #[test]
+    fn test_overlap_vector() {
+        let a: Rect<f32> = rect(0.0, 0.0, 10.0, 10.0);
+
+        assert_eq!(a.overlap_vector(&rect(20.0, 0.0, 5.0, 5.0)), None);
+        assert_eq!(a.overlap_vector(&rect(10.0, 0.0, 5.0, 5.0)), None);
+
+        let b = rect(-3.0, 1.0, 5.0, 5.0);
+        assert_eq!(a.overlap_vector(&b), Some(vec2(2.0, 0.0)));
+
+        let c = rect(1.0, 9.0, 5.0, 5.0);
+        let v = a.overlap_vector(&c).unwrap();
+        assert_eq!(v, vec2(0.0, -1.0));
+        assert!(!a.translate(v).intersects(&c));
+        assert_eq!(c.overlap_vector(&a), Some(vec2(0.0, 1.0)));
+
+        let inner = rect(4.0, 4.0, 2.0, 2.0);
+        assert_eq!(inner.overlap_vector(&a), Some(vec2(-6.0, 0.0)));
+    }
+
+    #[test]
+    fn test_align_inside() {
+        use crate::rect::Align;
+
+        let container: Rect<f32> = rect(0.0, 0.0, 100.0, 50.0);
+        let r: Rect<f32> = rect(-7.0, 3.0, 10.0, 20.0);
+
+        assert_eq!(r.align_inside(&container, Align::Start, Align::Start), rect(0.0, 0.0, 10.0, 20.0));
+        assert_eq!(r.align_inside(&container, Align::Center, Align::End), rect(45.0, 30.0, 10.0, 20.0));
+        assert_eq!(r.align_inside(&container, Align::End, Align::Center), rect(90.0, 15.0, 10.0, 20.0));
+
+        let big: Rect<f32> = rect(0.0, 0.0, 120.0, 10.0);
+        assert_eq!(big.align_inside(&container, Align::Center, Align::Start), rect(-10.0, 0.0, 120.0, 10.0));
+    }
+
+    #[test]
+    fn test_with_aspect_ratio() {
+        use crate::rect::AnchorCorner;
+
+        let r: Rect<f32> = rect(10.0, 20.0, 40.0, 20.0);
+
+        // Too wide: the width shrinks.
+        assert_eq!(r.with_aspect_ratio(1.0, AnchorCorner::TopLeft), rect(10.0, 20.0, 20.0, 20.0));
+        assert_eq!(r.with_aspect_ratio(1.0, AnchorCorner::BottomRight), rect(30.0, 20.0, 20.0, 20.0));
+
+        // Too tall: the height shrinks.
+        assert_eq!(r.with_aspect_ratio(4.0, AnchorCorner::TopRight), rect(10.0, 20.0, 40.0, 10.0));
+        assert_eq!(r.with_aspect_ratio(4.0, AnchorCorner::BottomLeft), rect(10.0, 30.0, 40.0, 10.0));
+
+        // Already at the right ratio.
+        assert_eq!(r.with_aspect_ratio(2.0, AnchorCorner::BottomRight), r);
+        assert_eq!(r.with_aspect_ratio(3.0, AnchorCorner::TopLeft).size.aspect_ratio(), 3.0);
+    }
+
+    #[test]
+    fn test_cells_intersected() {
+        let r: Rect<f32> = rect(-0.5, -20.0, 1.0, 20.0);
+        let cells: Vec<_> = r.cells_intersected(size2(10.0, 10.0)).collect();
+        assert_eq!(cells, vec![point2(-1, -2), point2(0, -2), point2(-1, -1), point2(0, -1)]);
+
+        let r: Rect<i32> = rect(-10, 10, 10, 10);
+        let cells = r.cells_intersected(size2(10, 5));
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells.collect::<Vec<_>>(), vec![point2(-1, 2), point2(-1, 3)]);
+
+        let r: Rect<u32> = rect(0, 0, 0, 10);
+        assert_eq!(r.cells_intersected(size2(10, 10)).next(), None);
+    }
+
+    #[test]
+    fn test_snap_to_grid() {
+        use crate::num::RoundingMode;
+
+        let r: Rect<f32> = rect(-1.0, 5.0, 12.0, 2.0);
+        let cell = size2(4.0, 4.0);
+        assert_eq!(r.snap_to_grid(cell, RoundingMode::Floor), rect(-4.0, 4.0, 12.0, 0.0));
+        assert_eq!(r.snap_to_grid(cell, RoundingMode::Ceil), rect(0.0, 8.0, 12.0, 0.0));
+        assert_eq!(r.snap_to_grid(cell, RoundingMode::Round), rect(0.0, 4.0, 12.0, 4.0));
+        assert_eq!(r.snap_to_grid(cell, RoundingMode::RoundOut), rect(-4.0, 4.0, 16.0, 4.0));
+        assert_eq!(r.snap_to_grid(cell, RoundingMode::RoundHalfEven), rect(0.0, 4.0, 12.0, 4.0));
+
+        // Edges halfway between grid lines.
+        let r: Rect<i32> = rect(2, 6, 8, 4);
+        assert_eq!(r.snap_to_grid(size2(4, 4), RoundingMode::Round), rect(4, 8, 8, 4));
+        assert_eq!(r.snap_to_grid(size2(4, 4), RoundingMode::RoundHalfEven), rect(0, 8, 8, 0));
+
+        let r: Rect<i32> = rect(-7, -1, 1, 1);
+        assert_eq!(r.snap_to_grid(size2(4, 4), RoundingMode::RoundOut), rect(-8, -4, 4, 4));
+    }
+
+    #[test]
+    fn test_round_out_to_tiles() {
+        use crate::num::RoundingMode;
+
+        let r: Rect<u32> = rect(100, 30, 200, 20);
+        let tile = size2(64, 8);
+        assert_eq!(r.round_out_to_tiles(tile), rect(64, 24, 256, 32));
+        assert_eq!(r.round_out_to_tiles(tile), r.snap_to_grid(tile, RoundingMode::RoundOut));
+
+        // Aligned rectangles are unchanged, including empty ones.
+        assert_eq!(rect(64, 8, 128, 0).round_out_to_tiles(tile), rect(64, 8, 128, 0));
+        assert_eq!(r.round_out_to_tiles(size2(1, 1)), r);
+    }
+
+    #[test]
+    fn test_inflate_round_to() {
+        let r: Rect<f32> = rect(-3.0, 2.0, 6.0, 4.0);
+        assert_eq!(r.inflate_round_to(1.0, 2.0, 4.0), rect(-4.0, 0.0, 8.0, 8.0));
+        // Edges already on the grid stay in place.
+        assert_eq!(r.inflate_round_to(1.0, 0.0, 2.0), rect(-4.0, 2.0, 8.0, 4.0));
+        // Deflating can't make the result smaller than the deflated rectangle.
+        assert_eq!(r.inflate_round_to(-1.0, -1.0, 3.0), rect(-3.0, 3.0, 6.0, 3.0));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() {
+        use rand::Rng;
+
+        let mut rng = rand::rngs::mock::StepRng::new(0, 0x9E37_79B9_7F4A_7C15);
+
+        let r: Rect<f32> = rect(-10.0, 5.0, 3.0, 0.5);
+        let ri: Rect<i32> = rect(-2, 0, 2, 1);
+        for _ in 0..100 {
+            assert!(r.contains(r.sample(&mut rng)));
+            assert!(ri.contains(ri.sample(&mut rng)));
+        }
+
+        let p: Point2D<f64> = rng.gen();
+        assert!(p.x >= 0.0 && p.x < 1.0 && p.y >= 0.0 && p.y < 1.0);
+        let _: Rect<u8> = rng.gen();
+    }
+
+    #[test]
+    fn test_cast() {
+        let r: Rect<f32> = rect(1.5, -2.5, 3.75, 4.0);
+        assert_eq!(r.cast::<f64>(), rect(1.5, -2.5, 3.75, 4.0));
+        assert_eq!(r.to_i32(), rect(1, -2, 3, 4));
+        assert_eq!(r.to_i32().to_f32(), rect(1.0, -2.0, 3.0, 4.0));
+        assert_eq!(r.origin.to_i32(), point2(1, -2));
+        assert_eq!(r.size.cast::<u32>(), size2(3, 4));
+        let negative: Rect<f32> = rect(-1.0, 0.0, 1.0, 1.0);
+        assert_eq!(negative.try_cast::<u32>(), None);
+    }
+
+    #[test]
+    fn test_try_cast_detailed() {
+        let r: Rect<f64> = rect(0.0, 1.0, 1e20, 2.0);
+        assert_eq!(r.try_cast_detailed::<f32>(), Ok(rect(0.0, 1.0, 1e20, 2.0)));
+
+        let err = r.try_cast_detailed::<i32>().unwrap_err();
+        assert_eq!(err.component(), "size.width");
+        assert_eq!(
+            format!("{}", err),
+            "cannot cast component `size.width` to the target type"
+        );
+        assert_eq!(r.to_box2d().try_cast_detailed::<i32>().unwrap_err().component(), "max.x");
+    }
+
+    #[test]
+    fn test_cast_with() {
+        use crate::num::RoundingMode;
+
+        let r: Rect<f32> = rect(-0.6, 1.5, 1.2, 0.4);
+        assert_eq!(r.cast_with::<i32>(RoundingMode::Floor), rect(-1, 1, 1, 0));
+        assert_eq!(r.cast_with::<i32>(RoundingMode::Ceil), rect(0, 2, 1, 0));
+        assert_eq!(r.cast_with::<i32>(RoundingMode::Round), rect(-1, 2, 2, 0));
+        assert_eq!(r.cast_with::<i32>(RoundingMode::RoundOut), rect(-1, 1, 2, 1));
+    }
+
+    #[test]
+    // Intersecting malformed rectangles is an error with the debug-checks feature.
+    #[cfg(not(feature = "debug-checks"))]
     fn test_nan() {
         let r1: Rect<f32> = rect(-2.0, 5.0, 4.0, std::f32::NAN);
         let r2: Rect<f32> = rect(std::f32::NAN, -1.0, 3.0, 10.0);
 
         assert_eq!(r1.intersection(&r2), None);
     }
+
+    #[test]
+    fn test_is_well_formed() {
+        let well_formed = |r: Rect<f32>| r.is_well_formed();
+        assert!(well_formed(Rect::zero()));
+        assert!(well_formed(rect(-2.0, 5.0, 4.0, 0.0)));
+        assert!(!well_formed(rect(-2.0, 5.0, 4.0, -0.5)));
+        assert!(!well_formed(rect(-2.0, 5.0, 4.0, f32::NAN)));
+        assert!(!well_formed(rect(f32::NEG_INFINITY, 5.0, 4.0, 1.0)));
+        assert!(!well_formed(rect(f32::MAX, 5.0, f32::MAX, 1.0)));
+
+        let ints: [Rect<i32>; 2] = [rect(-2, 5, 4, 0), rect(-2, 5, -4, 0)];
+        assert!(ints[0].is_well_formed());
+        assert!(!ints[1].is_well_formed());
+
+        // The maximum edges would overflow.
+        let r: Rect<i32> = rect(i32::MAX, 0, 1, 1);
+        assert!(!r.is_well_formed());
+        let r: Rect<i32> = rect(0, i32::MIN, 1, i32::MAX);
+        assert!(r.is_well_formed());
+        let r: Rect<u8> = rect(200, 0, 55, 56);
+        assert!(r.is_well_formed());
+        let r: Rect<u8> = rect(200, 200, 55, 56);
+        assert!(!r.is_well_formed());
+    }
+
+    #[test]
+    #[cfg(all(feature = "debug-checks", debug_assertions))]
+    #[should_panic]
+    fn test_debug_checks() {
+        let r: Rect<f32> = rect(0.0, 0.0, 10.0, 10.0);
+        r.union(&rect(0.0, 0.0, -1.0, 1.0));
+    }
 }
@@ -8,7 +8,7 @@
 // except according to those terms.
 
 use crate::approxeq::ApproxEq;
-use crate::trig::Trig;
+use crate::trig::{self, Trig};
 use crate::{point2, point3, vec3, Angle, Point2D, Point3D, Vector2D, Vector3D};
 use crate::{Transform2D, Transform3D, UnknownUnit};
 use core::cmp::{Eq, PartialEq};
@@ -193,7 +193,7 @@ impl<T: Float, Src, Dst> Rotation2D<T, Src, Dst> {
     /// The input point must be use the unit Src, and the returned point has the unit Dst.
     #[inline]
     pub fn transform_point(&self, point: Point2D<T, Src>) -> Point2D<T, Dst> {
-        let (sin, cos) = Float::sin_cos(self.angle);
+        let (sin, cos) = trig::sin_cos(self.angle);
         point2(point.x * cos - point.y * sin, point.y * cos + point.x * sin)
     }
 
@@ -456,9 +456,9 @@ where
     pub fn euler(roll: Angle<T>, pitch: Angle<T>, yaw: Angle<T>) -> Self {
         let half = T::one() / (T::one() + T::one());
 
-        let (sy, cy) = Float::sin_cos(half * yaw.get());
-        let (sp, cp) = Float::sin_cos(half * pitch.get());
-        let (sr, cr) = Float::sin_cos(half * roll.get());
+        let (sy, cy) = trig::sin_cos(half * yaw.get());
+        let (sp, cp) = trig::sin_cos(half * pitch.get());
+        let (sr, cr) = trig::sin_cos(half * roll.get());
 
         Self::quaternion(
             cy * sr * cp - sy * cr * sp,
@@ -544,7 +544,7 @@ where
 
         // r1 and r3 form an orthonormal basis.
         let r3 = r2.sub(r1.mul(dot)).normalize();
-        let (sin, cos) = Float::sin_cos(theta);
+        let (sin, cos) = trig::sin_cos(theta);
         r1.mul(cos).add(r3.mul(sin))
     }
 
@@ -997,3 +997,23 @@ fn from_euler() {
 
     assert!(ypr_pe.approx_eq(&ypr_pq));
 }
+
+#[cfg(feature = "deterministic-trig")]
+#[test]
+fn deterministic_trig() {
+    use crate::default::{Rotation2D, Rotation3D};
+
+    // With the feature, every rotation goes through the software sine and cosine.
+    let angle = 0.7f32;
+    let (sin, cos) = (Trig::sin(angle), Trig::cos(angle));
+    assert_eq!(Angle::radians(angle).sin_cos(), (sin, cos));
+
+    let p = Rotation2D::radians(angle).transform_point(point2(1.0, 0.0));
+    assert_eq!((p.x, p.y), (cos, sin));
+
+    let (half_sin, half_cos) = (Trig::sin(angle / 2.0), Trig::cos(angle / 2.0));
+    let r = Rotation3D::around_z(Angle::radians(angle));
+    assert_eq!((r.k, r.r), (half_sin, half_cos));
+    let r = Rotation3D::euler(Angle::zero(), Angle::zero(), Angle::radians(angle));
+    assert_eq!((r.k, r.r), (half_sin, half_cos));
+}
@@ -0,0 +1,433 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A bulk-loaded R-tree for spatial indexing of static sets of rectangles.
+//!
+//! Unlike the `Quadtree`, the tree is built once from all of its items, which gives
+//! a balanced tree with tight node bounds without having to know the indexed area in
+//! advance. This suits sets of rectangles that are rebuilt rather than updated, like
+//! the display items of a frame.
+//!
+//! ```rust
+//! use euclid::{point2, rect};
+//! use euclid::default::Rect;
+//! use euclid::rtree::RTree;
+//!
+//! let items: Vec<(Rect<f32>, &str)> = vec![
+//!     (rect(10.0, 10.0, 20.0, 10.0), "button"),
+//!     (rect(50.0, 50.0, 40.0, 40.0), "image"),
+//!     (rect(0.0, 0.0, 100.0, 5.0), "toolbar"),
+//! ];
+//! let tree = RTree::bulk_load(items);
+//!
+//! let hits: Vec<&str> = tree.query_point(point2(15.0, 15.0)).into_iter().map(|(_, v)| *v).collect();
+//! assert_eq!(hits, vec!["button"]);
+//!
+//! let nearest: Vec<&str> = tree.nearest(point2(45.0, 45.0), 2).into_iter().map(|(_, v)| *v).collect();
+//! assert_eq!(nearest, vec!["image", "button"]);
+//! ```
+
+use crate::box2d::Box2D;
+use crate::num::Zero;
+use crate::point::Point2D;
+use crate::rect::Rect;
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ops::{Add, Mul, Range, Sub};
+
+/// The maximum number of children of a node.
+const NODE_CAPACITY: usize = 16;
+
+struct Node<T, U> {
+    bounds: Box2D<T, U>,
+    /// Indices into the entries for leaves, or into the nodes otherwise.
+    children: Range<usize>,
+    is_leaf: bool,
+}
+
+/// An R-tree storing values keyed by rectangles, built with the Sort-Tile-Recursive
+/// algorithm.
+///
+/// The tree can't be modified once built. Rectangles with a negative or NaN size
+/// are kept but never returned by queries.
+pub struct RTree<T, V, U> {
+    entries: Vec<(Box2D<T, U>, V)>,
+    /// The nodes, level by level from the leaves, the root being the last one.
+    nodes: Vec<Node<T, U>>,
+}
+
+impl<T, V, U> RTree<T, V, U>
+where
+    T: Copy + Zero + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Builds a tree containing the given rectangles and values.
+    pub fn bulk_load<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = (Rect<T, U>, V)>,
+    {
+        let mut entries: Vec<(Box2D<T, U>, V)> = items
+            .into_iter()
+            .map(|(r, value)| (r.to_box2d(), value))
+            .collect();
+        let mut nodes = Vec::new();
+        if entries.is_empty() {
+            return RTree { entries, nodes };
+        }
+
+        sort_tiles(&mut entries, |e| &e.0);
+        let mut level = group(&entries, 0, true, |e| &e.0);
+
+        while level.len() > 1 {
+            sort_tiles(&mut level, |n| &n.bounds);
+            let parents = group(&level, nodes.len(), false, |n| &n.bounds);
+            nodes.append(&mut level);
+            level = parents;
+        }
+        nodes.append(&mut level);
+
+        RTree { entries, nodes }
+    }
+
+    /// Returns the number of items in the tree.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the tree contains no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the smallest box containing all of the items, or `None` if the tree is
+    /// empty.
+    pub fn bounds(&self) -> Option<Box2D<T, U>> {
+        self.nodes.last().map(|root| root.bounds)
+    }
+
+    /// Returns an iterator over the rectangles and values of the items, in no
+    /// particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Rect<T, U>, &V)> + '_ {
+        self.entries.iter().map(|(b, value)| (b.to_rect(), value))
+    }
+
+    /// Returns the items whose rectangle contains the point, following the semantics
+    /// of `Rect::contains`.
+    pub fn query_point(&self, p: Point2D<T, U>) -> Vec<(Rect<T, U>, &V)> {
+        self.query(|b| contains_inclusive(b, p), |b| b.contains(p))
+    }
+
+    /// Returns the items whose rectangle intersects the given one, following the
+    /// semantics of `Rect::intersects`.
+    pub fn query_rect(&self, rect: &Rect<T, U>) -> Vec<(Rect<T, U>, &V)> {
+        let query = rect.to_box2d();
+        self.query(|b| b.intersects(&query), |b| b.intersects(&query))
+    }
+
+    fn query<N, E>(&self, visit_node: N, accept: E) -> Vec<(Rect<T, U>, &V)>
+    where
+        N: Fn(&Box2D<T, U>) -> bool,
+        E: Fn(&Box2D<T, U>) -> bool,
+    {
+        let mut result = Vec::new();
+        let mut stack: Vec<usize> = self.nodes.len().checked_sub(1).into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !visit_node(&node.bounds) {
+                continue;
+            }
+
+            if node.is_leaf {
+                for (b, value) in &self.entries[node.children.clone()] {
+                    if accept(b) {
+                        result.push((b.to_rect(), value));
+                    }
+                }
+            } else {
+                stack.extend(node.children.clone());
+            }
+        }
+
+        result
+    }
+
+    /// Returns the `k` items closest to the point, the closest first.
+    ///
+    /// The distance to an item is the distance to the closest point of its rectangle,
+    /// which is zero for rectangles containing the point. Items at the same distance
+    /// are returned in no particular order.
+    pub fn nearest(&self, p: Point2D<T, U>, k: usize) -> Vec<(Rect<T, U>, &V)> {
+        let mut result = Vec::new();
+        let mut queue = BinaryHeap::new();
+        if let Some(root) = self.nodes.last() {
+            queue.push(Candidate {
+                square_distance: square_distance(&root.bounds, p),
+                index: self.nodes.len() - 1,
+                is_entry: false,
+            });
+        }
+
+        // Best-first search: since the distance to a node is a lower bound of the
+        // distance to its items, entries come out of the queue in order.
+        while let Some(candidate) = queue.pop() {
+            if result.len() == k {
+                break;
+            }
+
+            if candidate.is_entry {
+                let (ref b, ref value) = self.entries[candidate.index];
+                result.push((b.to_rect(), value));
+                continue;
+            }
+
+            let node = &self.nodes[candidate.index];
+            for index in node.children.clone() {
+                let b = if node.is_leaf {
+                    &self.entries[index].0
+                } else {
+                    &self.nodes[index].bounds
+                };
+                if !is_valid(b) {
+                    continue;
+                }
+                queue.push(Candidate {
+                    square_distance: square_distance(b, p),
+                    index,
+                    is_entry: node.is_leaf,
+                });
+            }
+        }
+
+        result
+    }
+}
+
+/// Orders the items such that consecutive groups of `NODE_CAPACITY` items are close
+/// to each other: the items are sorted into vertical slices by the x coordinate of
+/// their center, then each slice is sorted by the y coordinate.
+fn sort_tiles<T, U, I, F>(items: &mut [I], bounds: F)
+where
+    T: Copy + PartialOrd + Add<Output = T>,
+    F: Fn(&I) -> &Box2D<T, U>,
+{
+    // Sums of the coordinates of the corners compare like the centers.
+    let center_x = |item: &I| bounds(item).min.x + bounds(item).max.x;
+    let center_y = |item: &I| bounds(item).min.y + bounds(item).max.y;
+    let cmp = |a: T, b: T| total_cmp(&a, &b);
+
+    let groups = (items.len() + NODE_CAPACITY - 1) / NODE_CAPACITY;
+    let mut slices = 1;
+    while slices * slices < groups {
+        slices += 1;
+    }
+
+    items.sort_by(|a, b| cmp(center_x(a), center_x(b)));
+    for slice in items.chunks_mut(slices * NODE_CAPACITY) {
+        slice.sort_by(|a, b| cmp(center_y(a), center_y(b)));
+    }
+}
+
+/// Compares two values, NaN being greater than all other values, so that sorting
+/// doesn't depend on the position of NaNs.
+fn total_cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    match a.partial_cmp(b) {
+        Some(ordering) => ordering,
+        None => {
+            let is_nan = |v: &T| v.partial_cmp(v).is_none();
+            is_nan(a).cmp(&is_nan(b))
+        }
+    }
+}
+
+/// Returns true if the box can be returned by queries, that is if it is not negative
+/// and has no NaN coordinates.
+fn is_valid<T: PartialOrd, U>(b: &Box2D<T, U>) -> bool {
+    b.min.x <= b.max.x && b.min.y <= b.max.y
+}
+
+/// Creates the nodes containing each consecutive group of `NODE_CAPACITY` items, the
+/// indices of the children starting at `offset`.
+fn group<T, U, I, F>(items: &[I], offset: usize, is_leaf: bool, bounds: F) -> Vec<Node<T, U>>
+where
+    T: Copy + PartialOrd,
+    F: Fn(&I) -> &Box2D<T, U>,
+{
+    items
+        .chunks(NODE_CAPACITY)
+        .enumerate()
+        .map(|(i, chunk)| {
+            // Items that are never returned don't extend the bounds, which are left
+            // negative if there are no other items.
+            let node_bounds = chunk
+                .iter()
+                .map(&bounds)
+                .filter(|b| is_valid(b))
+                .fold(None, |acc: Option<Box2D<T, U>>, b| match acc {
+                    Some(acc) => Some(Box2D::new(acc.min.min(b.min), acc.max.max(b.max))),
+                    None => Some(*b),
+                })
+                .unwrap_or_else(|| {
+                    let b = bounds(&chunk[0]);
+                    Box2D::new(b.max, b.min)
+                });
+            let start = offset + i * NODE_CAPACITY;
+
+            Node {
+                bounds: node_bounds,
+                children: start..start + chunk.len(),
+                is_leaf,
+            }
+        })
+        .collect()
+}
+
+/// Returns true if the point is in the box, edges included, since the edges of an
+/// item can be on the right or bottom edges of its node.
+fn contains_inclusive<T: PartialOrd, U>(b: &Box2D<T, U>, p: Point2D<T, U>) -> bool {
+    b.min.x <= p.x && p.x <= b.max.x && b.min.y <= p.y && p.y <= b.max.y
+}
+
+fn square_distance<T, U>(b: &Box2D<T, U>, p: Point2D<T, U>) -> T
+where
+    T: Copy + Zero + PartialOrd + Sub<Output = T> + Mul<Output = T> + Add<Output = T>,
+{
+    let axis = |v: T, min: T, max: T| {
+        if v < min {
+            min - v
+        } else if v > max {
+            v - max
+        } else {
+            T::zero()
+        }
+    };
+    let dx = axis(p.x, b.min.x, b.max.x);
+    let dy = axis(p.y, b.min.y, b.max.y);
+
+    dx * dx + dy * dy
+}
+
+/// An entry or node in the queue of the nearest neighbor search, the closest one
+/// being the greatest.
+struct Candidate<T> {
+    square_distance: T,
+    index: usize,
+    is_entry: bool,
+}
+
+impl<T: PartialOrd> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd> Eq for Candidate<T> {}
+
+impl<T: PartialOrd> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> Ord for Candidate<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Entries go before nodes at the same distance, so that items touching the
+        // bounds of a node aren't returned after the node's items.
+        total_cmp(&other.square_distance, &self.square_distance)
+            .then(self.is_entry.cmp(&other.is_entry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{default, point2, rect};
+
+    fn grid(n: i32) -> RTree<i32, i32, crate::UnknownUnit> {
+        let items = (0..n * n).map(|i| (rect((i % n) * 10, (i / n) * 10, 8, 8), i));
+        RTree::bulk_load(items)
+    }
+
+    fn values<V: Copy + Ord>(items: Vec<(default::Rect<i32>, &V)>) -> Vec<V> {
+        let mut values: Vec<V> = items.into_iter().map(|(_, v)| *v).collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn test_query() {
+        let tree = grid(30);
+        assert_eq!(tree.len(), 900);
+        assert_eq!(tree.bounds(), Some(Box2D::new(point2(0, 0), point2(298, 298))));
+        assert_eq!(tree.iter().count(), 900);
+
+        assert_eq!(values(tree.query_point(point2(123, 45))), vec![132]);
+        assert_eq!(values(tree.query_point(point2(128, 45))), Vec::<i32>::new());
+        assert_eq!(values(tree.query_point(point2(297, 297))), vec![899]);
+        assert_eq!(
+            values(tree.query_rect(&rect(15, 5, 20, 10))),
+            vec![1, 2, 3, 31, 32, 33]
+        );
+        assert_eq!(values(tree.query_rect(&rect(400, 0, 10, 10))), Vec::<i32>::new());
+
+        // Compare with a linear search.
+        let query = rect(33, 77, 150, 41);
+        let expected: Vec<i32> = (0..900)
+            .filter(|i| rect((i % 30) * 10, (i / 30) * 10, 8, 8).intersects(&query))
+            .collect();
+        assert_eq!(values(tree.query_rect(&query)), expected);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let tree = grid(20);
+        let nearest = |p, k| -> Vec<i32> {
+            tree.nearest(p, k).into_iter().map(|(_, v)| *v).collect()
+        };
+
+        assert_eq!(nearest(point2(3, 3), 1), vec![0]);
+        assert_eq!(nearest(point2(-50, -50), 1), vec![0]);
+        assert_eq!(nearest(point2(17, 3), 2), vec![1, 2]);
+        assert_eq!(nearest(point2(1000, 1000), 1), vec![399]);
+        assert_eq!(nearest(point2(5, 5), 0), Vec::<i32>::new());
+        assert_eq!(tree.nearest(point2(5, 5), 1000).len(), 400);
+
+        // The results are sorted by distance.
+        let results = tree.nearest(point2(104, 57), 30);
+        let distances: Vec<i32> = results
+            .iter()
+            .map(|(r, _)| square_distance(&r.to_box2d(), point2(104, 57)))
+            .collect();
+        let mut sorted = distances.clone();
+        sorted.sort();
+        assert_eq!(distances, sorted);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let empty: RTree<f32, (), crate::UnknownUnit> = RTree::bulk_load(Vec::new());
+        assert!(empty.is_empty());
+        assert_eq!(empty.bounds(), None);
+        assert!(empty.query_point(point2(0.0, 0.0)).is_empty());
+        assert!(empty.nearest(point2(0.0, 0.0), 3).is_empty());
+
+        let items: Vec<(default::Rect<f32>, char)> = vec![
+            (rect(0.0, 0.0, 10.0, 10.0), 'a'),
+            (rect(5.0, 5.0, -2.0, 2.0), 'n'),
+            (rect(5.0, 5.0, 0.0, 0.0), 'p'),
+            (rect(f32::NAN, 5.0, 1.0, 1.0), 'x'),
+        ];
+        let tree = RTree::bulk_load(items);
+        let hits: Vec<char> = tree.query_point(point2(5.0, 5.0)).into_iter().map(|(_, v)| *v).collect();
+        assert_eq!(hits, vec!['a']);
+        let nearest: Vec<char> = tree.nearest(point2(50.0, 50.0), 5).into_iter().map(|(_, v)| *v).collect();
+        assert_eq!(nearest, vec!['a', 'p']);
+    }
+}
@@ -8,9 +8,9 @@
 // except according to those terms.
 //! A type-checked scaling factor between units.
 
-use crate::num::One;
+use crate::num::{One, Zero};
 
-use crate::{Point2D, Point3D, Rect, Size2D, Vector2D, Box2D, Box3D};
+use crate::{Point2D, Point3D, Rect, Size2D, Vector2D, Box2D, Box3D, Transform2D};
 use core::cmp::Ordering;
 use core::fmt;
 use core::hash::{Hash, Hasher};
@@ -293,6 +293,30 @@ impl<T: NumCast, Src, Dst> Scale<T, Src, Dst> {
     pub fn try_cast<NewT: NumCast>(self) -> Option<Scale<NewT, Src, Dst>> {
         NumCast::from(self.0).map(Scale::new)
     }
+
+    // Convenience functions for common casts
+
+    /// Cast into an `f32` scale factor.
+    ///
+    /// ```rust
+    /// use euclid::{point2, Point2D, Scale};
+    /// enum CssPx {};
+    /// enum DevicePx {};
+    ///
+    /// let dpi_ratio: Scale<f64, CssPx, DevicePx> = Scale::new(1.5);
+    /// let p: Point2D<f32, CssPx> = point2(10.0, 20.0);
+    /// assert_eq!(dpi_ratio.to_f32().transform_point(p), point2(15.0, 30.0));
+    /// ```
+    #[inline]
+    pub fn to_f32(self) -> Scale<f32, Src, Dst> {
+        self.cast()
+    }
+
+    /// Cast into an `f64` scale factor.
+    #[inline]
+    pub fn to_f64(self) -> Scale<f64, Src, Dst> {
+        self.cast()
+    }
 }
 
 // scale0 * scale1
@@ -382,9 +406,234 @@ impl<T: One, Src, Dst> One for Scale<T, Src, Dst> {
     }
 }
 
+/// A scaling factor between two different units of measurement, with separate factors
+/// for the x and y axes.
+///
+/// This is the non-uniform counterpart of `Scale`, for example for content that is
+/// rasterized with a different scale on each axis.
+///
+/// ```rust
+/// use euclid::{point2, rect, Scale2D};
+/// enum LayoutPx {};
+/// enum DevicePx {};
+///
+/// let zoom: Scale2D<f32, LayoutPx, DevicePx> = Scale2D::new(2.0, 0.5);
+///
+/// assert_eq!(zoom.transform_point(point2(10.0, 10.0)), point2(20.0, 5.0));
+/// assert_eq!(zoom.transform_rect(&rect(2.0, 2.0, 4.0, 4.0)), rect(4.0, 1.0, 8.0, 2.0));
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: serde::Serialize",
+        deserialize = "T: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Scale2D<T, Src, Dst> {
+    pub x: T,
+    pub y: T,
+    #[doc(hidden)]
+    pub _unit: PhantomData<(Src, Dst)>,
+}
+
+impl<T, Src, Dst> Scale2D<T, Src, Dst> {
+    /// Constructor taking the x and y factors.
+    #[inline]
+    pub const fn new(x: T, y: T) -> Self {
+        Scale2D {
+            x,
+            y,
+            _unit: PhantomData,
+        }
+    }
+
+    /// Constructor taking the same factor for both axes.
+    #[inline]
+    pub fn uniform(factor: T) -> Self
+    where
+        T: Copy,
+    {
+        Scale2D::new(factor, factor)
+    }
+
+    /// Creates an identity scale (1.0).
+    #[inline]
+    pub fn identity() -> Self
+    where
+        T: One,
+    {
+        Scale2D::new(T::one(), T::one())
+    }
+
+    /// Returns the given point transformed by this scale.
+    #[inline]
+    pub fn transform_point(self, point: Point2D<T, Src>) -> Point2D<T::Output, Dst>
+    where
+        T: Copy + Mul,
+    {
+        Point2D::new(point.x * self.x, point.y * self.y)
+    }
+
+    /// Returns the given vector transformed by this scale.
+    #[inline]
+    pub fn transform_vector(self, vec: Vector2D<T, Src>) -> Vector2D<T::Output, Dst>
+    where
+        T: Copy + Mul,
+    {
+        Vector2D::new(vec.x * self.x, vec.y * self.y)
+    }
+
+    /// Returns the given size transformed by this scale.
+    #[inline]
+    pub fn transform_size(self, size: Size2D<T, Src>) -> Size2D<T::Output, Dst>
+    where
+        T: Copy + Mul,
+    {
+        Size2D::new(size.width * self.x, size.height * self.y)
+    }
+
+    /// Returns the given rect transformed by this scale.
+    #[inline]
+    pub fn transform_rect(self, rect: &Rect<T, Src>) -> Rect<T::Output, Dst>
+    where
+        T: Copy + Mul,
+    {
+        Rect::new(
+            self.transform_point(rect.origin),
+            self.transform_size(rect.size),
+        )
+    }
+
+    /// Returns the given box transformed by this scale.
+    #[inline]
+    pub fn transform_box2d(self, b: &Box2D<T, Src>) -> Box2D<T::Output, Dst>
+    where
+        T: Copy + Mul,
+    {
+        Box2D {
+            min: self.transform_point(b.min),
+            max: self.transform_point(b.max),
+        }
+    }
+
+    /// Returns `true` if this scale has no effect.
+    #[inline]
+    pub fn is_identity(self) -> bool
+    where
+        T: PartialEq + One,
+    {
+        self.x == T::one() && self.y == T::one()
+    }
+
+    /// Returns `true` if both axes are scaled by the same factor.
+    #[inline]
+    pub fn is_uniform(self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.x == self.y
+    }
+
+    /// The inverse scale (1.0 / self).
+    pub fn inverse(self) -> Scale2D<T::Output, Dst, Src>
+    where
+        T: One + Div,
+    {
+        Scale2D::new(T::one() / self.x, T::one() / self.y)
+    }
+
+    /// Returns the transform scaling by the same factors.
+    #[inline]
+    pub fn to_transform(self) -> Transform2D<T, Src, Dst>
+    where
+        T: Zero,
+    {
+        Transform2D::scale(self.x, self.y)
+    }
+}
+
+impl<T: NumCast, Src, Dst> Scale2D<T, Src, Dst> {
+    /// Cast from one numeric representation to another, preserving the units.
+    ///
+    /// # Panics
+    ///
+    /// If the source value cannot be represented by the target type `NewT`, then
+    /// method panics. Use `try_cast` if that must be case.
+    #[inline]
+    pub fn cast<NewT: NumCast>(self) -> Scale2D<NewT, Src, Dst> {
+        self.try_cast().unwrap()
+    }
+
+    /// Fallible cast from one numeric representation to another, preserving the units.
+    /// If the source value cannot be represented by the target type `NewT`, then `None`
+    /// is returned.
+    pub fn try_cast<NewT: NumCast>(self) -> Option<Scale2D<NewT, Src, Dst>> {
+        match (NumCast::from(self.x), NumCast::from(self.y)) {
+            (Some(x), Some(y)) => Some(Scale2D::new(x, y)),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Copy, Src, Dst> From<Scale<T, Src, Dst>> for Scale2D<T, Src, Dst> {
+    fn from(scale: Scale<T, Src, Dst>) -> Self {
+        Scale2D::uniform(scale.0)
+    }
+}
+
+// scale0 * scale1
+// (A,B) * (B,C) = (A,C)
+impl<T: Mul, A, B, C> Mul<Scale2D<T, B, C>> for Scale2D<T, A, B> {
+    type Output = Scale2D<T::Output, A, C>;
+
+    #[inline]
+    fn mul(self, other: Scale2D<T, B, C>) -> Self::Output {
+        Scale2D::new(self.x * other.x, self.y * other.y)
+    }
+}
+
+impl<T: PartialEq, Src, Dst> PartialEq for Scale2D<T, Src, Dst> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<T: Eq, Src, Dst> Eq for Scale2D<T, Src, Dst> {}
+
+impl<T: Clone, Src, Dst> Clone for Scale2D<T, Src, Dst> {
+    fn clone(&self) -> Self {
+        Scale2D::new(self.x.clone(), self.y.clone())
+    }
+}
+
+impl<T: Copy, Src, Dst> Copy for Scale2D<T, Src, Dst> {}
+
+impl<T: fmt::Debug, Src, Dst> fmt::Debug for Scale2D<T, Src, Dst> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Scale2D").field(&self.x).field(&self.y).finish()
+    }
+}
+
+impl<T: Hash, Src, Dst> Hash for Scale2D<T, Src, Dst> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+    }
+}
+
+impl<T: One, Src, Dst> One for Scale2D<T, Src, Dst> {
+    #[inline]
+    fn one() -> Self {
+        Scale2D::identity()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Scale;
+    use super::{Scale, Scale2D};
+    use crate::{point2, size2};
 
     enum Inch {}
     enum Cm {}
@@ -417,4 +666,33 @@ mod tests {
         assert_eq!(a.clone() + b.clone(), Scale::new(5));
         assert_eq!(a - b, Scale::new(-1));
     }
+
+    #[test]
+    fn test_cast() {
+        let mm_per_inch: Scale<f64, Inch, Mm> = Scale::new(25.4);
+        assert_eq!(mm_per_inch.to_f32(), Scale::new(25.4f32));
+        assert_eq!(mm_per_inch.to_f32().to_f64().get() as f32, 25.4f32);
+        assert_eq!(mm_per_inch.cast::<i32>(), Scale::new(25));
+    }
+
+    #[test]
+    fn test_scale2d() {
+        let inch_to_mm: Scale2D<f32, Inch, Mm> = Scale2D::new(25.0, 50.0);
+        let mm_to_cm: Scale2D<f32, Mm, Cm> = Scale2D::from(Scale::new(0.1));
+        assert!(mm_to_cm.is_uniform());
+        assert!(!inch_to_mm.is_uniform());
+
+        let inch_to_cm: Scale2D<f32, Inch, Cm> = inch_to_mm * mm_to_cm;
+        assert_eq!(inch_to_cm.transform_point(point2(2.0, 1.0)), point2(5.0, 5.0));
+        assert_eq!(inch_to_cm.transform_size(size2(2.0, 2.0)), size2(5.0, 10.0));
+
+        let inch_to_cm: Scale2D<f32, Inch, Cm> = Scale2D::new(4.0, 2.0);
+        let cm_to_inch: Scale2D<f32, Cm, Inch> = inch_to_cm.inverse();
+        assert_eq!(cm_to_inch, Scale2D::new(0.25, 0.5));
+        assert!((Scale2D::<f32, Inch, Inch>::identity()).is_identity());
+
+        let p = point2(3.0, 4.0);
+        assert_eq!(inch_to_mm.to_transform().transform_point(p), inch_to_mm.transform_point(p));
+        assert_eq!(inch_to_mm.cast::<i32>(), Scale2D::new(25, 50));
+    }
 }
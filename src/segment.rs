@@ -0,0 +1,178 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::box2d::Box2D;
+use crate::num::One;
+use crate::point::Point2D;
+use crate::vector::Vector2D;
+
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Mul, Sub};
+
+/// A 2d line segment going from one point to another, optionally tagged with a unit.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct LineSegment2D<T, U> {
+    pub from: Point2D<T, U>,
+    pub to: Point2D<T, U>,
+}
+
+impl<T: Copy, U> Copy for LineSegment2D<T, U> {}
+
+impl<T: Clone, U> Clone for LineSegment2D<T, U> {
+    fn clone(&self) -> Self {
+        LineSegment2D {
+            from: self.from.clone(),
+            to: self.to.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for LineSegment2D<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.from == other.from && self.to == other.to
+    }
+}
+
+impl<T: Eq, U> Eq for LineSegment2D<T, U> {}
+
+impl<T: Hash, U> Hash for LineSegment2D<T, U> {
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        self.from.hash(h);
+        self.to.hash(h);
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for LineSegment2D<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("LineSegment2D")
+            .field(&self.from)
+            .field(&self.to)
+            .finish()
+    }
+}
+
+impl<T, U> LineSegment2D<T, U> {
+    /// Constructor.
+    #[inline]
+    pub const fn new(from: Point2D<T, U>, to: Point2D<T, U>) -> Self {
+        LineSegment2D { from, to }
+    }
+}
+
+impl<T: Copy, U> LineSegment2D<T, U> {
+    /// Returns the same segment going in the opposite direction.
+    #[inline]
+    #[must_use]
+    pub fn reverse(&self) -> Self {
+        LineSegment2D::new(self.to, self.from)
+    }
+
+    /// Returns the vector going from the start to the end of the segment.
+    #[inline]
+    pub fn to_vector(&self) -> Vector2D<T, U>
+    where
+        T: Sub<Output = T>,
+    {
+        self.to - self.from
+    }
+
+    /// Returns the same segment, translated by a vector.
+    #[inline]
+    #[must_use]
+    pub fn translate(&self, by: Vector2D<T, U>) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        LineSegment2D::new(self.from + by, self.to + by)
+    }
+
+    /// Returns the point at `t` along the segment, `from` being at `t = 0` and `to`
+    /// at `t = 1`.
+    #[inline]
+    pub fn sample(&self, t: T) -> Point2D<T, U>
+    where
+        T: One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        self.from.lerp(self.to, t)
+    }
+
+    /// Returns the smallest box containing the segment.
+    #[inline]
+    pub fn bounding_box(&self) -> Box2D<T, U>
+    where
+        T: PartialOrd,
+    {
+        Box2D::new(self.from.min(self.to), self.from.max(self.to))
+    }
+}
+
+impl<T: Float, U> LineSegment2D<T, U> {
+    /// Returns the length of the segment.
+    #[inline]
+    pub fn length(&self) -> T {
+        self.to_vector().length()
+    }
+
+    /// Returns the point of the segment that is closest to `p`.
+    pub fn closest_point(&self, p: Point2D<T, U>) -> Point2D<T, U> {
+        let v = self.to_vector();
+        let square_length = v.square_length();
+        if square_length == T::zero() {
+            return self.from;
+        }
+
+        let t = ((p - self.from).dot(v) / square_length).max(T::zero()).min(T::one());
+        self.from + v * t
+    }
+
+    /// Returns the distance between `p` and the closest point of the segment.
+    #[inline]
+    pub fn distance_to_point(&self, p: Point2D<T, U>) -> T {
+        (p - self.closest_point(p)).length()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{default, point2, vec2};
+
+    #[test]
+    fn test_segment() {
+        let s: default::LineSegment2D<f32> = LineSegment2D::new(point2(4.0, 1.0), point2(1.0, 5.0));
+        assert_eq!(s.to_vector(), vec2(-3.0, 4.0));
+        assert_eq!(s.length(), 5.0);
+        assert_eq!(s.sample(0.5), point2(2.5, 3.0));
+        assert_eq!(s.reverse().sample(0.0), point2(1.0, 5.0));
+        assert_eq!(s.bounding_box(), Box2D::new(point2(1.0, 1.0), point2(4.0, 5.0)));
+        assert_eq!(s.translate(vec2(1.0, 1.0)).from, point2(5.0, 2.0));
+    }
+
+    #[test]
+    fn test_distance_to_point() {
+        let s: default::LineSegment2D<f32> = LineSegment2D::new(point2(0.0, 0.0), point2(10.0, 0.0));
+        assert_eq!(s.closest_point(point2(4.0, 3.0)), point2(4.0, 0.0));
+        assert_eq!(s.distance_to_point(point2(4.0, 3.0)), 3.0);
+        assert_eq!(s.distance_to_point(point2(-3.0, 4.0)), 5.0);
+        assert_eq!(s.distance_to_point(point2(13.0, -4.0)), 5.0);
+
+        let point: default::LineSegment2D<f32> = LineSegment2D::new(point2(1.0, 1.0), point2(1.0, 1.0));
+        assert_eq!(point.distance_to_point(point2(4.0, 5.0)), 5.0);
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A compact serde representation that serializes values as flat arrays.
+//!
+//! By default, transforms and rectangles are serialized as structs with named fields.
+//! Points, vectors and sizes already serialize as tuples like `[x, y]`. Use this module
+//! with `#[serde(with = "euclid::serde_array")]` to serialize a field as a flat array of
+//! its terms instead. This keeps payloads small, for example for IPC.
+//!
+//! | Type          | Array                                  |
+//! |---------------|----------------------------------------|
+//! | `Transform2D` | `[m11, m12, m21, m22, m31, m32]`       |
+//! | `Transform3D` | `[m11, m12, m13, m14, m21, ..., m44]`  |
+//! | `Rect`        | `[x, y, width, height]`                |
+//! | `Box2D`       | `[min.x, min.y, max.x, max.y]`         |
+//!
+//! ```rust
+//! use euclid::default::{Rect, Transform2D};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Layer {
+//!     #[serde(with = "euclid::serde_array")]
+//!     transform: Transform2D<f32>,
+//!     #[serde(with = "euclid::serde_array")]
+//!     clip: Rect<f32>,
+//! }
+//! ```
+
+use crate::{point2, size2, Box2D, Rect, Transform2D, Transform3D};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Conversion to and from the flat array representation used by this module.
+pub trait ArrayRepr: Sized {
+    /// The array type, for example `[T; 6]` for `Transform2D`.
+    type Array;
+
+    /// Returns the terms of this value as a flat array.
+    fn to_array_repr(&self) -> Self::Array;
+
+    /// Creates a value from its terms.
+    fn from_array_repr(array: Self::Array) -> Self;
+}
+
+/// Serializes `value` as a flat array.
+pub fn serialize<V, S>(value: &V, serializer: S) -> Result<S::Ok, S::Error>
+where
+    V: ArrayRepr,
+    V::Array: Serialize,
+    S: Serializer,
+{
+    value.to_array_repr().serialize(serializer)
+}
+
+/// Deserializes a value from a flat array.
+pub fn deserialize<'de, V, D>(deserializer: D) -> Result<V, D::Error>
+where
+    V: ArrayRepr,
+    V::Array: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    V::Array::deserialize(deserializer).map(V::from_array_repr)
+}
+
+impl<T: Copy, Src, Dst> ArrayRepr for Transform2D<T, Src, Dst> {
+    type Array = [T; 6];
+
+    #[inline]
+    fn to_array_repr(&self) -> [T; 6] {
+        self.to_array()
+    }
+
+    #[inline]
+    fn from_array_repr(array: [T; 6]) -> Self {
+        Transform2D::from_array(array)
+    }
+}
+
+impl<T: Copy, Src, Dst> ArrayRepr for Transform3D<T, Src, Dst> {
+    type Array = [T; 16];
+
+    #[inline]
+    fn to_array_repr(&self) -> [T; 16] {
+        self.to_array()
+    }
+
+    #[inline]
+    fn from_array_repr(array: [T; 16]) -> Self {
+        Transform3D::from_array(array)
+    }
+}
+
+impl<T: Copy, U> ArrayRepr for Rect<T, U> {
+    type Array = [T; 4];
+
+    #[inline]
+    fn to_array_repr(&self) -> [T; 4] {
+        [self.origin.x, self.origin.y, self.size.width, self.size.height]
+    }
+
+    #[inline]
+    fn from_array_repr(array: [T; 4]) -> Self {
+        Rect::new(point2(array[0], array[1]), size2(array[2], array[3]))
+    }
+}
+
+impl<T: Copy, U> ArrayRepr for Box2D<T, U> {
+    type Array = [T; 4];
+
+    #[inline]
+    fn to_array_repr(&self) -> [T; 4] {
+        [self.min.x, self.min.y, self.max.x, self.max.y]
+    }
+
+    #[inline]
+    fn from_array_repr(array: [T; 4]) -> Self {
+        Box2D::new(point2(array[0], array[1]), point2(array[2], array[3]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::default::{Box2D, Rect, Transform2D, Transform3D};
+    use crate::{point2, rect};
+
+    extern crate serde_test;
+    use self::serde_test::{assert_tokens, Token};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Compact {
+        #[serde(with = "crate::serde_array")]
+        transform: Transform2D<f32>,
+        #[serde(with = "crate::serde_array")]
+        rect: Rect<i32>,
+        #[serde(with = "crate::serde_array")]
+        bounds: Box2D<i32>,
+    }
+
+    #[test]
+    fn test_serde_array() {
+        let value = Compact {
+            transform: Transform2D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0),
+            rect: rect(1, 2, 3, 4),
+            bounds: Box2D::new(point2(5, 6), point2(7, 8)),
+        };
+
+        assert_tokens(&value, &[
+            Token::Struct { name: "Compact", len: 3 },
+            Token::Str("transform"),
+            Token::Tuple { len: 6 },
+            Token::F32(1.0), Token::F32(2.0),
+            Token::F32(3.0), Token::F32(4.0),
+            Token::F32(5.0), Token::F32(6.0),
+            Token::TupleEnd,
+            Token::Str("rect"),
+            Token::Tuple { len: 4 },
+            Token::I32(1), Token::I32(2), Token::I32(3), Token::I32(4),
+            Token::TupleEnd,
+            Token::Str("bounds"),
+            Token::Tuple { len: 4 },
+            Token::I32(5), Token::I32(6), Token::I32(7), Token::I32(8),
+            Token::TupleEnd,
+            Token::StructEnd,
+        ]);
+    }
+
+    #[test]
+    fn test_serde_array_3d() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Wrapper(#[serde(with = "crate::serde_array")] Transform3D<i32>);
+
+        let mut tokens = vec![
+            Token::NewtypeStruct { name: "Wrapper" },
+            Token::Tuple { len: 16 },
+        ];
+        tokens.extend((1..=16).map(Token::I32));
+        tokens.push(Token::TupleEnd);
+
+        let mut terms = [0; 16];
+        for (term, value) in terms.iter_mut().zip(1..) {
+            *term = value;
+        }
+        assert_tokens(&Wrapper(Transform3D::from_array(terms)), &tokens);
+    }
+}
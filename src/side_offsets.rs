@@ -10,15 +10,17 @@
 //! A group of side offsets, which correspond to top/left/bottom/right for borders, padding,
 //! and margins in CSS.
 
+use crate::interpolate::Interpolate;
 use crate::length::Length;
-use crate::num::Zero;
+use crate::num::{One, Zero};
+use crate::rect::Rect;
 use crate::scale::Scale;
 use crate::Vector2D;
 use core::cmp::{Eq, PartialEq};
 use core::fmt;
 use core::hash::Hash;
 use core::marker::PhantomData;
-use core::ops::{Add, Div, DivAssign, Mul, MulAssign, Neg};
+use core::ops::{Add, Div, DivAssign, Mul, MulAssign, Neg, Sub};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -183,6 +185,40 @@ impl<T, U> SideOffsets2D<T, U> {
         }
     }
 
+    /// Construct side offsets from the gaps between an outer rectangle and an inner
+    /// rectangle, such that `outer.inner_rect(offsets) == *inner` and
+    /// `inner.outer_rect(offsets) == *outer`.
+    ///
+    /// The outer rectangle should contain the inner one, which is checked in debug
+    /// builds, otherwise some offsets are negative.
+    ///
+    /// ```rust
+    /// use euclid::default::SideOffsets2D;
+    /// use euclid::rect;
+    ///
+    /// let border_box = rect(0, 0, 100, 50);
+    /// let padding_box = rect(10, 5, 80, 40);
+    /// let border = SideOffsets2D::from_rects(&border_box, &padding_box);
+    /// assert_eq!(border, SideOffsets2D::new(5, 10, 5, 10));
+    /// assert_eq!(border_box.inner_rect(border), padding_box);
+    /// ```
+    pub fn from_rects(outer: &Rect<T, U>, inner: &Rect<T, U>) -> Self
+    where
+        T: Copy + Zero + PartialOrd + Add<T, Output = T> + Sub<T, Output = T>,
+    {
+        debug_assert!(
+            outer.contains_rect(inner),
+            "SideOffsets2D::from_rects: the outer rectangle doesn't contain the inner one"
+        );
+
+        SideOffsets2D::new(
+            inner.min_y() - outer.min_y(),
+            outer.max_x() - inner.max_x(),
+            outer.max_y() - inner.max_y(),
+            inner.min_x() - outer.min_x(),
+        )
+    }
+
     /// Constructor, setting all sides to zero.
     pub fn zero() -> Self
         where T: Zero,
@@ -213,6 +249,18 @@ impl<T, U> SideOffsets2D<T, U> {
         SideOffsets2D::new_all_same(all.0)
     }
 
+    /// Constructor for the offsets by which a gaussian blur of standard deviation
+    /// `sigma` spreads content, which is `3 * sigma` on all sides.
+    ///
+    /// About 0.13% of the mass of a gaussian lies more than three standard deviations
+    /// past one side, which is at most 0.34 out of 255 and rounds to 0 with 8-bit colors.
+    pub fn blur_outset(sigma: T) -> Self
+        where T: Copy + One + Add<Output = T> + Mul<Output = T>
+    {
+        let three = T::one() + T::one() + T::one();
+        SideOffsets2D::new_all_same(sigma * three)
+    }
+
     pub fn horizontal(&self) -> T
         where T: Copy + Add<T, Output = T>
     {
@@ -226,6 +274,21 @@ impl<T, U> SideOffsets2D<T, U> {
     }
 }
 
+impl<T, U> Interpolate<T> for SideOffsets2D<T, U>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        let one_t = T::one() - t;
+        SideOffsets2D::new(
+            one_t * self.top + t * other.top,
+            one_t * self.right + t * other.right,
+            one_t * self.bottom + t * other.bottom,
+            one_t * self.left + t * other.left,
+        )
+    }
+}
+
 impl<T, U> Add for SideOffsets2D<T, U>
 where
     T: Add<T, Output = T>,
@@ -368,6 +431,16 @@ fn test_is_zero() {
     assert!(!s2.is_zero());
 }
 
+#[test]
+fn interpolate() {
+    let s1: SideOffsets2D<f32, ()> = SideOffsets2D::new(0.0, 2.0, 4.0, 8.0);
+    let s2: SideOffsets2D<f32, ()> = SideOffsets2D::new_all_same(4.0);
+
+    assert_eq!(s1.interpolate(&s2, 0.0), s1);
+    assert_eq!(s1.interpolate(&s2, 0.5), SideOffsets2D::new(2.0, 3.0, 4.0, 6.0));
+    assert_eq!(s1.interpolate(&s2, 1.0), s2);
+}
+
 #[cfg(test)]
 mod ops {
     use crate::Scale;
@@ -7,8 +7,9 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use super::UnknownUnit;
+use super::{DisplayWithUnit, UnitName, UnknownUnit};
 use crate::approxord::{max, min};
+use crate::interpolate::Interpolate;
 use crate::length::Length;
 use crate::num::*;
 use crate::scale::Scale;
@@ -17,13 +18,13 @@ use crate::vector::{vec3, BoolVector3D, Vector3D};
 #[cfg(feature = "mint")]
 use mint;
 
-use core::cmp::{Eq, PartialEq};
+use core::cmp::{Eq, Ordering, PartialEq};
 use core::fmt;
 use core::hash::Hash;
 use core::iter::Sum;
 use core::marker::PhantomData;
 use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
-use num_traits::{NumCast, Signed, Float};
+use num_traits::{CheckedMul, NumCast, Signed, Float};
 #[cfg(feature = "serde")]
 use serde;
 
@@ -99,6 +100,32 @@ where
     }
 }
 
+#[cfg(feature = "rand")]
+impl<T, U> rand::distributions::Distribution<Size2D<T, U>> for rand::distributions::Standard
+where
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Size2D<T, U> {
+        Size2D::new(rng.gen(), rng.gen())
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl<T, U> quickcheck::Arbitrary for Size2D<T, U>
+where
+    T: quickcheck::Arbitrary,
+    U: 'static,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        Size2D::new(T::arbitrary(g), T::arbitrary(g))
+    }
+
+    fn shrink(&self) -> alloc::boxed::Box<dyn Iterator<Item = Self>> {
+        let shrunk = quickcheck::Arbitrary::shrink(&(self.width.clone(), self.height.clone()));
+        alloc::boxed::Box::new(shrunk.map(|(width, height)| Size2D::new(width, height)))
+    }
+}
+
 impl<T, U> Eq for Size2D<T, U> where T: Eq {}
 
 impl<T, U> PartialEq for Size2D<T, U>
@@ -110,6 +137,23 @@ where
     }
 }
 
+/// Sizes are ordered lexicographically, by `width` and then by `height`.
+///
+/// This is only implemented for totally ordered scalars such as integers, and is
+/// meant to give a deterministic order to collections of sizes rather than to
+/// compare their areas.
+impl<T: Ord, U> PartialOrd for Size2D<T, U> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord, U> Ord for Size2D<T, U> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.width.cmp(&other.width).then_with(|| self.height.cmp(&other.height))
+    }
+}
+
 impl<T, U> Hash for Size2D<T, U>
 where
     T: Hash,
@@ -128,6 +172,34 @@ impl<T: fmt::Debug, U> fmt::Debug for Size2D<T, U> {
     }
 }
 
+impl<T: fmt::Display, U> Size2D<T, U> {
+    pub(crate) fn fmt_with_suffix(&self, f: &mut fmt::Formatter, suffix: &str) -> fmt::Result {
+        fmt::Display::fmt(&self.width, f)?;
+        write!(f, "x")?;
+        fmt::Display::fmt(&self.height, f)?;
+        write!(f, "{}", suffix)
+    }
+}
+
+impl<T: fmt::Display, U> fmt::Display for Size2D<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_suffix(f, "")
+    }
+}
+
+impl<T, U: UnitName> Size2D<T, U> {
+    /// Returns an adaptor displaying the size with the suffix of its unit after each value.
+    pub fn display_with_unit(&self) -> DisplayWithUnit<'_, Self> {
+        DisplayWithUnit(self)
+    }
+}
+
+impl<'a, T: fmt::Display, U: UnitName> fmt::Display for DisplayWithUnit<'a, Size2D<T, U>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_with_suffix(f, U::SUFFIX)
+    }
+}
+
 impl<T: Default, U> Default for Size2D<T, U> {
     fn default() -> Self {
         Size2D::new(Default::default(), Default::default())
@@ -200,6 +272,25 @@ impl<T: Copy, U> Size2D<T, U> {
         vec2(self.width, self.height)
     }
 
+    /// Swap width and height.
+    ///
+    /// This is typically used to convert between the inline and block axes of
+    /// vertical writing modes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use euclid::size2;
+    /// # use euclid::default::Size2D;
+    /// let size: Size2D<i32> = size2(100, 20);
+    /// assert_eq!(size.transposed(), size2(20, 100));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn transposed(self) -> Self {
+        size2(self.height, self.width)
+    }
+
     /// Drop the units, preserving only the numeric value.
     #[inline]
     pub fn to_untyped(self) -> Size2D<T, UnknownUnit> {
@@ -231,6 +322,48 @@ impl<T: Copy, U> Size2D<T, U> {
         Size2D::new(self.width.round(), self.height.round())
     }
 
+    /// Rounds each component to the nearest integer value, halves rounding to the even
+    /// integer.
+    #[inline]
+    #[must_use]
+    pub fn round_half_even(self) -> Self
+    where
+        T: RoundHalfEven,
+    {
+        Size2D::new(self.width.round_half_even(), self.height.round_half_even())
+    }
+
+    /// Rounds each component to the nearest integer value like `round`, and returns the
+    /// rounded size along with the rounding error, that is the amount by which each
+    /// component was reduced.
+    ///
+    /// Carrying the error over to the next size to round lets layout distribute the
+    /// fractional parts, so that for example rounded column widths still add up to the
+    /// rounded total width.
+    ///
+    /// ```rust
+    /// # use euclid::{size2, vec2};
+    /// # use euclid::default::Size2D;
+    /// // Three columns sharing 100 pixels.
+    /// let column: Size2D<f32> = size2(100.0 / 3.0, 20.0);
+    /// let mut error = vec2(0.0, 0.0);
+    /// let mut total = 0.0;
+    /// for _ in 0..3 {
+    ///     let (rounded, e) = (column + error.to_size()).round_with_error();
+    ///     error = e;
+    ///     total += rounded.width;
+    /// }
+    /// assert_eq!(total, 100.0);
+    /// ```
+    #[inline]
+    pub fn round_with_error(self) -> (Self, Vector2D<T, U>)
+    where
+        T: Round + Sub<Output = T>,
+    {
+        let rounded = self.round();
+        (rounded, (self - rounded).to_vector())
+    }
+
     /// Rounds each component to the smallest integer equal or greater than the original value.
     ///
     /// This behavior is preserved for negative values (unlike the basic cast).
@@ -277,6 +410,36 @@ impl<T: Copy, U> Size2D<T, U> {
         self.width * self.height
     }
 
+    /// Returns the aspect ratio of this size, that is its width divided by its height.
+    ///
+    /// ```rust
+    /// # use euclid::size2;
+    /// # use euclid::default::Size2D;
+    /// let size: Size2D<f32> = size2(1920.0, 1080.0);
+    /// assert_eq!(size.aspect_ratio(), 16.0 / 9.0);
+    /// ```
+    #[inline]
+    pub fn aspect_ratio(self) -> T::Output
+    where
+        T: Div,
+    {
+        self.width / self.height
+    }
+
+    /// Returns result of multiplication of both components, or `None` if it overflows.
+    ///
+    /// ```rust
+    /// # use euclid::default::Size2D;
+    /// assert_eq!(Size2D::new(300, 200).checked_area(), Some(60000));
+    /// assert_eq!(Size2D::new(100_000, 100_000).checked_area(), None::<i32>);
+    /// ```
+    pub fn checked_area(self) -> Option<T>
+    where
+        T: CheckedMul,
+    {
+        self.width.checked_mul(&self.height)
+    }
+
     /// Linearly interpolate each component between this size and another size.
     ///
     /// # Example
@@ -304,6 +467,16 @@ impl<T: Copy, U> Size2D<T, U> {
     }
 }
 
+impl<T, U> Interpolate<T> for Size2D<T, U>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    #[inline]
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
 impl<T: NumCast + Copy, U> Size2D<T, U> {
     /// Cast from one numeric representation to another, preserving the units.
     ///
@@ -327,6 +500,46 @@ impl<T: NumCast + Copy, U> Size2D<T, U> {
         }
     }
 
+    /// Fallible cast from one numeric representation to another, preserving the units.
+    ///
+    /// Unlike `try_cast`, the error names the first component that could not be cast.
+    pub fn try_cast_detailed<NewT: NumCast>(self) -> Result<Size2D<NewT, U>, CastError> {
+        Ok(Size2D::new(
+            cast_component(self.width, "width")?,
+            cast_component(self.height, "height")?,
+        ))
+    }
+
+    /// Cast from one numeric representation to another, preserving the units and
+    /// rounding the components according to `mode` first.
+    ///
+    /// `RoundingMode::RoundOut` ceils the components so that the resulting size is
+    /// never smaller than the original one.
+    ///
+    /// ```rust
+    /// # use euclid::size2;
+    /// # use euclid::num::RoundingMode;
+    /// enum Mm {}
+    ///
+    /// let s = size2::<_, Mm>(10.2, 3.5);
+    /// assert_eq!(s.cast_with::<u32>(RoundingMode::Floor), size2(10, 3));
+    /// assert_eq!(s.cast_with::<u32>(RoundingMode::Round), size2(10, 4));
+    /// assert_eq!(s.cast_with::<u32>(RoundingMode::RoundOut), size2(11, 4));
+    /// ```
+    #[inline]
+    pub fn cast_with<NewT: NumCast>(self, mode: RoundingMode) -> Size2D<NewT, U>
+    where
+        T: Round + RoundHalfEven + Floor + Ceil,
+    {
+        match mode {
+            RoundingMode::Floor => self.floor(),
+            RoundingMode::Ceil | RoundingMode::RoundOut => self.ceil(),
+            RoundingMode::Round => self.round(),
+            RoundingMode::RoundHalfEven => self.round_half_even(),
+        }
+        .cast()
+    }
+
     // Convenience functions for common casts
 
     /// Cast into an `f32` size.
@@ -416,6 +629,45 @@ impl<T: Signed, U> Size2D<T, U> {
     }
 }
 
+impl<U> Size2D<u32, U> {
+    /// Returns true if both components are powers of two.
+    ///
+    /// Zero is not a power of two.
+    #[inline]
+    pub fn is_power_of_two(self) -> bool {
+        self.width.is_power_of_two() && self.height.is_power_of_two()
+    }
+
+    /// Returns the smallest size with power of two components greater than or equal to
+    /// this size.
+    ///
+    /// Zero components become one. Like `u32::next_power_of_two`, this panics in debug
+    /// mode if a component would overflow; see `checked_next_power_of_two`.
+    ///
+    /// ```rust
+    /// # use euclid::size2;
+    /// # use euclid::default::Size2D;
+    /// let size: Size2D<u32> = size2(100, 64);
+    /// assert_eq!(size.next_power_of_two(), size2(128, 64));
+    /// assert!(size.next_power_of_two().is_power_of_two());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn next_power_of_two(self) -> Self {
+        size2(self.width.next_power_of_two(), self.height.next_power_of_two())
+    }
+
+    /// Returns the smallest size with power of two components greater than or equal to
+    /// this size, or `None` if a component would overflow.
+    #[inline]
+    pub fn checked_next_power_of_two(self) -> Option<Self> {
+        Some(size2(
+            self.width.checked_next_power_of_two()?,
+            self.height.checked_next_power_of_two()?,
+        ))
+    }
+}
+
 impl<T: PartialOrd, U> Size2D<T, U> {
     /// Returns the size each component of which are minimum of this size and another.
     #[inline]
@@ -441,12 +693,28 @@ impl<T: PartialOrd, U> Size2D<T, U> {
         self.max(start).min(end)
     }
 
-    // Returns true if this size is larger or equal to the other size in all dimensions.
+    /// Returns true if this size is larger or equal to the other size in all dimensions.
     #[inline]
     pub fn contains(self, other: Self) -> bool {
         self.width >= other.width && self.height >= other.height
     }
 
+    /// Returns true if this size fits in the other size, as is or rotated by 90 degrees,
+    /// that is if `other` contains this size or its transpose.
+    ///
+    /// ```rust
+    /// # use euclid::size2;
+    /// # use euclid::default::Size2D;
+    /// let slot: Size2D<i32> = size2(100, 50);
+    /// assert!(size2(40, 80).fits_in_rotated(slot));
+    /// assert!(!size2(40, 120).fits_in_rotated(slot));
+    /// ```
+    #[inline]
+    pub fn fits_in_rotated(self, other: Self) -> bool {
+        (other.width >= self.width && other.height >= self.height)
+            || (other.width >= self.height && other.height >= self.width)
+    }
+
     /// Returns vector with results of "greater then" operation on each component.
     pub fn greater_than(self, other: Self) -> BoolVector2D {
         BoolVector2D {
@@ -727,6 +995,46 @@ mod size2d {
         assert_eq!(p.area(), 3.0);
     }
 
+    #[test]
+    pub fn test_checked_area() {
+        assert_eq!(Size2D::new(40_000, 50_000).checked_area(), Some(2_000_000_000i32));
+        assert_eq!(Size2D::new(50_000, 50_000).checked_area(), None::<i32>);
+        assert_eq!(Size2D::new(50_000, 50_000).checked_area(), Some(2_500_000_000u32));
+    }
+
+    #[test]
+    pub fn test_round_with_error() {
+        let s: Size2D<f32> = Size2D::new(10.25, 3.5);
+        let (rounded, error) = s.round_with_error();
+        assert_eq!(rounded, Size2D::new(10.0, 4.0));
+        assert_eq!(error, crate::vec2(0.25, -0.5));
+        assert_eq!(rounded + error.to_size(), s);
+    }
+
+    #[test]
+    pub fn test_contains() {
+        let slot: Size2D<f32> = Size2D::new(64.0, 32.0);
+        assert!(slot.contains(Size2D::new(64.0, 32.0)));
+        assert!(!slot.contains(Size2D::new(32.0, 64.0)));
+        assert!(Size2D::new(32.0, 64.0).fits_in_rotated(slot));
+        assert!(Size2D::new(20.0, 30.0).fits_in_rotated(slot));
+        assert!(!Size2D::new(33.0, 33.0).fits_in_rotated(slot));
+        assert!(!Size2D::new(f32::NAN, 1.0).fits_in_rotated(slot));
+    }
+
+    #[test]
+    pub fn test_power_of_two() {
+        let size: Size2D<u32> = Size2D::new(100, 64);
+        assert!(!size.is_power_of_two());
+        assert!(Size2D::<u32>::new(1, 1024).is_power_of_two());
+        assert!(!Size2D::<u32>::new(0, 1).is_power_of_two());
+
+        assert_eq!(size.next_power_of_two(), Size2D::new(128, 64));
+        assert_eq!(Size2D::<u32>::new(0, 1).next_power_of_two(), Size2D::new(1, 1));
+        assert_eq!(size.checked_next_power_of_two(), Some(Size2D::new(128, 64)));
+        assert_eq!(Size2D::<u32>::new(1, (1 << 31) + 1).checked_next_power_of_two(), None);
+    }
+
     #[cfg(feature = "mint")]
     #[test]
     pub fn test_mint() {
@@ -737,6 +1045,13 @@ mod size2d {
         assert_eq!(s1, s2);
     }
 
+    #[cfg(feature = "quickcheck")]
+    quickcheck::quickcheck! {
+        fn prop_size_round_trip(s: Size2D<i32>) -> bool {
+            s.to_vector().to_size() == s && Size2D::from(s.to_tuple()) == s
+        }
+    }
+
     mod ops {
         use crate::default::Size2D;
         use crate::scale::Scale;
@@ -1021,6 +1336,36 @@ impl<T: fmt::Debug, U> fmt::Debug for Size3D<T, U> {
     }
 }
 
+impl<T: fmt::Display, U> Size3D<T, U> {
+    pub(crate) fn fmt_with_suffix(&self, f: &mut fmt::Formatter, suffix: &str) -> fmt::Result {
+        fmt::Display::fmt(&self.width, f)?;
+        write!(f, "x")?;
+        fmt::Display::fmt(&self.height, f)?;
+        write!(f, "x")?;
+        fmt::Display::fmt(&self.depth, f)?;
+        write!(f, "{}", suffix)
+    }
+}
+
+impl<T: fmt::Display, U> fmt::Display for Size3D<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_suffix(f, "")
+    }
+}
+
+impl<T, U: UnitName> Size3D<T, U> {
+    /// Returns an adaptor displaying the size with the suffix of its unit after each value.
+    pub fn display_with_unit(&self) -> DisplayWithUnit<'_, Self> {
+        DisplayWithUnit(self)
+    }
+}
+
+impl<'a, T: fmt::Display, U: UnitName> fmt::Display for DisplayWithUnit<'a, Size3D<T, U>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_with_suffix(f, U::SUFFIX)
+    }
+}
+
 impl<T: Default, U> Default for Size3D<T, U> {
     fn default() -> Self {
         Size3D::new(Default::default(), Default::default(), Default::default())
@@ -1198,6 +1543,16 @@ impl<T: Copy, U> Size3D<T, U> {
     }
 }
 
+impl<T, U> Interpolate<T> for Size3D<T, U>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    #[inline]
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
 impl<T: NumCast + Copy, U> Size3D<T, U> {
     /// Cast from one numeric representation to another, preserving the units.
     ///
@@ -1337,7 +1692,7 @@ impl<T: PartialOrd, U> Size3D<T, U> {
         self.max(start).min(end)
     }
 
-    // Returns true if this size is larger or equal to the other size in all dimensions.
+    /// Returns true if this size is larger or equal to the other size in all dimensions.
     #[inline]
     pub fn contains(self, other: Self) -> bool {
         self.width >= other.width && self.height >= other.height && self.depth >= other.depth
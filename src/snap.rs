@@ -0,0 +1,169 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Snapping of points and rectangles to the device pixel grid.
+//!
+//! The functions in this module take a value in some logical space (for example CSS
+//! pixels) along with the `Scale` to device pixels, and return the snapped device
+//! space value together with the error introduced by snapping. Keeping the error
+//! around lets callers compensate for it, for example to position content relative
+//! to a snapped clip without seams.
+//!
+//! ```rust
+//! use euclid::{point2, rect, vec2, Scale};
+//! use euclid::approxeq::ApproxEq;
+//! use euclid::snap;
+//!
+//! enum CssPx {}
+//! enum DevicePx {}
+//!
+//! let scale: Scale<f32, CssPx, DevicePx> = Scale::new(2.0);
+//!
+//! let snapped = snap::snap_point(point2(10.2, 3.3), scale);
+//! assert_eq!(snapped.value, point2(20.0, 7.0));
+//! assert!(snapped.error.approx_eq(&vec2(-0.4, 0.4)));
+//!
+//! let r = snap::snap_rect_out(&rect(0.1, 0.1, 10.0, 10.0), scale);
+//! assert_eq!(r.value, rect(0.0, 0.0, 21.0, 21.0));
+//! ```
+
+use crate::box2d::Box2D;
+use crate::num::{Ceil, Floor, Round};
+use crate::point::Point2D;
+use crate::rect::Rect;
+use crate::scale::Scale;
+use crate::side_offsets::SideOffsets2D;
+use crate::vector::Vector2D;
+use core::ops::{Add, Div, Mul, Sub};
+
+/// A snapped value along with the error introduced by snapping it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Snapped<V, E> {
+    /// The value, snapped to the device pixel grid.
+    pub value: V,
+    /// The difference between the snapped value and the exact one, in device space.
+    pub error: E,
+}
+
+/// Snaps a point to the nearest device pixel.
+///
+/// The error is the vector from the exact device space position to the snapped one.
+pub fn snap_point<T, Src, Dst>(
+    point: Point2D<T, Src>,
+    scale: Scale<T, Src, Dst>,
+) -> Snapped<Point2D<T, Dst>, Vector2D<T, Dst>>
+where
+    T: Copy + Round + Mul<Output = T> + Sub<Output = T>,
+{
+    let exact = scale.transform_point(point);
+    let value = exact.round();
+
+    Snapped {
+        value,
+        error: value - exact,
+    }
+}
+
+/// Snaps the edges of a rectangle to the nearest device pixels.
+///
+/// This has the same semantics as `Rect::round`. The error holds how far each edge
+/// moved outwards, negative values meaning that the edge moved inwards.
+pub fn snap_rect<T, Src, Dst>(
+    rect: &Rect<T, Src>,
+    scale: Scale<T, Src, Dst>,
+) -> Snapped<Rect<T, Dst>, SideOffsets2D<T, Dst>>
+where
+    T: Copy + Round + Add<Output = T> + Mul<Output = T> + Sub<Output = T>,
+{
+    let exact = scale.transform_rect(rect).to_box2d();
+    snapped_box(exact, exact.round())
+}
+
+/// Snaps the edges of a rectangle outwards to device pixels, such that the snapped
+/// rectangle contains the exact one.
+///
+/// This has the same semantics as `Rect::round_out`. The error holds how far each
+/// edge moved outwards, and is never negative.
+pub fn snap_rect_out<T, Src, Dst>(
+    rect: &Rect<T, Src>,
+    scale: Scale<T, Src, Dst>,
+) -> Snapped<Rect<T, Dst>, SideOffsets2D<T, Dst>>
+where
+    T: Copy + Floor + Ceil + Add<Output = T> + Mul<Output = T> + Sub<Output = T>,
+{
+    let exact = scale.transform_rect(rect).to_box2d();
+    snapped_box(exact, exact.round_out())
+}
+
+/// Converts a snapped point back to the source space.
+#[inline]
+pub fn unsnap_point<T, Src, Dst>(point: Point2D<T, Dst>, scale: Scale<T, Src, Dst>) -> Point2D<T, Src>
+where
+    T: Copy + Div<Output = T>,
+{
+    point / scale
+}
+
+/// Converts a snapped rectangle back to the source space.
+#[inline]
+pub fn unsnap_rect<T, Src, Dst>(rect: &Rect<T, Dst>, scale: Scale<T, Src, Dst>) -> Rect<T, Src>
+where
+    T: Copy + Div<Output = T>,
+{
+    *rect / scale
+}
+
+fn snapped_box<T, U>(exact: Box2D<T, U>, snapped: Box2D<T, U>) -> Snapped<Rect<T, U>, SideOffsets2D<T, U>>
+where
+    T: Copy + Sub<Output = T>,
+{
+    Snapped {
+        value: snapped.to_rect(),
+        error: SideOffsets2D::new(
+            exact.min.y - snapped.min.y,
+            snapped.max.x - exact.max.x,
+            snapped.max.y - exact.max.y,
+            exact.min.x - snapped.min.x,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{point2, rect};
+
+    enum CssPx {}
+    enum DevicePx {}
+
+    #[test]
+    fn test_snap_rect() {
+        let scale: Scale<f32, CssPx, DevicePx> = Scale::new(2.0);
+        let r = rect(0.25, 0.75, 1.0, 1.0);
+
+        let snapped = snap_rect(&r, scale);
+        assert_eq!(snapped.value, rect(1.0, 2.0, 2.0, 2.0));
+        assert_eq!(snapped.error, SideOffsets2D::new(-0.5, 0.5, 0.5, -0.5));
+
+        let snapped = snap_rect_out(&r, scale);
+        assert_eq!(snapped.value, rect(0.0, 1.0, 3.0, 3.0));
+        assert_eq!(snapped.error, SideOffsets2D::new_all_same(0.5));
+        assert!(snapped.value.contains_rect(&scale.transform_rect(&r)));
+
+        assert_eq!(unsnap_rect(&snapped.value, scale), rect(0.0, 0.5, 1.5, 1.5));
+    }
+
+    #[test]
+    fn test_unsnap_point() {
+        let scale: Scale<f32, CssPx, DevicePx> = Scale::new(2.0);
+        let snapped = snap_point(point2(0.7, -0.7), scale);
+        assert_eq!(snapped.value, point2(1.0, -1.0));
+        assert_eq!(unsnap_point(snapped.value, scale), point2(0.5, -0.5));
+    }
+}
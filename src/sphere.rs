@@ -0,0 +1,297 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::box3d::Box3D;
+use crate::frustum::Frustum;
+use crate::num::{One, Zero};
+use crate::point::Point3D;
+use crate::transform3d::Transform3D;
+use crate::vector::{vec3, Vector3D};
+
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Mul, Neg, Sub};
+
+/// A sphere, represented by its center and radius, optionally tagged with a unit.
+///
+/// Spheres are cheaper to cull than boxes, at the cost of a looser fit.
+#[repr(C)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct Sphere<T, U> {
+    pub center: Point3D<T, U>,
+    pub radius: T,
+}
+
+impl<T: Copy, U> Copy for Sphere<T, U> {}
+
+impl<T: Clone, U> Clone for Sphere<T, U> {
+    fn clone(&self) -> Self {
+        Sphere {
+            center: self.center.clone(),
+            radius: self.radius.clone(),
+        }
+    }
+}
+
+impl<T: PartialEq, U> PartialEq for Sphere<T, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.center == other.center && self.radius == other.radius
+    }
+}
+
+impl<T: Eq, U> Eq for Sphere<T, U> {}
+
+impl<T: Hash, U> Hash for Sphere<T, U> {
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        self.center.hash(h);
+        self.radius.hash(h);
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for Sphere<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sphere")
+            .field("center", &self.center)
+            .field("radius", &self.radius)
+            .finish()
+    }
+}
+
+impl<T, U> Sphere<T, U> {
+    /// Constructor.
+    #[inline]
+    pub const fn new(center: Point3D<T, U>, radius: T) -> Self {
+        Sphere { center, radius }
+    }
+}
+
+impl<T, U> Sphere<T, U>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    /// Returns true if the point is inside of the sphere or on its surface.
+    #[inline]
+    pub fn contains_point3d(&self, p: Point3D<T, U>) -> bool {
+        (p - self.center).square_length() <= self.radius * self.radius
+    }
+
+    /// Returns true if the sphere is at least partially inside of the frustum.
+    ///
+    /// Like `Frustum::intersects_box3d`, the test is conservative and can return true
+    /// for some spheres that are outside of the frustum, near its edges.
+    pub fn intersects_frustum(&self, frustum: &Frustum<T, U>) -> bool
+    where
+        T: Neg<Output = T>,
+    {
+        frustum
+            .planes
+            .iter()
+            .all(|plane| plane.signed_distance_to_point(self.center) >= -self.radius)
+    }
+}
+
+impl<T: Float, U> Sphere<T, U> {
+    /// Returns the smallest sphere containing the box.
+    ///
+    /// ```rust
+    /// use euclid::default::{Box3D, Sphere};
+    /// use euclid::point3;
+    ///
+    /// let b = Box3D::new(point3(0.0, 0.0, 0.0), point3(2.0, 4.0, 4.0));
+    /// let s = Sphere::from_box3d(&b);
+    /// assert_eq!(s, Sphere::new(point3(1.0, 2.0, 2.0), 3.0));
+    /// ```
+    #[inline]
+    pub fn from_box3d(b: &Box3D<T, U>) -> Self {
+        let center = b.center();
+        Sphere::new(center, (b.max - center).length())
+    }
+
+    /// Returns a sphere containing the result of transforming this sphere, or `None`
+    /// if the transform has a perspective component, which doesn't map spheres to
+    /// ellipsoids.
+    ///
+    /// The radius is scaled by the largest factor by which the transform stretches a
+    /// vector, which is its largest singular value, so the result is only the smallest
+    /// sphere containing the transformed one for transforms with a uniform scale.
+    pub fn transform<Dst>(&self, m: &Transform3D<T, U, Dst>) -> Option<Sphere<T, Dst>> {
+        let (zero, one) = (T::zero(), T::one());
+        if m.m14 != zero || m.m24 != zero || m.m34 != zero || m.m44 != one {
+            return None;
+        }
+
+        let center = m.transform_point3d(self.center)?;
+        Some(Sphere::new(center, self.radius * max_singular_value(m)))
+    }
+
+    /// Returns the smallest `t >= 0` such that `origin + direction * t` is on the
+    /// surface of the sphere, or `None` if the ray misses the sphere.
+    ///
+    /// If the origin is inside of the sphere, this is where the ray exits it. The
+    /// direction doesn't need to be normalized, but `t` is only a distance if it is.
+    pub fn ray_intersection(&self, origin: Point3D<T, U>, direction: Vector3D<T, U>) -> Option<T> {
+        // Solve |origin + direction * t - center|² = radius² for t.
+        let to_origin = origin - self.center;
+        let a = direction.square_length();
+        let half_b = direction.dot(to_origin);
+        let c = to_origin.square_length() - self.radius * self.radius;
+
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < T::zero() || a == T::zero() {
+            return None;
+        }
+
+        let sqrt_d = discriminant.sqrt();
+        let near = (-half_b - sqrt_d) / a;
+        let far = (-half_b + sqrt_d) / a;
+        if near >= T::zero() {
+            Some(near)
+        } else if far >= T::zero() {
+            Some(far)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the largest singular value of the upper 3x3 matrix of the transform.
+///
+/// This is the square root of the largest eigenvalue of the symmetric matrix `A` whose
+/// terms are the dot products of the rows, computed with the closed form for symmetric
+/// 3x3 matrices.
+fn max_singular_value<T: Float, Src, Dst>(m: &Transform3D<T, Src, Dst>) -> T {
+    // The rows of the upper 3x3 matrix are the images of the unit axes.
+    let r1 = vec3::<T, Src>(m.m11, m.m12, m.m13);
+    let r2 = vec3::<T, Src>(m.m21, m.m22, m.m23);
+    let r3 = vec3::<T, Src>(m.m31, m.m32, m.m33);
+    let (a11, a22, a33) = (r1.dot(r1), r2.dot(r2), r3.dot(r3));
+    let (a12, a13, a23) = (r1.dot(r2), r1.dot(r3), r2.dot(r3));
+
+    let max_diagonal = a11.max(a22).max(a33);
+    let off_diagonal = a12 * a12 + a13 * a13 + a23 * a23;
+    if off_diagonal == T::zero() {
+        return max_diagonal.sqrt();
+    }
+
+    let (two, three, six) = (T::one() + T::one(), T::from(3.0).unwrap(), T::from(6.0).unwrap());
+    let trace = a11 + a22 + a33;
+    let q = trace / three;
+    let (b11, b22, b33) = (a11 - q, a22 - q, a33 - q);
+    let p = ((b11 * b11 + b22 * b22 + b33 * b33 + two * off_diagonal) / six).sqrt();
+    // Half of the determinant of (A - qI) / p, which is within [-1, 1] up to rounding.
+    let det = b11 * (b22 * b33 - a23 * a23) - a12 * (a12 * b33 - a23 * a13)
+        + a13 * (a12 * a23 - b22 * a13);
+    let r = (det / (p * p * p) / two).max(-T::one()).min(T::one());
+    let eigenvalue = q + two * p * (r.acos() / three).cos();
+
+    // Round up so that rounding errors don't make the sphere too small. The largest
+    // eigenvalue is at least the largest diagonal term and at most the trace.
+    let margin = T::one() + T::epsilon() * T::from(16.0).unwrap();
+    (eigenvalue * margin).max(max_diagonal).min(trace).sqrt()
+}
+
+impl<T: Zero + One, U> Sphere<T, U> {
+    /// The sphere of radius one, centered on the origin.
+    #[inline]
+    pub fn unit() -> Self {
+        Sphere::new(Point3D::origin(), T::one())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::default::{Box3D, Frustum, Sphere, Transform3D};
+    use crate::{point3, vec3, Angle};
+
+    #[test]
+    fn test_bounding_sphere() {
+        let b = Box3D::new(point3(-1.0, -1.0, -1.0), point3(1.0, 1.0, 1.0));
+        let s = Sphere::from_box3d(&b);
+        assert_eq!(s.center, point3(0.0, 0.0, 0.0));
+        assert_eq!(s.radius, 3.0f64.sqrt());
+        assert!(s.contains_point3d(point3(-0.99, -0.99, -0.99)));
+        assert!(s.contains_point3d(point3(1.7, 0.0, 0.0)));
+        assert!(!s.contains_point3d(point3(1.8, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_transform() {
+        let s: Sphere<f64> = Sphere::new(point3(1.0, 0.0, 0.0), 2.0);
+        let m = Transform3D::scale(2.0, 3.0, 1.0).then_translate(vec3(0.0, 1.0, 0.0));
+        assert_eq!(s.transform(&m), Some(Sphere::new(point3(2.0, 1.0, 0.0), 6.0)));
+
+        let m = Transform3D::rotation(0.0, 0.0, 1.0, Angle::degrees(90.0));
+        let t = s.transform(&m).unwrap();
+        assert!((t.center - point3(0.0, 1.0, 0.0)).length() < 1e-6);
+        assert!((t.radius - 2.0).abs() < 1e-6);
+
+        assert_eq!(s.transform(&Transform3D::perspective(5.0)), None);
+    }
+
+    #[test]
+    fn test_transform_shear() {
+        // Rows (1, 0, 0), (1, 1, 0) and (0, 0, 1): the largest stretch is the golden ratio,
+        // more than the length of the longest row.
+        let m = Transform3D::<f64>::new(
+            1.0, 0.0, 0.0, 0.0,
+            1.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            5.0, 0.0, 0.0, 1.0,
+        );
+        let s = Sphere::new(point3(0.0, 0.0, 0.0), 1.0);
+        let t = s.transform(&m).unwrap();
+        let golden = (1.0 + 5.0f64.sqrt()) / 2.0;
+        assert!(t.radius >= golden && t.radius - golden < 1e-9);
+
+        let skewed = Transform3D::skew(Angle::degrees(30.0), Angle::degrees(-50.0))
+            .then(&Transform3D::rotation(0.6, 0.0, 0.8, Angle::degrees(40.0)))
+            .then_scale(1.0, 2.0, 0.5)
+            .then(&m);
+        for m in &[m, skewed] {
+            let t = s.transform(m).unwrap();
+            for i in 0..40 {
+                for j in 0..20 {
+                    let (theta, phi) = (i as f64 * 0.157, j as f64 * 0.157);
+                    let p = point3(theta.cos() * phi.sin(), theta.sin() * phi.sin(), phi.cos());
+                    let q = m.transform_point3d(p).unwrap();
+                    assert!(t.contains_point3d(q), "{:?} is outside of {:?}", q, t);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_frustum() {
+        let f = Frustum::from_transform(&Transform3D::<f64>::ortho(-10.0, 10.0, -10.0, 10.0, 1.0, 100.0));
+        assert!(Sphere::new(point3(0.0, 0.0, -50.0), 1.0).intersects_frustum(&f));
+        assert!(Sphere::new(point3(11.0, 0.0, -50.0), 2.0).intersects_frustum(&f));
+        assert!(!Sphere::new(point3(13.0, 0.0, -50.0), 2.0).intersects_frustum(&f));
+        assert!(!Sphere::new(point3(0.0, 0.0, 2.0), 2.0).intersects_frustum(&f));
+    }
+
+    #[test]
+    fn test_ray_intersection() {
+        let s = Sphere::new(point3(0.0, 0.0, -10.0), 2.0);
+        let origin = point3(0.0, 0.0, 0.0);
+        assert_eq!(s.ray_intersection(origin, vec3(0.0, 0.0, -1.0)), Some(8.0));
+        assert_eq!(s.ray_intersection(origin, vec3(0.0, 0.0, -2.0)), Some(4.0));
+        assert_eq!(s.ray_intersection(origin, vec3(0.0, 0.0, 1.0)), None);
+        assert_eq!(s.ray_intersection(origin, vec3(0.0, 1.0, 0.0)), None);
+        assert_eq!(s.ray_intersection(point3(0.0, 0.0, -9.0), vec3(0.0, 0.0, -1.0)), Some(3.0));
+        assert_eq!(Sphere::<f64>::unit().ray_intersection(origin, vec3(1.0, 0.0, 0.0)), Some(1.0));
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Smooth curves going through a sequence of points.
+//!
+//! The curves are uniform Catmull-Rom splines: each segment between two consecutive
+//! points is a cubic curve whose tangents are given by the neighboring points. The
+//! end points are repeated, so the curve starts and ends at the first and last
+//! points. Segments can be converted to `CubicBezier` curves, for example to measure
+//! their length or to draw them.
+//!
+//! ```rust
+//! use euclid::default::Point2D;
+//! use euclid::point2;
+//! use euclid::spline;
+//!
+//! let points: [Point2D<f32>; 4] = [point2(0.0, 0.0), point2(1.0, 1.0), point2(2.0, 0.0), point2(3.0, 1.0)];
+//! assert_eq!(spline::catmull_rom_segments(&points).count(), 3);
+//!
+//! // Parameters go from 0 at the first point to 3 at the last one.
+//! assert_eq!(spline::sample_catmull_rom(&points, 1.0), Some(point2(1.0, 1.0)));
+//! assert_eq!(spline::sample_catmull_rom(&points, 3.0), Some(point2(3.0, 1.0)));
+//! ```
+
+use crate::bezier::CubicBezier;
+use crate::point::Point2D;
+use num_traits::Float;
+
+/// Returns the curve going from `points[i]` to `points[i + 1]`, with the end points
+/// repeated as needed.
+fn segment<T: Float, U>(points: &[Point2D<T, U>], i: usize) -> CubicBezier<T, U> {
+    let last = points.len() - 1;
+    let p0 = points[i.saturating_sub(1)];
+    let (p1, p2) = (points[i], points[i + 1]);
+    let p3 = points[(i + 2).min(last)];
+
+    let two = T::one() + T::one();
+    CubicBezier::from_hermite(p1, (p2 - p0) / two, p2, (p3 - p1) / two)
+}
+
+/// Returns the segments of the Catmull-Rom spline going through the points, as Bézier
+/// curves.
+///
+/// There is one segment less than there are points, and none if there are less than
+/// two points.
+pub fn catmull_rom_segments<T: Float, U>(
+    points: &[Point2D<T, U>],
+) -> impl Iterator<Item = CubicBezier<T, U>> + '_ {
+    (0..points.len().saturating_sub(1)).map(move |i| segment(points, i))
+}
+
+/// Returns the point at `t` on the Catmull-Rom spline going through the points, or
+/// `None` if there are no points.
+///
+/// The integer values of `t` are at the points, `t = 0` being at the first one. Values
+/// outside of the range of the points are clamped.
+pub fn sample_catmull_rom<T: Float, U>(points: &[Point2D<T, U>], t: T) -> Option<Point2D<T, U>> {
+    match points.len() {
+        0 => return None,
+        1 => return Some(points[0]),
+        _ => {}
+    }
+
+    let last = T::from(points.len() - 1).unwrap();
+    let t = t.max(T::zero()).min(last);
+    // The last point is at the end of the last segment.
+    let i = t.floor().min(last - T::one());
+    let index = i.to_usize().unwrap();
+
+    Some(segment(points, index).sample(t - i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default;
+    use crate::point2;
+
+    #[test]
+    fn test_catmull_rom() {
+        let points: [default::Point2D<f64>; 4] = [
+            point2(0.0, 0.0),
+            point2(1.0, 2.0),
+            point2(3.0, 2.0),
+            point2(4.0, 0.0),
+        ];
+        let segments: [CubicBezier<f64, crate::UnknownUnit>; 3] = {
+            let mut iter = catmull_rom_segments(&points);
+            [iter.next().unwrap(), iter.next().unwrap(), iter.next().unwrap()]
+        };
+
+        // The curve goes through the points and is smooth at them.
+        for (i, s) in segments.iter().enumerate() {
+            assert_eq!(s.from, points[i]);
+            assert_eq!(s.to, points[i + 1]);
+        }
+        let close = |a: default::Vector2D<f64>, b: default::Vector2D<f64>| (a - b).length() < 1e-12;
+        assert!(close(segments[0].derivative(1.0), segments[1].derivative(0.0)));
+        assert!(close(segments[1].derivative(1.0), segments[2].derivative(0.0)));
+
+        // The tangent at a point is parallel to the line between its neighbors.
+        assert!(close(segments[1].derivative(0.0), (points[2] - points[0]) / 2.0));
+
+        // The curve is symmetric, like the points.
+        let mid = sample_catmull_rom(&points, 1.5).unwrap();
+        assert!((mid.x - 2.0).abs() < 1e-12);
+        assert!(mid.y > 2.0);
+
+        assert_eq!(sample_catmull_rom(&points, -1.0), Some(points[0]));
+        assert_eq!(sample_catmull_rom(&points, 2.0), Some(points[2]));
+        assert_eq!(sample_catmull_rom(&points, 10.0), Some(points[3]));
+    }
+
+    #[test]
+    fn test_catmull_rom_few_points() {
+        let empty: [default::Point2D<f32>; 0] = [];
+        assert_eq!(catmull_rom_segments(&empty).count(), 0);
+        assert_eq!(sample_catmull_rom(&empty, 0.0), None);
+
+        let one: [default::Point2D<f32>; 1] = [point2(1.0, 2.0)];
+        assert_eq!(catmull_rom_segments(&one).count(), 0);
+        assert_eq!(sample_catmull_rom(&one, 0.5), Some(point2(1.0, 2.0)));
+
+        // With two points the spline is a straight line.
+        let two: [default::Point2D<f32>; 2] = [point2(0.0, 0.0), point2(3.0, 0.0)];
+        assert_eq!(sample_catmull_rom(&two, 0.5), Some(point2(1.5, 0.0)));
+    }
+}
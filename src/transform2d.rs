@@ -17,15 +17,17 @@ use crate::point::{Point2D, point2};
 use crate::vector::{Vector2D, vec2};
 use crate::rect::Rect;
 use crate::box2d::Box2D;
+use crate::display::write_grid;
 use crate::transform3d::Transform3D;
-use core::ops::{Add, Mul, Div, Sub};
+use core::ops::{Add, Mul, Div, Neg, Sub};
 use core::marker::PhantomData;
 use core::cmp::{Eq, PartialEq};
 use core::hash::{Hash};
 use crate::approxeq::ApproxEq;
+use crate::interpolate::Interpolate;
 use crate::trig::Trig;
 use core::fmt;
-use num_traits::NumCast;
+use num_traits::{Float, FloatConst, NumCast};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -84,6 +86,32 @@ where
     }
 }
 
+/// Shrinks each term towards the identity transform.
+#[cfg(feature = "quickcheck")]
+impl<T, Src, Dst> quickcheck::Arbitrary for Transform2D<T, Src, Dst>
+where
+    T: quickcheck::Arbitrary + num_traits::Float,
+    Src: 'static,
+    Dst: 'static,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let (m11, m12, m21, m22, m31, m32) = quickcheck::Arbitrary::arbitrary(g);
+        Transform2D::new(m11, m12, m21, m22, m31, m32)
+    }
+
+    fn shrink(&self) -> alloc::boxed::Box<dyn Iterator<Item = Self>> {
+        let identity = Self::identity().to_array();
+        let array = self.to_array();
+        alloc::boxed::Box::new((0..6).flat_map(move |i| {
+            quickcheck::Arbitrary::shrink(&(array[i] - identity[i])).map(move |delta| {
+                let mut shrunk = array;
+                shrunk[i] = identity[i] + delta;
+                Transform2D::from_array(shrunk)
+            })
+        }))
+    }
+}
+
 impl<T: Copy, Src, Dst> Copy for Transform2D<T, Src, Dst> {}
 
 impl<T: Clone, Src, Dst> Clone for Transform2D<T, Src, Dst> {
@@ -301,6 +329,31 @@ impl<T: Copy, Src, Dst> Transform2D<T, Src, Dst> {
     {
         Transform3D::new_2d(self.m11, self.m12, self.m21, self.m22, self.m31, self.m32)
     }
+
+    /// Returns the terms of the 3 by 3 matrix of this transform in the column-vector
+    /// notation, row by row.
+    ///
+    /// This is the layout used by most APIs transforming column vectors, for example
+    /// Skia's `SkMatrix`: the translation terms are at indices 2 and 5, and the last row
+    /// is `[0, 0, 1]`. See `to_array` for the layout of `Transform2D::new` instead.
+    ///
+    /// ```rust
+    /// use euclid::default::Transform2D;
+    ///
+    /// let m = Transform2D::new(1, 2, 3, 4, 5, 6);
+    /// assert_eq!(m.to_row_major_3x3(), [1, 3, 5, 2, 4, 6, 0, 0, 1]);
+    /// ```
+    #[inline]
+    pub fn to_row_major_3x3(&self) -> [T; 9]
+    where
+        T: Zero + One,
+    {
+        [
+            self.m11, self.m21, self.m31,
+            self.m12, self.m22, self.m32,
+            T::zero(), T::zero(), T::one(),
+        ]
+    }
 }
 
 impl<T: NumCast + Copy, Src, Dst> Transform2D<T, Src, Dst> {
@@ -327,6 +380,20 @@ impl<T: NumCast + Copy, Src, Dst> Transform2D<T, Src, Dst> {
             _ => None
         }
     }
+
+    // Convenience functions for common casts.
+
+    /// Cast into an `f32` transform.
+    #[inline]
+    pub fn to_f32(&self) -> Transform2D<f32, Src, Dst> {
+        self.cast()
+    }
+
+    /// Cast into an `f64` transform.
+    #[inline]
+    pub fn to_f64(&self) -> Transform2D<f64, Src, Dst> {
+        self.cast()
+    }
 }
 
 impl<T, Src, Dst> Transform2D<T, Src, Dst>
@@ -356,6 +423,26 @@ where
     }
 }
 
+macro_rules! transform2d_identity {
+    ($ty:ty, $_0:expr, $_1:expr) => {
+        impl<Src, Dst> Transform2D<$ty, Src, Dst> {
+            /// The identity transform, usable in constant expressions.
+            ///
+            /// Equivalent to [`identity()`](#method.identity).
+            pub const IDENTITY: Self = Transform2D::new(
+                $_1, $_0,
+                $_0, $_1,
+                $_0, $_0,
+            );
+        }
+    };
+}
+
+transform2d_identity!(f32, 0.0, 1.0);
+transform2d_identity!(f64, 0.0, 1.0);
+transform2d_identity!(i32, 0, 1);
+transform2d_identity!(i64, 0, 1);
+
 
 /// Methods for combining generic transformations
 impl<T, Src, Dst> Transform2D<T, Src, Dst>
@@ -457,6 +544,33 @@ where
     }
 }
 
+/// Methods for creating skew transformations
+impl<T, Src, Dst> Transform2D<T, Src, Dst>
+where
+    T: Copy + Zero + One + Trig,
+{
+    /// Create a 2d skew transform, skewing along the x axis by `alpha` and along the
+    /// y axis by `beta`.
+    ///
+    /// See <https://drafts.csswg.org/css-transforms/#funcdef-skew>
+    ///
+    /// ```rust
+    /// use euclid::{point2, Angle};
+    /// use euclid::approxeq::ApproxEq;
+    /// use euclid::default::Transform2D;
+    ///
+    /// let skew = Transform2D::skew(Angle::degrees(45.0), Angle::degrees(0.0));
+    /// assert!(skew.transform_point(point2(0.0, 1.0)).approx_eq(&point2(1.0, 1.0)));
+    /// ```
+    pub fn skew(alpha: Angle<T>, beta: Angle<T>) -> Self {
+        Self::new(
+            T::one(), beta.radians.tan(),
+            alpha.radians.tan(), T::one(),
+            T::zero(), T::zero(),
+        )
+    }
+}
+
 /// Methods for creating and combining scale transformations
 impl<T, Src, Dst> Transform2D<T, Src, Dst> {
     /// Create a 2d scale transform:
@@ -480,6 +594,29 @@ impl<T, Src, Dst> Transform2D<T, Src, Dst> {
         )
     }
 
+    /// Create a transform flipping the y axis within a viewport of the given height:
+    ///
+    /// ```text
+    /// 1  0
+    /// 0 -1
+    /// 0  height
+    /// ```
+    ///
+    /// This converts between y-down coordinates, as used by web content, and y-up
+    /// coordinates, as used by OpenGL framebuffers. The transform is its own inverse.
+    /// See also `Transform3D::flip_y`.
+    #[inline]
+    pub fn flip_y(height: T) -> Self
+    where
+        T: Zero + One + Neg<Output = T>,
+    {
+        Self::new(
+            T::one(),  T::zero(),
+            T::zero(), -T::one(),
+            T::zero(), height,
+        )
+    }
+
     /// Applies a scale after self's transformation and returns the resulting transform.
     #[inline]
     #[must_use]
@@ -521,6 +658,9 @@ where
     }
 
     /// Returns the given vector transformed by this matrix.
+    ///
+    /// Unlike `transform_point`, this ignores the translation, which is what directions
+    /// and offsets need.
     #[inline]
     #[must_use]
     pub fn transform_vector(&self, vec: Vector2D<T, Src>) -> Vector2D<T, Dst> {
@@ -564,6 +704,30 @@ where
     }
 }
 
+impl<T, U> Transform2D<T, U, U>
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    /// Transforms each point of the slice in place.
+    ///
+    /// The result is the same as calling `transform_point` on each point, in a loop
+    /// that the compiler can vectorize. The transform must not change the unit.
+    ///
+    /// ```rust
+    /// use euclid::default::{Point2D, Transform2D};
+    /// use euclid::point2;
+    ///
+    /// let mut points: [Point2D<i32>; 2] = [point2(1, 2), point2(3, 4)];
+    /// Transform2D::translation(10, 20).transform_points(&mut points);
+    /// assert_eq!(points, [point2(11, 22), point2(13, 24)]);
+    /// ```
+    pub fn transform_points(&self, points: &mut [Point2D<T, U>]) {
+        for p in points.iter_mut() {
+            *p = self.transform_point(*p);
+        }
+    }
+}
+
 
 impl<T, Src, Dst> Transform2D<T, Src, Dst>
 where
@@ -604,6 +768,219 @@ where
     }
 }
 
+/// The components of an affine transform, as returned by `Transform2D::decompose`.
+///
+/// The transform is obtained by applying, in order, the scale, the shear, the rotation
+/// and the translation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct DecomposedTransform2D<T> {
+    pub translation: Vector2D<T, UnknownUnit>,
+    pub rotation: Angle<T>,
+    pub scale: Vector2D<T, UnknownUnit>,
+    /// Horizontal shear factor, such that `x` becomes `x + shear * y`.
+    pub shear: T,
+}
+
+impl<T: Float + FloatConst> DecomposedTransform2D<T> {
+    /// Interpolates each component, the rotation taking the shortest path.
+    ///
+    /// This is how affine transforms are usually animated, since interpolating the
+    /// terms of the matrices directly doesn't preserve the shapes.
+    pub fn lerp(&self, other: &Self, t: T) -> Self {
+        DecomposedTransform2D {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.lerp(other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+            shear: self.shear + (other.shear - self.shear) * t,
+        }
+    }
+}
+
+impl<T: Float, Src, Dst> Transform2D<T, Src, Dst> {
+    /// Returns the factors by which the x and y axes are scaled, which are the lengths
+    /// of the images of the unit vectors (the first two rows of the matrix).
+    ///
+    /// The factors are always positive, so a mirroring is not reported. This is useful
+    /// to pick the resolution at which to rasterize transformed content.
+    ///
+    /// ```rust
+    /// use euclid::default::Transform2D;
+    /// use euclid::vec2;
+    ///
+    /// let m = Transform2D::scale(2.0, -3.0).then_translate(vec2(5.0, 5.0));
+    /// assert_eq!(m.extract_scale(), vec2(2.0, 3.0));
+    /// ```
+    pub fn extract_scale(&self) -> Vector2D<T, UnknownUnit> {
+        vec2(
+            vec2::<T, UnknownUnit>(self.m11, self.m12).length(),
+            vec2::<T, UnknownUnit>(self.m21, self.m22).length(),
+        )
+    }
+
+    /// Splits this transform into a scale, a shear, a rotation and a translation, or
+    /// returns `None` if it is not invertible.
+    ///
+    /// A negative determinant is represented with a negative vertical scale.
+    pub fn decompose(&self) -> Option<DecomposedTransform2D<T>> {
+        let row1: Vector2D<T, UnknownUnit> = vec2(self.m11, self.m12);
+        let row2: Vector2D<T, UnknownUnit> = vec2(self.m21, self.m22);
+
+        let scale_x = row1.length();
+        if scale_x == T::zero() {
+            return None;
+        }
+        let row1 = row1 / scale_x;
+        let scale_y = row1.cross(row2);
+        if scale_y == T::zero() {
+            return None;
+        }
+
+        Some(DecomposedTransform2D {
+            translation: vec2(self.m31, self.m32),
+            rotation: Angle::radians(row1.y.atan2(row1.x)),
+            scale: vec2(scale_x, scale_y),
+            shear: row1.dot(row2) / scale_y,
+        })
+    }
+
+    /// Returns a fraction of this transform, going from the identity for `t = 0` to this
+    /// transform for `t = 1`, or `None` if it is not invertible.
+    ///
+    /// Each component of the decomposed transform is interpolated, see
+    /// `DecomposedTransform2D::lerp`, so the intermediate transforms don't distort the
+    /// shapes. Transforms that mirror shapes go through a degenerate transform.
+    ///
+    /// This is not a matrix power: composing the transform for `t = 0.5` with itself
+    /// generally doesn't give back this transform, since the translation is interpolated
+    /// linearly while the rotation turns it.
+    ///
+    /// ```rust
+    /// use euclid::default::Transform2D;
+    /// use euclid::approxeq::ApproxEq;
+    /// use euclid::{vec2, Angle};
+    ///
+    /// let m = Transform2D::rotation(Angle::degrees(90.0)).then_translate(vec2(10.0, 0.0));
+    /// let half: Transform2D<f32> = m.interpolate_from_identity(0.5).unwrap();
+    /// let expected = Transform2D::rotation(Angle::degrees(45.0)).then_translate(vec2(5.0, 0.0));
+    /// assert!(half.approx_eq(&expected));
+    /// assert!(!half.then(&half).approx_eq(&m));
+    /// ```
+    pub fn interpolate_from_identity(&self, t: T) -> Option<Self>
+    where
+        T: FloatConst,
+    {
+        let identity = DecomposedTransform2D {
+            translation: vec2(T::zero(), T::zero()),
+            rotation: Angle::zero(),
+            scale: vec2(T::one(), T::one()),
+            shear: T::zero(),
+        };
+
+        Some(Transform2D::recompose(&identity.lerp(&self.decompose()?, t)))
+    }
+
+    /// Builds a transform from its components, the inverse of `decompose`.
+    pub fn recompose(d: &DecomposedTransform2D<T>) -> Self {
+        let (sin, cos) = d.rotation.sin_cos();
+        let (sx, sy) = (d.scale.x, d.scale.y);
+        let shear = d.shear * sy;
+
+        Transform2D::new(
+            sx * cos, sx * sin,
+            shear * cos - sy * sin, shear * sin + sy * cos,
+            d.translation.x, d.translation.y,
+        )
+    }
+}
+
+/// A builder for `Transform2D`, applying operations in the order they are specified.
+///
+/// Each operation is applied after the previous ones, so that the transform built by
+/// `Transform2DBuilder::new().translate(x, y).rotate(a)` translates points and then
+/// rotates them around the origin. This is equivalent to chaining the `then_*`
+/// methods of `Transform2D`.
+///
+/// ```rust
+/// use euclid::{point2, Angle, Transform2DBuilder};
+/// use euclid::approxeq::ApproxEq;
+/// use euclid::default::Transform2D;
+///
+/// let transform: Transform2D<f32> = Transform2DBuilder::new()
+///     .translate(1.0, 0.0)
+///     .rotate(Angle::degrees(90.0))
+///     .scale(2.0, 2.0)
+///     .build();
+///
+/// let p = transform.transform_point(point2(0.0, 0.0));
+/// assert!(p.approx_eq(&point2(0.0, 2.0)));
+/// ```
+pub struct Transform2DBuilder<T, Src, Dst> {
+    transform: Transform2D<T, Src, Dst>,
+}
+
+impl<T, Src, Dst> Transform2DBuilder<T, Src, Dst>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Zero + One + Trig,
+{
+    /// Starts from the identity transform.
+    #[inline]
+    pub fn new() -> Self {
+        Transform2DBuilder {
+            transform: Transform2D::identity(),
+        }
+    }
+
+    /// Applies a translation.
+    #[inline]
+    #[must_use]
+    pub fn translate(self, x: T, y: T) -> Self {
+        self.then(&Transform2D::translation(x, y))
+    }
+
+    /// Applies a rotation around the origin.
+    #[inline]
+    #[must_use]
+    pub fn rotate(self, theta: Angle<T>) -> Self {
+        self.then(&Transform2D::rotation(theta))
+    }
+
+    /// Applies a scale relative to the origin.
+    #[inline]
+    #[must_use]
+    pub fn scale(self, x: T, y: T) -> Self {
+        self.then(&Transform2D::scale(x, y))
+    }
+
+    /// Applies an arbitrary transform.
+    #[inline]
+    #[must_use]
+    pub fn then(self, transform: &Transform2D<T, Dst, Dst>) -> Self {
+        Transform2DBuilder {
+            transform: self.transform.then(transform),
+        }
+    }
+
+    /// Returns the built transform.
+    #[inline]
+    pub fn build(self) -> Transform2D<T, Src, Dst> {
+        self.transform
+    }
+}
+
+impl<T, Src, Dst> Default for Transform2DBuilder<T, Src, Dst>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Zero + One + Trig,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl <T, Src, Dst> Default for Transform2D<T, Src, Dst>
     where T: Zero + One
 {
@@ -613,6 +990,22 @@ impl <T, Src, Dst> Default for Transform2D<T, Src, Dst>
     }
 }
 
+/// Interpolates the decomposed transforms, see `DecomposedTransform2D::lerp`, or the
+/// terms of the matrices if one of them is not invertible.
+impl<T: Float + FloatConst, Src, Dst> Interpolate<T> for Transform2D<T, Src, Dst> {
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        if let (Some(from), Some(to)) = (self.decompose(), other.decompose()) {
+            return Transform2D::recompose(&from.lerp(&to, t));
+        }
+
+        let mut array = self.to_array();
+        for (a, b) in array.iter_mut().zip(other.to_array().iter()) {
+            *a = *a + (*b - *a) * t;
+        }
+        Transform2D::from_array(array)
+    }
+}
+
 impl<T: ApproxEq<T>, Src, Dst> ApproxEq<T> for Transform2D<T, Src, Dst> {
     #[inline]
     fn approx_epsilon() -> T { T::approx_epsilon() }
@@ -639,6 +1032,90 @@ where T: Copy + fmt::Debug +
     }
 }
 
+/// Displays the transform as a grid of three rows and two columns, noting whether it
+/// is the identity or a translation. The precision, if any, applies to all terms.
+///
+/// ```rust
+/// use euclid::default::Transform2D;
+///
+/// let m = Transform2D::translation(10.0, -2.5);
+/// assert_eq!(format!("{:.1}", m), "[  1.0,  0.0 ]\n[  0.0,  1.0 ]\n[ 10.0, -2.5 ] (translation)");
+/// ```
+impl<T, Src, Dst> fmt::Display for Transform2D<T, Src, Dst>
+where T: Copy + fmt::Display +
+         PartialEq +
+         One + Zero {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (zero, one): (T, T) = (Zero::zero(), One::one());
+        let note = if self.is_identity() {
+            Some("identity")
+        } else if self.m11 == one && self.m12 == zero && self.m21 == zero && self.m22 == one {
+            Some("translation")
+        } else {
+            None
+        };
+
+        write_grid(f, &self.to_array(), 2, note)
+    }
+}
+
+/// The order in which graphics backends store the six coefficients of a 2d affine
+/// transform.
+///
+/// The coefficients are named after the CSS `matrix(a, b, c, d, e, f)` function,
+/// where `a = m11`, `b = m12`, `c = m21`, `d = m22`, `e = m31` and `f = m32`.
+#[cfg(feature = "affine")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AffineLayout {
+    /// `[a, b, c, d, e, f]`, used by CSS, canvas, Cairo's `cairo_matrix_t` and
+    /// Azure's `Matrix`. This is the order of `Transform2D::to_array`.
+    Abcdef,
+    /// `[a, c, e, b, d, f]`, the row-major layout used by Skia's `SkMatrix`.
+    /// This is the order of `Transform2D::to_array_transposed`.
+    Acebdf,
+}
+
+#[cfg(feature = "affine")]
+impl<T: Copy, Src, Dst> Transform2D<T, Src, Dst> {
+    /// Returns the six coefficients of this transform in the given backend layout.
+    #[inline]
+    pub fn to_affine_array(&self, layout: AffineLayout) -> [T; 6] {
+        match layout {
+            AffineLayout::Abcdef => self.to_array(),
+            AffineLayout::Acebdf => self.to_array_transposed(),
+        }
+    }
+
+    /// Creates a transform from six coefficients stored in the given backend layout.
+    #[inline]
+    pub fn from_affine_array(array: [T; 6], layout: AffineLayout) -> Self {
+        match layout {
+            AffineLayout::Abcdef => Self::from_array(array),
+            AffineLayout::Acebdf => Self::new(
+                array[0], array[3],
+                array[1], array[4],
+                array[2], array[5],
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "affine")]
+impl<T: Copy, Src, Dst> From<[T; 6]> for Transform2D<T, Src, Dst> {
+    /// Creates a transform from coefficients in the `[a, b, c, d, e, f]` layout.
+    fn from(array: [T; 6]) -> Self {
+        Self::from_affine_array(array, AffineLayout::Abcdef)
+    }
+}
+
+#[cfg(feature = "affine")]
+impl<T: Copy, Src, Dst> From<Transform2D<T, Src, Dst>> for [T; 6] {
+    /// Returns the coefficients in the `[a, b, c, d, e, f]` layout.
+    fn from(m: Transform2D<T, Src, Dst>) -> Self {
+        m.to_affine_array(AffineLayout::Abcdef)
+    }
+}
+
 #[cfg(feature = "mint")]
 impl<T, Src, Dst> From<mint::RowMatrix3x2<T>> for Transform2D<T, Src, Dst> {
     fn from(m: mint::RowMatrix3x2<T>) -> Self {
@@ -676,6 +1153,87 @@ mod test {
 
     fn rad(v: f32) -> Angle<f32> { Angle::radians(v) }
 
+    #[cfg(feature = "quickcheck")]
+    quickcheck::quickcheck! {
+        fn prop_then_identity(m: default::Transform2D<f64>) -> bool {
+            let identity = default::Transform2D::identity();
+            !m.to_array().iter().all(|v| v.is_finite())
+                || (m.then(&identity) == m && identity.then(&m) == m)
+        }
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    pub fn test_quickcheck_shrink() {
+        use quickcheck::Arbitrary;
+
+        assert!(default::Transform2D::<f64>::identity().shrink().next().is_none());
+        let m = default::Transform2D::new(1.0, 0.0, 0.0, 1.0, 5.0, 0.0);
+        assert!(m.shrink().all(|s| s.m31.abs() < 5.0 && s.m11 == 1.0));
+    }
+
+    /// A Q16.16 fixed-point scalar, with the minimal set of traits transforms need.
+    mod fixed {
+        use crate::Trig;
+        use core::ops::{Add, Div, Mul, Neg, Sub};
+
+        #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+        pub struct Q16(pub i32);
+
+        impl Q16 {
+            pub fn from_f64(v: f64) -> Self { Q16((v * 65536.0).round() as i32) }
+            pub fn to_f64(self) -> f64 { self.0 as f64 / 65536.0 }
+        }
+
+        impl Add for Q16 { type Output = Q16; fn add(self, o: Q16) -> Q16 { Q16(self.0 + o.0) } }
+        impl Sub for Q16 { type Output = Q16; fn sub(self, o: Q16) -> Q16 { Q16(self.0 - o.0) } }
+        impl Neg for Q16 { type Output = Q16; fn neg(self) -> Q16 { Q16(-self.0) } }
+        impl Mul for Q16 {
+            type Output = Q16;
+            fn mul(self, o: Q16) -> Q16 { Q16(((self.0 as i64 * o.0 as i64) >> 16) as i32) }
+        }
+        impl Div for Q16 {
+            type Output = Q16;
+            fn div(self, o: Q16) -> Q16 { Q16((((self.0 as i64) << 16) / o.0 as i64) as i32) }
+        }
+        impl num_traits::Zero for Q16 {
+            fn zero() -> Q16 { Q16(0) }
+            fn is_zero(&self) -> bool { self.0 == 0 }
+        }
+        impl num_traits::One for Q16 {
+            fn one() -> Q16 { Q16(1 << 16) }
+        }
+        impl Trig for Q16 {
+            fn sin(self) -> Q16 { Q16::from_f64(self.to_f64().sin()) }
+            fn cos(self) -> Q16 { Q16::from_f64(self.to_f64().cos()) }
+            fn tan(self) -> Q16 { Q16::from_f64(self.to_f64().tan()) }
+            fn fast_atan2(y: Q16, x: Q16) -> Q16 { Q16::from_f64(y.to_f64().atan2(x.to_f64())) }
+            fn degrees_to_radians(deg: Q16) -> Q16 { Q16::from_f64(deg.to_f64().to_radians()) }
+            fn radians_to_degrees(rad: Q16) -> Q16 { Q16::from_f64(rad.to_f64().to_degrees()) }
+        }
+    }
+
+    #[test]
+    pub fn test_fixed_point() {
+        use self::fixed::Q16;
+
+        let q = |v: f64| Q16::from_f64(v);
+        let m: default::Transform2D<Q16> = Transform2D::scale(q(2.0), q(2.0))
+            .then_rotate(Angle::degrees(q(90.0)))
+            .then_translate(vec2(q(10.0), q(0.0)));
+
+        let p = m.transform_point(point2(q(1.0), q(0.5)));
+        assert!((p.x.to_f64() - 9.0).abs() < 1e-3);
+        assert!((p.y.to_f64() - 2.0).abs() < 1e-3);
+
+        let back = m.inverse().unwrap().transform_point(p);
+        assert!((back.x.to_f64() - 1.0).abs() < 1e-3);
+        assert!((back.y.to_f64() - 0.5).abs() < 1e-3);
+
+        let skew: default::Transform2D<Q16> = Transform2D::skew(Angle::zero(), Angle::zero());
+        assert_eq!(skew, Transform2D::identity());
+    }
+
     #[test]
     pub fn test_translation() {
         let t1 = Mat::translation(1.0, 2.0);
@@ -689,6 +1247,15 @@ mod test {
         assert_eq!(t1.then(&t1), Mat::translation(2.0, 4.0));
     }
 
+    #[test]
+    pub fn test_cast() {
+        let m: default::Transform2D<f64> = default::Transform2D::new(1.5, 0.0, 0.0, 2.0, 10.25, -3.0);
+        assert_eq!(m.to_f32(), Mat::new(1.5, 0.0, 0.0, 2.0, 10.25, -3.0));
+        assert_eq!(m.to_f32().to_f64(), m);
+        assert_eq!(m.cast::<i32>(), default::Transform2D::new(1, 0, 0, 2, 10, -3));
+        assert!(default::Transform2D::new(f64::NAN, 0.0, 0.0, 1.0, 0.0, 0.0).try_cast::<i32>().is_none());
+    }
+
     #[test]
     pub fn test_rotation() {
         let r1 = Mat::rotation(rad(FRAC_PI_2));
@@ -781,6 +1348,127 @@ mod test {
         assert!(!m2.is_identity());
     }
 
+    #[test]
+    pub fn test_const_identity() {
+        const IDENTITY: Mat = Mat::IDENTITY;
+        assert_eq!(IDENTITY, Mat::identity());
+        assert_eq!(default::Transform2D::<i32>::IDENTITY, default::Transform2D::identity());
+    }
+
+    #[test]
+    pub fn test_decompose() {
+        let shear = Mat::new(1.0, 0.0, 0.5, 1.0, 0.0, 0.0);
+        let m = Mat::scale(2.0, 3.0)
+            .then(&shear)
+            .then_rotate(rad(FRAC_PI_2))
+            .then_translate(vec2(10.0, 20.0));
+
+        let d = m.decompose().unwrap();
+        assert!(d.scale.approx_eq(&vec2(2.0, 3.0)));
+        assert!(d.shear.approx_eq(&0.5));
+        assert!(d.rotation.get().approx_eq(&FRAC_PI_2));
+        assert_eq!(d.translation, vec2(10.0, 20.0));
+        assert!(Mat::recompose(&d).approx_eq(&m));
+
+        // Reflections are represented with a negative vertical scale.
+        let flip = Mat::scale(-1.0, 1.0);
+        let d = flip.decompose().unwrap();
+        assert!(d.scale.approx_eq(&vec2(1.0, -1.0)));
+        assert!(Mat::recompose(&d).approx_eq(&flip));
+
+        assert!(Mat::scale(0.0, 1.0).decompose().is_none());
+    }
+
+    #[test]
+    pub fn test_flip_y() {
+        let flip = Mat::flip_y(100.0);
+        assert_eq!(flip.then(&flip), Mat::identity());
+        assert_eq!(flip.transform_point(point2(1.0, 20.0)), point2(1.0, 80.0));
+        assert_eq!(flip.to_3d(), default::Transform3D::flip_y(100.0));
+    }
+
+    #[test]
+    pub fn test_skew() {
+        let alpha = Angle::degrees(30.0);
+        let beta = Angle::degrees(-15.0);
+        let m = Mat::skew(alpha, beta);
+        assert_eq!(m.to_3d(), default::Transform3D::skew(alpha, beta));
+        assert_eq!(Mat::skew(Angle::zero(), Angle::zero()), Mat::identity());
+    }
+
+    #[test]
+    pub fn test_builder() {
+        let built: Mat = Transform2DBuilder::new()
+            .scale(2.0, 3.0)
+            .rotate(rad(FRAC_PI_2))
+            .translate(1.0, 2.0)
+            .build();
+        let chained = Mat::scale(2.0, 3.0)
+            .then_rotate(rad(FRAC_PI_2))
+            .then_translate(vec2(1.0, 2.0));
+        assert_eq!(built, chained);
+
+        let built: Mat = Transform2DBuilder::new().then(&chained).build();
+        assert_eq!(built, chained);
+    }
+
+    #[test]
+    pub fn test_decomposed_lerp() {
+        let a = Mat::identity().decompose().unwrap();
+        let b = Mat::rotation(rad(FRAC_PI_2)).then_scale(3.0, 3.0).decompose().unwrap();
+        let half = Mat::recompose(&a.lerp(&b, 0.5));
+        let expected = Mat::rotation(rad(FRAC_PI_2 / 2.0)).then_scale(2.0, 2.0);
+        assert!(half.approx_eq(&expected));
+    }
+
+    #[test]
+    pub fn test_interpolate_from_identity() {
+        let m = Mat::scale(4.0, 9.0).then_rotate(rad(FRAC_PI_2)).then_translate(vec2(2.0, 4.0));
+        assert!(m.interpolate_from_identity(0.0).unwrap().approx_eq(&Mat::identity()));
+        assert!(m.interpolate_from_identity(1.0).unwrap().approx_eq(&m));
+
+        let expected = Mat::scale(2.5, 5.0).then_rotate(rad(FRAC_PI_2 / 2.0)).then_translate(vec2(1.0, 2.0));
+        assert!(m.interpolate_from_identity(0.5).unwrap().approx_eq(&expected));
+
+        assert!(Mat::scale(0.0, 1.0).interpolate_from_identity(0.5).is_none());
+    }
+
+    #[test]
+    pub fn test_row_major_3x3() {
+        let m = Mat::scale(2.0, 3.0).then_translate(vec2(4.0, 5.0));
+        let a = m.to_row_major_3x3();
+        assert_eq!(a, [2.0, 0.0, 4.0, 0.0, 3.0, 5.0, 0.0, 0.0, 1.0]);
+
+        // Multiplying the matrix with a column vector transforms the point.
+        let p = point2(1.0, 1.0);
+        let x = a[0] * p.x + a[1] * p.y + a[2];
+        let y = a[3] * p.x + a[4] * p.y + a[5];
+        assert_eq!(point2(x, y), m.transform_point(p));
+        assert_eq!(Mat::from_array(m.to_array()), m);
+    }
+
+    #[test]
+    pub fn test_interpolate() {
+        use crate::interpolate::Interpolate;
+
+        let a = Mat::translation(2.0, 0.0);
+        let b = Mat::rotation(rad(FRAC_PI_2)).then_translate(vec2(4.0, 2.0));
+        let expected = Mat::rotation(rad(FRAC_PI_2 / 2.0)).then_translate(vec2(3.0, 1.0));
+        assert!(a.interpolate(&b, 0.5).approx_eq(&expected));
+
+        // Singular transforms are interpolated term by term.
+        let flat = Mat::scale(0.0, 2.0);
+        assert_eq!(flat.interpolate(&Mat::identity(), 0.5), Mat::scale(0.5, 1.5));
+    }
+
+    #[test]
+    pub fn test_extract_scale() {
+        let m = Mat::scale(2.0, 0.5).then_rotate(rad(FRAC_PI_2)).then_translate(vec2(3.0, 4.0));
+        assert!(m.extract_scale().approx_eq(&vec2(2.0, 0.5)));
+        assert_eq!(Mat::identity().extract_scale(), vec2(1.0, 1.0));
+        assert_eq!(Mat::scale(0.0, 2.0).extract_scale(), vec2(0.0, 2.0));
+    }
+
     #[test]
     pub fn test_transform_vector() {
         // Translation does not apply to vectors.
@@ -789,6 +1477,33 @@ mod test {
         assert_eq!(v1, m1.transform_vector(v1));
     }
 
+    #[test]
+    pub fn test_transform_points() {
+        let m = Mat::rotation(rad(0.5)).then_scale(2.0, 3.0).then_translate(vec2(1.0, -1.0));
+        let points = [point2(0.0, 0.0), point2(1.5, -2.0), point2(100.0, 0.25)];
+        let mut transformed = points;
+        m.transform_points(&mut transformed);
+        for (p, t) in points.iter().zip(transformed.iter()) {
+            assert_eq!(m.transform_point(*p), *t);
+        }
+    }
+
+    #[cfg(feature = "affine")]
+    #[test]
+    pub fn test_affine_array() {
+        let m = Mat::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+
+        assert_eq!(m.to_affine_array(AffineLayout::Abcdef), [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(m.to_affine_array(AffineLayout::Acebdf), [1.0, 3.0, 5.0, 2.0, 4.0, 6.0]);
+
+        for &layout in &[AffineLayout::Abcdef, AffineLayout::Acebdf] {
+            assert_eq!(Mat::from_affine_array(m.to_affine_array(layout), layout), m);
+        }
+
+        let a: [f32; 6] = m.into();
+        assert_eq!(Mat::from(a), m);
+    }
+
     #[cfg(feature = "mint")]
     #[test]
     pub fn test_mint() {
@@ -11,6 +11,7 @@
 
 use super::{UnknownUnit, Angle};
 use crate::approxeq::ApproxEq;
+use crate::interpolate::Interpolate;
 use crate::homogen::HomogeneousVector;
 #[cfg(feature = "mint")]
 use mint;
@@ -20,7 +21,9 @@ use crate::vector::{Vector2D, Vector3D, vec2, vec3};
 use crate::rect::Rect;
 use crate::box2d::Box2D;
 use crate::box3d::Box3D;
+use crate::display::write_grid;
 use crate::transform2d::Transform2D;
+use crate::rotation::Rotation3D;
 use crate::scale::Scale;
 use crate::num::{One, Zero};
 use core::ops::{Add, Mul, Sub, Div, Neg};
@@ -28,7 +31,7 @@ use core::marker::PhantomData;
 use core::fmt;
 use core::cmp::{Eq, PartialEq};
 use core::hash::{Hash};
-use num_traits::NumCast;
+use num_traits::{Float, NumCast};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -105,6 +108,40 @@ where
     }
 }
 
+/// Shrinks each term towards the identity transform.
+#[cfg(feature = "quickcheck")]
+impl<T, Src, Dst> quickcheck::Arbitrary for Transform3D<T, Src, Dst>
+where
+    T: quickcheck::Arbitrary + num_traits::Float,
+    Src: 'static,
+    Dst: 'static,
+{
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let (m11, m12, m13, m14) = quickcheck::Arbitrary::arbitrary(g);
+        let (m21, m22, m23, m24) = quickcheck::Arbitrary::arbitrary(g);
+        let (m31, m32, m33, m34) = quickcheck::Arbitrary::arbitrary(g);
+        let (m41, m42, m43, m44) = quickcheck::Arbitrary::arbitrary(g);
+        Transform3D::new(
+            m11, m12, m13, m14,
+            m21, m22, m23, m24,
+            m31, m32, m33, m34,
+            m41, m42, m43, m44,
+        )
+    }
+
+    fn shrink(&self) -> alloc::boxed::Box<dyn Iterator<Item = Self>> {
+        let identity = Self::identity().to_array();
+        let array = self.to_array();
+        alloc::boxed::Box::new((0..16).flat_map(move |i| {
+            quickcheck::Arbitrary::shrink(&(array[i] - identity[i])).map(move |delta| {
+                let mut shrunk = array;
+                shrunk[i] = identity[i] + delta;
+                Transform3D::from_array(shrunk)
+            })
+        }))
+    }
+}
+
 impl<T: Copy, Src, Dst> Copy for Transform3D<T, Src, Dst> {}
 
 impl<T: Clone, Src, Dst> Clone for Transform3D<T, Src, Dst> {
@@ -227,6 +264,18 @@ impl<T, Src, Dst> Transform3D<T, Src, Dst> {
     ///   0    0   1   0
     /// m41  m42   0   1
     /// ```
+    ///
+    /// The parameters are in the same order as the ones of the CSS `matrix(a, b, c, d, e, f)`
+    /// function, see <https://drafts.csswg.org/css-transforms/#funcdef-transform-matrix>.
+    ///
+    /// ```rust
+    /// use euclid::point2;
+    /// use euclid::default::Transform3D;
+    ///
+    /// // matrix(2, 0, 0, 3, 10, 20)
+    /// let m = Transform3D::new_2d(2.0, 0.0, 0.0, 3.0, 10.0, 20.0);
+    /// assert_eq!(m.transform_point2d(point2(1.0, 1.0)), Some(point2(12.0, 23.0)));
+    /// ```
     #[inline]
     pub fn new_2d(m11: T, m12: T, m21: T, m22: T, m41: T, m42: T) -> Self
     where
@@ -487,6 +536,27 @@ where
 }
 
 
+macro_rules! transform3d_identity {
+    ($ty:ty, $_0:expr, $_1:expr) => {
+        impl<Src, Dst> Transform3D<$ty, Src, Dst> {
+            /// The identity transform, usable in constant expressions.
+            ///
+            /// Equivalent to [`identity()`](#method.identity).
+            pub const IDENTITY: Self = Transform3D::new(
+                $_1, $_0, $_0, $_0,
+                $_0, $_1, $_0, $_0,
+                $_0, $_0, $_1, $_0,
+                $_0, $_0, $_0, $_1,
+            );
+        }
+    };
+}
+
+transform3d_identity!(f32, 0.0, 1.0);
+transform3d_identity!(f64, 0.0, 1.0);
+transform3d_identity!(i32, 0, 1);
+transform3d_identity!(i64, 0, 1);
+
 /// Methods for combining generic transformations
 impl <T, Src, Dst> Transform3D<T, Src, Dst>
 where
@@ -573,7 +643,8 @@ where
     T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T> + Zero + One + Trig,
 {
     /// Create a 3d rotation transform from an angle / axis.
-    /// The supplied axis must be normalized.
+    /// The supplied axis must be normalized, see `try_rotation` otherwise. The result
+    /// for other axes is not a rotation, and is not checked for.
     pub fn rotation(x: T, y: T, z: T, theta: Angle<T>) -> Self {
         let (_0, _1): (T, T) = (Zero::zero(), One::one());
         let _2 = _1 + _1;
@@ -649,6 +720,45 @@ where
         )
     }
 
+    /// Create a transform flipping the y axis within a viewport of the given height:
+    ///
+    /// ```text
+    /// 1  0      0 0
+    /// 0 -1      0 0
+    /// 0  0      1 0
+    /// 0  height 0 1
+    /// ```
+    ///
+    /// This converts between y-down coordinates, as used by web content, and y-up
+    /// coordinates, as used by OpenGL framebuffers. The transform is its own inverse,
+    /// so it works in both directions. A height of zero mirrors around the x axis,
+    /// which converts between y-down normalized device coordinates and the y-up clip
+    /// space of OpenGL.
+    ///
+    /// ```rust
+    /// use euclid::point2;
+    /// use euclid::default::Transform3D;
+    ///
+    /// let flip = Transform3D::flip_y(600.0);
+    /// assert_eq!(flip.transform_point2d(point2(10.0, 0.0)), Some(point2(10.0, 600.0)));
+    /// assert_eq!(flip.transform_point2d(point2(10.0, 450.0)), Some(point2(10.0, 150.0)));
+    /// ```
+    #[inline]
+    pub fn flip_y(height: T) -> Self
+    where
+        T: Neg<Output = T>,
+    {
+        let zero = || T::zero();
+        let one = || T::one();
+
+        Self::new(
+            one(),  zero(),  zero(), zero(),
+            zero(), -one(),  zero(), zero(),
+            zero(), zero(),  one(),  zero(),
+            zero(), height,  zero(), one(),
+        )
+    }
+
     /// Returns a transform with a scale applied before self's transformation.
     #[must_use]
     pub fn pre_scale(&self, x: T, y: T, z: T) -> Self
@@ -716,6 +826,9 @@ where
 
     /// Returns the given 2d vector transformed by this matrix.
     ///
+    /// Unlike `transform_point2d`, this ignores the translation and the perspective
+    /// components, which is what directions and offsets need.
+    ///
     /// The input point must be use the unit Src, and the returned point has the unit Dst.
     #[inline]
     pub fn transform_vector2d(&self, v: Vector2D<T, Src>) -> Vector2D<T, Dst> {
@@ -752,8 +865,30 @@ where
         self.transform_point3d_homogeneous(p).to_point3d()
     }
 
+    /// Returns the given homogeneous vector transformed by this transform.
+    ///
+    /// Unlike `transform_point3d`, this does not perform the perspective divide, which
+    /// lets the result go through further transforms before being converted back to a
+    /// point with `HomogeneousVector::to_point3d`, once, at the end.
+    ///
+    /// The input vector must be use the unit Src, and the returned vector has the unit Dst.
+    #[inline]
+    pub fn transform_homogeneous(
+        &self, v: HomogeneousVector<T, Src>
+    ) -> HomogeneousVector<T, Dst> {
+        let x = v.x * self.m11 + v.y * self.m21 + v.z * self.m31 + v.w * self.m41;
+        let y = v.x * self.m12 + v.y * self.m22 + v.z * self.m32 + v.w * self.m42;
+        let z = v.x * self.m13 + v.y * self.m23 + v.z * self.m33 + v.w * self.m43;
+        let w = v.x * self.m14 + v.y * self.m24 + v.z * self.m34 + v.w * self.m44;
+
+        HomogeneousVector::new(x, y, z, w)
+    }
+
     /// Returns the given 3d vector transformed by this matrix.
     ///
+    /// Unlike `transform_point3d`, this ignores the translation and the perspective
+    /// components, which is what directions and offsets need.
+    ///
     /// The input point must be use the unit Src, and the returned point has the unit Dst.
     #[inline]
     pub fn transform_vector3d(&self, v: Vector3D<T, Src>) -> Vector3D<T, Dst> {
@@ -813,6 +948,21 @@ where
     }
 }
 
+impl<T, U> Transform3D<T, U, U>
+where
+    T: Copy + Add<Output = T> + Mul<Output = T>,
+{
+    /// Transforms each homogeneous vector of the slice in place.
+    ///
+    /// The result is the same as calling `transform_homogeneous` on each vector, in a
+    /// loop that the compiler can vectorize. The transform must not change the unit.
+    pub fn transform_homogeneous_slice(&self, vectors: &mut [HomogeneousVector<T, U>]) {
+        for v in vectors.iter_mut() {
+            *v = self.transform_homogeneous(*v);
+        }
+    }
+}
+
 
 impl <T, Src, Dst> Transform3D<T, Src, Dst>
 where T: Copy +
@@ -825,6 +975,9 @@ where T: Copy +
          One + Zero {
 
     /// Create an orthogonal projection transform.
+    ///
+    /// This follows the OpenGL conventions: the view space is right handed (looking
+    /// towards negative z) and the depth is mapped to the `-1..1` range.
     pub fn ortho(left: T, right: T,
                  bottom: T, top: T,
                  near: T, far: T) -> Self {
@@ -842,6 +995,127 @@ where T: Copy +
         )
     }
 
+    /// Create an orthogonal projection transform for a left handed view space (looking
+    /// towards positive z), mapping the depth to the `-1..1` range.
+    pub fn ortho_lh(left: T, right: T,
+                    bottom: T, top: T,
+                    near: T, far: T) -> Self {
+        Self::ortho_impl(left, right, bottom, top, near, far, true, false)
+    }
+
+    /// Create an orthogonal projection transform for a right handed view space (looking
+    /// towards negative z), mapping the depth to the `0..1` range as Direct3D, Metal and
+    /// WebGPU expect.
+    pub fn ortho_zo(left: T, right: T,
+                    bottom: T, top: T,
+                    near: T, far: T) -> Self {
+        Self::ortho_impl(left, right, bottom, top, near, far, false, true)
+    }
+
+    /// Create an orthogonal projection transform for a left handed view space (looking
+    /// towards positive z), mapping the depth to the `0..1` range as Direct3D, Metal and
+    /// WebGPU expect.
+    pub fn ortho_lh_zo(left: T, right: T,
+                       bottom: T, top: T,
+                       near: T, far: T) -> Self {
+        Self::ortho_impl(left, right, bottom, top, near, far, true, true)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn ortho_impl(left: T, right: T,
+                  bottom: T, top: T,
+                  near: T, far: T,
+                  left_handed: bool, zero_to_one: bool) -> Self {
+        let (zero, one): (T, T) = (Zero::zero(), One::one());
+        let two = one + one;
+
+        let tx = -((right + left) / (right - left));
+        let ty = -((top + bottom) / (top - bottom));
+        let (sz, tz) = if zero_to_one {
+            (one / (far - near), -(near / (far - near)))
+        } else {
+            (two / (far - near), -((far + near) / (far - near)))
+        };
+        let sz = if left_handed { sz } else { -sz };
+
+        Transform3D::new(
+            two / (right - left), zero                , zero, zero,
+            zero                , two / (top - bottom), zero, zero,
+            zero                , zero                , sz  , zero,
+            tx                  , ty                  , tz  , one
+        )
+    }
+
+    /// Create a perspective projection transform from a vertical field of view and the
+    /// aspect ratio (width divided by height) of the viewport.
+    ///
+    /// This follows the OpenGL conventions: the view space is right handed (looking
+    /// towards negative z) and the depth is mapped to the `-1..1` range.
+    ///
+    /// Unlike `perspective`, which implements the CSS `perspective()` function, this
+    /// is meant to project a 3d scene onto a viewport.
+    pub fn perspective_fov(fov_y: Angle<T>, aspect_ratio: T, near: T, far: T) -> Self
+    where
+        T: Trig,
+    {
+        Self::perspective_fov_impl(fov_y, aspect_ratio, near, far, false, false)
+    }
+
+    /// Create a perspective projection transform for a left handed view space (looking
+    /// towards positive z), mapping the depth to the `-1..1` range.
+    pub fn perspective_fov_lh(fov_y: Angle<T>, aspect_ratio: T, near: T, far: T) -> Self
+    where
+        T: Trig,
+    {
+        Self::perspective_fov_impl(fov_y, aspect_ratio, near, far, true, false)
+    }
+
+    /// Create a perspective projection transform for a right handed view space (looking
+    /// towards negative z), mapping the depth to the `0..1` range as Direct3D, Metal and
+    /// WebGPU expect.
+    pub fn perspective_fov_zo(fov_y: Angle<T>, aspect_ratio: T, near: T, far: T) -> Self
+    where
+        T: Trig,
+    {
+        Self::perspective_fov_impl(fov_y, aspect_ratio, near, far, false, true)
+    }
+
+    /// Create a perspective projection transform for a left handed view space (looking
+    /// towards positive z), mapping the depth to the `0..1` range as Direct3D, Metal and
+    /// WebGPU expect.
+    pub fn perspective_fov_lh_zo(fov_y: Angle<T>, aspect_ratio: T, near: T, far: T) -> Self
+    where
+        T: Trig,
+    {
+        Self::perspective_fov_impl(fov_y, aspect_ratio, near, far, true, true)
+    }
+
+    fn perspective_fov_impl(fov_y: Angle<T>, aspect_ratio: T,
+                            near: T, far: T,
+                            left_handed: bool, zero_to_one: bool) -> Self
+    where
+        T: Trig,
+    {
+        let (zero, one): (T, T) = (Zero::zero(), One::one());
+        let two = one + one;
+
+        let sy = one / (fov_y.radians / two).tan();
+        let sx = sy / aspect_ratio;
+        let (sz, tz) = if zero_to_one {
+            (far / (far - near), -(far * near / (far - near)))
+        } else {
+            ((far + near) / (far - near), -(two * far * near / (far - near)))
+        };
+        let (sz, w) = if left_handed { (sz, one) } else { (-sz, -one) };
+
+        Transform3D::new(
+            sx  , zero, zero, zero,
+            zero, sy  , zero, zero,
+            zero, zero, sz  , w,
+            zero, zero, tz  , zero
+        )
+    }
+
     /// Check whether shapes on the XY plane with Z pointing towards the
     /// screen transformed by this matrix would be facing back.
     pub fn is_backface_visible(&self) -> bool {
@@ -968,6 +1242,44 @@ where T: Copy +
         self.m11 * self.m22 * self.m33 * self.m44
     }
 
+    /// Returns the transform to apply to surface normals, that is the inverse transpose
+    /// of the upper 3x3 block of this transform, or `None` if that block is not invertible.
+    ///
+    /// Unlike transforming them with this transform, normals transformed with
+    /// `transform_vector3d` of the normal matrix stay perpendicular to the transformed
+    /// surfaces under non-uniform scales and shears. The result is not normalized.
+    pub fn to_normal_matrix(&self) -> Option<Self> {
+        let c11 = self.m22 * self.m33 - self.m23 * self.m32;
+        let c12 = self.m23 * self.m31 - self.m21 * self.m33;
+        let c13 = self.m21 * self.m32 - self.m22 * self.m31;
+        let det = self.m11 * c11 + self.m12 * c12 + self.m13 * c13;
+
+        if det == Zero::zero() {
+            return None;
+        }
+
+        let zero = T::zero();
+        let inv_det = T::one() / det;
+        Some(Transform3D::new(
+            c11 * inv_det,
+            c12 * inv_det,
+            c13 * inv_det,
+            zero,
+
+            (self.m13 * self.m32 - self.m12 * self.m33) * inv_det,
+            (self.m11 * self.m33 - self.m13 * self.m31) * inv_det,
+            (self.m12 * self.m31 - self.m11 * self.m32) * inv_det,
+            zero,
+
+            (self.m12 * self.m23 - self.m13 * self.m22) * inv_det,
+            (self.m13 * self.m21 - self.m11 * self.m23) * inv_det,
+            (self.m11 * self.m22 - self.m12 * self.m21) * inv_det,
+            zero,
+
+            zero, zero, zero, T::one(),
+        ))
+    }
+
     /// Multiplies all of the transform's component by a scalar and returns the result.
     #[must_use]
     pub fn mul_s(&self, x: T) -> Self {
@@ -1026,6 +1338,278 @@ where
     }
 }
 
+/// The components of an affine 3d transform, as returned by `Transform3D::decompose`.
+///
+/// The transform is obtained by applying, in order, the scale, the shear, the rotation
+/// and the translation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))
+)]
+pub struct DecomposedTransform3D<T> {
+    pub translation: Vector3D<T, UnknownUnit>,
+    pub rotation: Rotation3D<T, UnknownUnit, UnknownUnit>,
+    pub scale: Vector3D<T, UnknownUnit>,
+    /// The `xy`, `xz` and `yz` shear factors, such that `x` becomes
+    /// `x + shear.x * y + shear.y * z` and `y` becomes `y + shear.z * z`.
+    pub shear: Vector3D<T, UnknownUnit>,
+}
+
+impl<T: Float + ApproxEq<T>> DecomposedTransform3D<T> {
+    /// Interpolates each component, the rotation using a spherical interpolation.
+    ///
+    /// This is how 3d transforms are usually animated, since interpolating the terms
+    /// of the matrices directly doesn't preserve the shapes.
+    pub fn lerp(&self, other: &Self, t: T) -> Self {
+        DecomposedTransform3D {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(&other.rotation, t),
+            scale: self.scale.lerp(other.scale, t),
+            shear: self.shear.lerp(other.shear, t),
+        }
+    }
+}
+
+impl<T: Float, Src, Dst> Transform3D<T, Src, Dst> {
+    /// Returns the factors by which the x, y and z axes are scaled, which are the
+    /// lengths of the images of the unit vectors (the first three rows of the upper 3x3
+    /// block).
+    ///
+    /// The factors are always positive and the perspective components are ignored.
+    /// This is useful to pick the resolution at which to rasterize transformed content.
+    ///
+    /// ```rust
+    /// use euclid::default::Transform3D;
+    /// use euclid::vec3;
+    ///
+    /// let m = Transform3D::scale(2.0, -3.0, 4.0).then_translate(vec3(5.0, 5.0, 5.0));
+    /// assert_eq!(m.extract_scale(), vec3(2.0, 3.0, 4.0));
+    /// ```
+    pub fn extract_scale(&self) -> Vector3D<T, UnknownUnit> {
+        vec3(
+            vec3::<T, UnknownUnit>(self.m11, self.m12, self.m13).length(),
+            vec3::<T, UnknownUnit>(self.m21, self.m22, self.m23).length(),
+            vec3::<T, UnknownUnit>(self.m31, self.m32, self.m33).length(),
+        )
+    }
+
+    /// Returns the largest factor by which the transform can stretch 2d content in the
+    /// plane `z = 0`, which is the largest singular value of the upper 2x2 block divided
+    /// by `m44`.
+    ///
+    /// The result is rounded up by a few units in the last place so that it bounds the
+    /// length of `transform_vector2d` of any unit vector. With a perspective projection,
+    /// that is if `m14` or `m24` is not zero, the stretching depends on the position of
+    /// the content and infinity is returned. Rasterizing content, for example glyphs, at
+    /// this scale avoids blurriness in all directions.
+    ///
+    /// ```rust
+    /// use euclid::default::Transform3D;
+    /// use euclid::Angle;
+    ///
+    /// let m = Transform3D::scale(2.0, 3.0, 1.0).then_rotate(0.0, 0.0, 1.0, Angle::degrees(30.0));
+    /// let scale: f32 = m.max_2d_scale();
+    /// assert!((scale - 3.0).abs() < 1e-5);
+    /// ```
+    pub fn max_2d_scale(&self) -> T {
+        if self.m14 != T::zero() || self.m24 != T::zero() {
+            return T::infinity();
+        }
+
+        // Splitting the block into a similarity and an anti-similarity, the largest
+        // singular value is the sum of their scales. Unlike the eigenvalues of the block
+        // times its transpose, this doesn't suffer from cancellation.
+        let two = T::one() + T::one();
+        let e = (self.m11 + self.m22) / two;
+        let f = (self.m11 - self.m22) / two;
+        let g = (self.m12 + self.m21) / two;
+        let h = (self.m12 - self.m21) / two;
+        let scale = (e.hypot(h) + f.hypot(g)) / self.m44.abs();
+
+        // Each of the operations above is off by at most half a unit in the last place.
+        scale + scale * T::epsilon() * two * two
+    }
+
+    /// Returns this transform with its upper 3x3 block made orthonormal using the
+    /// Gram-Schmidt process, the image of the x axis keeping its direction.
+    ///
+    /// Composing many incremental rotations accumulates rounding errors which slowly
+    /// introduce shear; orthonormalizing the result from time to time keeps it a pure
+    /// rotation. Any scale is removed, while the translation and perspective terms are
+    /// left untouched.
+    ///
+    /// Returns `None` if the upper 3x3 block is singular, or so close to it that the rows
+    /// are only independent because of rounding errors, since no rotation follows from it.
+    pub fn orthonormalize(&self) -> Option<Self> {
+        let x: Vector3D<T, UnknownUnit> = vec3(self.m11, self.m12, self.m13);
+        let y: Vector3D<T, UnknownUnit> = vec3(self.m21, self.m22, self.m23);
+        let z: Vector3D<T, UnknownUnit> = vec3(self.m31, self.m32, self.m33);
+
+        // Normalizes the part of `v` that is not `projection`, if it is larger than the
+        // rounding errors of the subtraction.
+        let sixteen = T::from(16.0).unwrap();
+        let residual = |v: Vector3D<T, UnknownUnit>, projection: Vector3D<T, UnknownUnit>| {
+            let r = v - projection;
+            let length = r.length();
+            if length > v.length() * T::epsilon() * sixteen {
+                Some(r / length)
+            } else {
+                None
+            }
+        };
+
+        let x = residual(x, Vector3D::zero())?;
+        let y = residual(y, x * x.dot(y))?;
+        let z = residual(z, x * x.dot(z) + y * y.dot(z))?;
+
+        Some(Transform3D::new(
+            x.x, x.y, x.z, self.m14,
+            y.x, y.y, y.z, self.m24,
+            z.x, z.y, z.z, self.m34,
+            self.m41, self.m42, self.m43, self.m44,
+        ))
+    }
+
+    /// Splits this transform into a scale, a shear, a rotation and a translation, or
+    /// returns `None` if it is not invertible or has perspective components.
+    ///
+    /// A negative determinant is represented with a negative z scale.
+    ///
+    /// ```rust
+    /// use euclid::default::Transform3D;
+    /// use euclid::approxeq::ApproxEq;
+    /// use euclid::{vec3, Angle};
+    ///
+    /// let m = Transform3D::scale(2.0, 3.0, 4.0)
+    ///     .then_rotate(1.0, 0.0, 0.0, Angle::degrees(30.0))
+    ///     .then_translate(vec3(1.0, 2.0, 3.0));
+    /// let d = m.decompose().unwrap();
+    /// assert!(d.scale.approx_eq(&vec3(2.0, 3.0, 4.0)));
+    /// assert_eq!(d.translation, vec3(1.0, 2.0, 3.0));
+    /// assert!(Transform3D::recompose(&d).approx_eq(&m));
+    /// ```
+    pub fn decompose(&self) -> Option<DecomposedTransform3D<T>>
+    where
+        T: ApproxEq<T>,
+    {
+        if self.m14 != T::zero() || self.m24 != T::zero() || self.m34 != T::zero()
+            || self.m44 != T::one() {
+            return None;
+        }
+
+        let row1: Vector3D<T, UnknownUnit> = vec3(self.m11, self.m12, self.m13);
+        let row2: Vector3D<T, UnknownUnit> = vec3(self.m21, self.m22, self.m23);
+        let row3: Vector3D<T, UnknownUnit> = vec3(self.m31, self.m32, self.m33);
+
+        // Gram-Schmidt, the projections being the shear factors.
+        let scale_x = row1.length();
+        if scale_x == T::zero() {
+            return None;
+        }
+        let x = row1 / scale_x;
+
+        let shear_xy = x.dot(row2);
+        let y = row2 - x * shear_xy;
+        let scale_y = y.length();
+        if scale_y == T::zero() {
+            return None;
+        }
+        let y = y / scale_y;
+
+        let (shear_xz, shear_yz) = (x.dot(row3), y.dot(row3));
+        let z = row3 - x * shear_xz - y * shear_yz;
+        let mut scale_z = z.length();
+        if scale_z == T::zero() {
+            return None;
+        }
+        if x.cross(y).dot(z) < T::zero() {
+            scale_z = -scale_z;
+        }
+        let z = z / scale_z;
+
+        Some(DecomposedTransform3D {
+            translation: vec3(self.m41, self.m42, self.m43),
+            rotation: rotation_from_basis(x, y, z),
+            scale: vec3(scale_x, scale_y, scale_z),
+            shear: vec3(shear_xy / scale_y, shear_xz / scale_z, shear_yz / scale_z),
+        })
+    }
+
+    /// Builds a transform from its components, the inverse of `decompose`.
+    pub fn recompose(d: &DecomposedTransform3D<T>) -> Self
+    where
+        T: ApproxEq<T>,
+    {
+        let r = d.rotation.to_transform();
+        let x: Vector3D<T, UnknownUnit> = vec3(r.m11, r.m12, r.m13);
+        let y: Vector3D<T, UnknownUnit> = vec3(r.m21, r.m22, r.m23);
+        let z: Vector3D<T, UnknownUnit> = vec3(r.m31, r.m32, r.m33);
+
+        let row1 = x * d.scale.x;
+        let row2 = (x * d.shear.x + y) * d.scale.y;
+        let row3 = (x * d.shear.y + y * d.shear.z + z) * d.scale.z;
+        let (zero, one) = (T::zero(), T::one());
+
+        Transform3D::new(
+            row1.x, row1.y, row1.z, zero,
+            row2.x, row2.y, row2.z, zero,
+            row3.x, row3.y, row3.z, zero,
+            d.translation.x, d.translation.y, d.translation.z, one,
+        )
+    }
+
+    /// Create a 3d rotation transform from an angle and an axis of any length, which
+    /// is normalized before use.
+    ///
+    /// Returns `None` if the axis has a length of zero.
+    ///
+    /// ```rust
+    /// use euclid::Angle;
+    /// use euclid::approxeq::ApproxEq;
+    /// use euclid::default::Transform3D;
+    ///
+    /// let r = Transform3D::try_rotation(0.0, 0.0, 5.0, Angle::degrees(90.0)).unwrap();
+    /// assert!(r.approx_eq(&Transform3D::rotation(0.0, 0.0, 1.0, Angle::degrees(90.0))));
+    /// assert!(Transform3D::try_rotation(0.0, 0.0, 0.0, Angle::degrees(90.0)).is_none());
+    /// ```
+    pub fn try_rotation(x: T, y: T, z: T, theta: Angle<T>) -> Option<Self>
+    where
+        T: Trig + ApproxEq<T>,
+    {
+        let axis: Vector3D<T, UnknownUnit> = vec3(x, y, z);
+        let axis = axis.try_normalize()?;
+
+        Some(Transform3D::rotation(axis.x, axis.y, axis.z, theta))
+    }
+}
+
+/// Returns the rotation whose matrix has the orthonormal, right-handed vectors `x`, `y`
+/// and `z` as rows.
+fn rotation_from_basis<T: Float>(
+    x: Vector3D<T, UnknownUnit>,
+    y: Vector3D<T, UnknownUnit>,
+    z: Vector3D<T, UnknownUnit>,
+) -> Rotation3D<T, UnknownUnit, UnknownUnit> {
+    // See `Rotation3D::to_transform` for the terms of the matrix. Each branch computes
+    // the quaternion multiplied by four times one of its components, the largest one
+    // to avoid losing precision, and normalizing it gets rid of the factor.
+    let one = T::one();
+    let trace = x.x + y.y + z.z;
+    let (i, j, k, r) = if trace > T::zero() {
+        (y.z - z.y, z.x - x.z, x.y - y.x, one + trace)
+    } else if x.x >= y.y && x.x >= z.z {
+        (one + x.x - y.y - z.z, x.y + y.x, z.x + x.z, y.z - z.y)
+    } else if y.y >= z.z {
+        (x.y + y.x, one + y.y - x.x - z.z, y.z + z.y, z.x - x.z)
+    } else {
+        (z.x + x.z, y.z + z.y, one + z.z - x.x - y.y, x.y - y.x)
+    };
+
+    Rotation3D::unit_quaternion(i, j, k, r)
+}
+
 impl<T: NumCast + Copy, Src, Dst> Transform3D<T, Src, Dst> {
     /// Cast from one numeric representation to another, preserving the units.
     #[inline]
@@ -1055,6 +1639,20 @@ impl<T: NumCast + Copy, Src, Dst> Transform3D<T, Src, Dst> {
             _ => None
         }
     }
+
+    // Convenience functions for common casts.
+
+    /// Cast into an `f32` transform.
+    #[inline]
+    pub fn to_f32(&self) -> Transform3D<f32, Src, Dst> {
+        self.cast()
+    }
+
+    /// Cast into an `f64` transform.
+    #[inline]
+    pub fn to_f64(&self) -> Transform3D<f64, Src, Dst> {
+        self.cast()
+    }
 }
 
 impl<T: ApproxEq<T>, Src, Dst> Transform3D<T, Src, Dst> {
@@ -1082,6 +1680,22 @@ impl<T: ApproxEq<T>, Src, Dst> Transform3D<T, Src, Dst> {
 }
 
 
+/// Interpolates the decomposed transforms, see `DecomposedTransform3D::lerp`, or the
+/// terms of the matrices if one of them can't be decomposed.
+impl<T: Float + ApproxEq<T>, Src, Dst> Interpolate<T> for Transform3D<T, Src, Dst> {
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        if let (Some(from), Some(to)) = (self.decompose(), other.decompose()) {
+            return Transform3D::recompose(&from.lerp(&to, t));
+        }
+
+        let mut array = self.to_array();
+        for (a, b) in array.iter_mut().zip(other.to_array().iter()) {
+            *a = *a + (*b - *a) * t;
+        }
+        Transform3D::from_array(array)
+    }
+}
+
 impl<T: ApproxEq<T>, Src, Dst> ApproxEq<T> for Transform3D<T, Src, Dst> {
     #[inline]
     fn approx_epsilon() -> T { T::approx_epsilon() }
@@ -1120,6 +1734,65 @@ where T: Copy + fmt::Debug +
     }
 }
 
+/// Displays the transform as a 4x4 grid, noting whether it is the identity or a
+/// translation. The precision, if any, applies to all terms.
+///
+/// ```rust
+/// use euclid::default::Transform3D;
+///
+/// let m = Transform3D::scale(2.0, 0.5, 1.0);
+/// assert_eq!(
+///     format!("{}", m),
+///     "[ 2,   0, 0, 0 ]\n[ 0, 0.5, 0, 0 ]\n[ 0,   0, 1, 0 ]\n[ 0,   0, 0, 1 ]",
+/// );
+/// ```
+impl<T, Src, Dst> fmt::Display for Transform3D<T, Src, Dst>
+where T: Copy + fmt::Display +
+         PartialEq +
+         One + Zero {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (zero, one): (T, T) = (Zero::zero(), One::one());
+        let note = if self.is_identity() {
+            Some("identity")
+        } else if self.m11 == one && self.m12 == zero && self.m13 == zero && self.m14 == zero &&
+                  self.m21 == zero && self.m22 == one && self.m23 == zero && self.m24 == zero &&
+                  self.m31 == zero && self.m32 == zero && self.m33 == one && self.m34 == zero &&
+                  self.m44 == one {
+            Some("translation")
+        } else {
+            None
+        };
+
+        write_grid(f, &self.to_array(), 4, note)
+    }
+}
+
+impl<T: Copy, Src, Dst> From<[[T; 4]; 4]> for Transform3D<T, Src, Dst> {
+    /// Creates a transform from its rows, see `Transform3D::from_arrays`.
+    fn from(array: [[T; 4]; 4]) -> Self {
+        Self::from_arrays(array)
+    }
+}
+
+impl<T: Copy, Src, Dst> From<Transform3D<T, Src, Dst>> for [[T; 4]; 4] {
+    /// Returns the rows of the transform, see `Transform3D::to_arrays`.
+    fn from(m: Transform3D<T, Src, Dst>) -> Self {
+        m.to_arrays()
+    }
+}
+
+/// Views the terms of the transform in the order of `Transform3D::to_array`, without
+/// copying.
+#[cfg(feature = "slice-cast")]
+#[allow(unsafe_code)]
+impl<T, Src, Dst> AsRef<[T; 16]> for Transform3D<T, Src, Dst> {
+    fn as_ref(&self) -> &[T; 16] {
+        // Safety: `Self` is `#[repr(C)]` and has the layout of `[T; 16]`, because its
+        // 16 terms are declared in this order and `_unit` is zero-sized.
+        unsafe { &*(self as *const Self as *const [T; 16]) }
+    }
+}
+
 #[cfg(feature = "mint")]
 impl<T, Src, Dst> From<mint::RowMatrix4<T>> for Transform3D<T, Src, Dst> {
     fn from(m: mint::RowMatrix4<T>) -> Self {
@@ -1156,6 +1829,25 @@ mod tests {
 
     type Mf32 = default::Transform3D<f32>;
 
+    #[cfg(feature = "quickcheck")]
+    quickcheck::quickcheck! {
+        fn prop_then_identity(m: default::Transform3D<f64>) -> bool {
+            let identity = default::Transform3D::identity();
+            !m.to_array().iter().all(|v| v.is_finite())
+                || (m.then(&identity) == m && identity.then(&m) == m)
+        }
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    pub fn test_quickcheck_shrink() {
+        use quickcheck::Arbitrary;
+
+        assert!(default::Transform3D::<f64>::identity().shrink().next().is_none());
+        let m = default::Transform3D::translation(0.0, 5.0, 0.0);
+        assert!(m.shrink().all(|s| s.m42.abs() < 5.0 && s.m11 == 1.0));
+    }
+
     // For convenience.
     fn rad(v: f32) -> Angle<f32> { Angle::radians(v) }
 
@@ -1176,6 +1868,15 @@ mod tests {
         assert_eq!(Mf32::translation(1.0, 2.0, 3.0).to_2d(), Transform2D::translation(1.0, 2.0));
     }
 
+    #[test]
+    pub fn test_cast() {
+        let m: default::Transform3D<f64> = default::Transform3D::translation(1.5, -2.0, 0.25);
+        assert_eq!(m.to_f32(), Mf32::translation(1.5, -2.0, 0.25));
+        assert_eq!(m.to_f32().to_f64(), m);
+        assert_eq!(m.cast::<i32>(), default::Transform3D::translation(1, -2, 0));
+        assert!(default::Transform3D::<f64>::scale(f64::INFINITY, 1.0, 1.0).try_cast::<i32>().is_none());
+    }
+
     #[test]
     pub fn test_rotation() {
         let r1 = Mf32::rotation(0.0, 0.0, 1.0, rad(FRAC_PI_2));
@@ -1233,6 +1934,84 @@ mod tests {
         assert!(result.approx_eq(&expected));
     }
 
+    #[test]
+    pub fn test_ortho_conventions() {
+        let (near, far) = (1.0f32, 9.0f32);
+        let check = |m: Mf32, z_sign: f32, near_depth: f32| {
+            let p = m.transform_point3d(point3(-2.0, 3.0, z_sign * near)).unwrap();
+            assert!(p.approx_eq(&point3(-1.0, 1.0, near_depth)));
+            let p = m.transform_point3d(point3(2.0, -3.0, z_sign * far)).unwrap();
+            assert!(p.approx_eq(&point3(1.0, -1.0, 1.0)));
+        };
+
+        check(Mf32::ortho(-2.0, 2.0, -3.0, 3.0, near, far), -1.0, -1.0);
+        check(Mf32::ortho_lh(-2.0, 2.0, -3.0, 3.0, near, far), 1.0, -1.0);
+        check(Mf32::ortho_zo(-2.0, 2.0, -3.0, 3.0, near, far), -1.0, 0.0);
+        check(Mf32::ortho_lh_zo(-2.0, 2.0, -3.0, 3.0, near, far), 1.0, 0.0);
+    }
+
+    #[test]
+    pub fn test_perspective_fov() {
+        let (near, far) = (1.0f32, 9.0f32);
+        let fov = rad(FRAC_PI_2);
+        // With a 90 degrees vertical field of view, the top edge of the frustum is at
+        // `y = distance`.
+        let check = |m: Mf32, z_sign: f32, near_depth: f32| {
+            let p = m.transform_point3d(point3(-2.0, 1.0, z_sign * near)).unwrap();
+            assert!(p.approx_eq(&point3(-1.0, 1.0, near_depth)));
+            let p = m.transform_point3d(point3(18.0, -9.0, z_sign * far)).unwrap();
+            assert!(p.approx_eq(&point3(1.0, -1.0, 1.0)));
+            assert!(m.transform_point3d(point3(0.0, 0.0, -z_sign)).is_none());
+        };
+
+        check(Mf32::perspective_fov(fov, 2.0, near, far), -1.0, -1.0);
+        check(Mf32::perspective_fov_lh(fov, 2.0, near, far), 1.0, -1.0);
+        check(Mf32::perspective_fov_zo(fov, 2.0, near, far), -1.0, 0.0);
+        check(Mf32::perspective_fov_lh_zo(fov, 2.0, near, far), 1.0, 0.0);
+    }
+
+    #[test]
+    pub fn test_flip_y() {
+        let flip = Mf32::flip_y(100.0);
+        assert_eq!(flip.then(&flip), Mf32::identity());
+        assert_eq!(flip.transform_point3d(point3(1.0, 20.0, 3.0)), Some(point3(1.0, 80.0, 3.0)));
+        assert_eq!(Mf32::flip_y(0.0), Mf32::scale(1.0, -1.0, 1.0));
+    }
+
+    #[test]
+    pub fn test_display() {
+        assert_eq!(
+            format!("{}", Mf32::identity()),
+            "[ 1, 0, 0, 0 ]\n[ 0, 1, 0, 0 ]\n[ 0, 0, 1, 0 ]\n[ 0, 0, 0, 1 ] (identity)",
+        );
+        assert_eq!(
+            format!("{:.2}", Mf32::translation(-1.0, 20.0, 0.5)),
+            "[  1.00,  0.00, 0.00, 0.00 ]\n\
+             [  0.00,  1.00, 0.00, 0.00 ]\n\
+             [  0.00,  0.00, 1.00, 0.00 ]\n\
+             [ -1.00, 20.00, 0.50, 1.00 ] (translation)",
+        );
+    }
+
+    #[test]
+    pub fn test_integer_transforms() {
+        type Mi32 = default::Transform3D<i32>;
+
+        let m = Mi32::translation(1, 2, 3)
+            .then_scale(2, 2, 1)
+            .then(&Mi32::new(
+                 0, 1, 0, 0,
+                -1, 0, 0, 0,
+                 0, 0, 1, 0,
+                 0, 0, 0, 1,
+            ));
+        assert_eq!(m.transform_point3d(point3(1, 1, 1)), Some(point3(-6, 4, 4)));
+        assert_eq!(m.transform_point2d(point2(0, 0)), Some(point2(-4, 2)));
+        assert_eq!(m.transform_vector3d(vec3(1, 0, 0)), vec3(0, 2, 0));
+        assert_eq!(Mi32::translation(4, 5, 6).inverse(), Some(Mi32::translation(-4, -5, -6)));
+        assert!(Mi32::IDENTITY.is_2d());
+    }
+
     #[test]
     pub fn test_is_2d() {
         assert!(Mf32::identity().is_2d());
@@ -1344,6 +2123,13 @@ mod tests {
         assert!(p1.approx_eq(&p2));
     }
 
+    #[test]
+    pub fn test_const_identity() {
+        const IDENTITY: Mf32 = Mf32::IDENTITY;
+        assert_eq!(IDENTITY, Mf32::identity());
+        assert_eq!(default::Transform3D::<f64>::IDENTITY, default::Transform3D::identity());
+    }
+
     #[test]
     pub fn test_is_identity() {
         let m1 = default::Transform3D::identity();
@@ -1352,6 +2138,134 @@ mod tests {
         assert!(!m2.is_identity());
     }
 
+    #[test]
+    pub fn test_extract_scale() {
+        let m = Mf32::scale(2.0, 3.0, 0.5)
+            .then_rotate(0.0, 0.0, 1.0, rad(FRAC_PI_2))
+            .then_translate(vec3(1.0, 2.0, 3.0));
+        assert!(m.extract_scale().approx_eq(&vec3(2.0, 3.0, 0.5)));
+        assert_eq!(Mf32::identity().extract_scale(), vec3(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    pub fn test_max_2d_scale() {
+        assert!((Mf32::identity().max_2d_scale() - 1.0).abs() < 1e-6);
+        assert!((Mf32::scale(2.0, -5.0, 10.0).max_2d_scale() - 5.0).abs() < 1e-5);
+
+        // A shear stretches the diagonal more than either axis.
+        let skew = Mf32::skew(rad(FRAC_PI_2 / 2.0), rad(0.0));
+        assert!((skew.max_2d_scale() - 1.618034).abs() < 1e-5);
+
+        // The scale bounds the length of all transformed unit vectors, including when
+        // the singular values are almost equal.
+        let transforms = [
+            skew,
+            Mf32::scale(1.0, 1.0001, 1.0).then_rotate(0.0, 0.0, 1.0, rad(0.3)),
+            Mf32::new_2d(0.1, -7.0, 3.5, 2.25, 10.0, 20.0),
+            Mf32::scale(1e-3, 1e-3, 1.0).then(&skew),
+        ];
+        for m in &transforms {
+            let scale = m.max_2d_scale();
+            for i in 0..256 {
+                let a = rad(PI * i as f32 / 128.0);
+                let v = m.transform_vector2d(vec2(a.radians.cos(), a.radians.sin()));
+                assert!(v.length() <= scale, "{:?} stretches {} past {}", m, v.length(), scale);
+            }
+        }
+
+        let mut m = Mf32::scale(4.0, 4.0, 1.0);
+        m.m44 = 2.0;
+        assert!((m.max_2d_scale() - 2.0).abs() < 1e-5);
+        let tilted = Mf32::rotation(0.0, 1.0, 0.0, rad(0.5)).then(&Mf32::perspective(100.0));
+        assert_eq!(tilted.max_2d_scale(), f32::INFINITY);
+        m.m24 = 0.5;
+        assert_eq!(m.max_2d_scale(), f32::INFINITY);
+    }
+
+    #[test]
+    pub fn test_nested_arrays() {
+        let rows = [
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ];
+        let m: Mf32 = rows.into();
+        assert_eq!(m, Mf32::from_arrays(rows));
+        assert_eq!((m.m12, m.m21, m.m43), (2.0, 5.0, 15.0));
+
+        let back: [[f32; 4]; 4] = m.into();
+        assert_eq!(back, rows);
+        assert_eq!(Mf32::translation(1.0, 2.0, 3.0).to_arrays()[3], [1.0, 2.0, 3.0, 1.0]);
+    }
+
+    #[cfg(feature = "slice-cast")]
+    #[test]
+    pub fn test_as_ref_array() {
+        let m = Mf32::translation(1.0, 2.0, 3.0).then_scale(4.0, 5.0, 6.0);
+        let terms: &[f32; 16] = m.as_ref();
+        assert_eq!(*terms, m.to_array());
+    }
+
+    #[test]
+    pub fn test_decompose() {
+        let eps = 1e-5;
+        let round_trip = |m: Mf32| {
+            let d = m.decompose().unwrap();
+            assert!(Mf32::recompose(&d).approx_eq_eps(&m, &eps), "{:?}", m);
+            d
+        };
+
+        // Rotations going through each way of computing the quaternion.
+        round_trip(Mf32::rotation(0.0, 0.0, 1.0, rad(0.5)));
+        round_trip(Mf32::rotation(1.0, 0.0, 0.0, rad(PI)));
+        round_trip(Mf32::rotation(0.0, 1.0, 0.0, rad(PI)));
+        round_trip(Mf32::rotation(0.0, 0.0, 1.0, rad(PI)));
+        round_trip(Mf32::rotation(0.6, 0.0, 0.8, rad(2.5)));
+
+        let m = Mf32::scale(2.0, 3.0, 0.5)
+            .then_rotate(0.0, 0.6, 0.8, rad(1.0))
+            .then_translate(vec3(1.0, 2.0, 3.0));
+        let d = round_trip(m);
+        assert_eq!(d.translation, vec3(1.0, 2.0, 3.0));
+        assert!(d.scale.approx_eq(&vec3(2.0, 3.0, 0.5)));
+        assert!(d.rotation.approx_eq_eps(&Rotation3D::around_axis(vec3(0.0, 0.6, 0.8), rad(1.0)), &eps));
+        assert!(d.shear.approx_eq(&vec3(0.0, 0.0, 0.0)));
+
+        let d = round_trip(Mf32::skew(rad(0.3), rad(-0.2)).then_rotate(0.8, 0.6, 0.0, rad(0.4)));
+        assert!(d.shear.x != 0.0);
+
+        // Mirroring gives a negative z scale.
+        let d = round_trip(Mf32::scale(-1.0, 1.0, 1.0));
+        assert!(d.scale.approx_eq(&vec3(1.0, 1.0, -1.0)));
+
+        assert!(Mf32::scale(1.0, 0.0, 1.0).decompose().is_none());
+        assert!(Mf32::perspective(100.0).decompose().is_none());
+
+        // Interpolating goes through intermediate rotations rather than shrinking.
+        let from = Mf32::identity().decompose().unwrap();
+        let to = Mf32::rotation(0.0, 0.0, 1.0, rad(FRAC_PI_2)).decompose().unwrap();
+        let half = Mf32::recompose(&from.lerp(&to, 0.5));
+        assert!(half.approx_eq(&Mf32::rotation(0.0, 0.0, 1.0, rad(FRAC_PI_2 / 2.0))));
+    }
+
+    #[test]
+    pub fn test_interpolate() {
+        use crate::interpolate::Interpolate;
+
+        let a = Mf32::scale(2.0, 2.0, 2.0);
+        let b = Mf32::rotation(1.0, 0.0, 0.0, rad(FRAC_PI_2)).then_translate(vec3(0.0, 4.0, 0.0));
+        let expected = Mf32::scale(1.5, 1.5, 1.5)
+            .then_rotate(1.0, 0.0, 0.0, rad(FRAC_PI_2 / 2.0))
+            .then_translate(vec3(0.0, 2.0, 0.0));
+        assert!(a.interpolate(&b, 0.5).approx_eq(&expected));
+
+        // Perspective transforms are interpolated term by term.
+        let p = Mf32::perspective(100.0);
+        let expected = Mf32::perspective(200.0);
+        assert!(p.interpolate(&Mf32::identity(), 0.5).approx_eq(&expected));
+    }
+
     #[test]
     pub fn test_transform_vector() {
         // Translation does not apply to vectors.
@@ -1367,6 +2281,75 @@ mod tests {
         assert_ne!(Some(v2.to_point()), m.transform_point2d(v2.to_point()));
     }
 
+    #[test]
+    pub fn test_normal_matrix() {
+        let m = Mf32::scale(2.0, 1.0, 1.0)
+            .then_rotate(0.0, 0.0, 1.0, rad(FRAC_PI_2))
+            .then_translate(vec3(5.0, 6.0, 7.0));
+        let n = m.to_normal_matrix().unwrap();
+        assert_eq!(n.m41, 0.0);
+        assert_eq!(n.m44, 1.0);
+
+        // The transformed normal of a surface stays perpendicular to its transformed tangent.
+        let tangent = vec3(1.0, 1.0, 0.0);
+        let normal = vec3(1.0, -1.0, 0.0);
+        let dot = m.transform_vector3d(tangent).dot(n.transform_vector3d(normal));
+        assert!(dot.approx_eq(&0.0));
+
+        assert!(Mf32::scale(1.0, 0.0, 1.0).to_normal_matrix().is_none());
+    }
+
+    #[test]
+    pub fn test_orthonormalize() {
+        let step = Mf32::rotation(0.6, 0.0, 0.8, rad(0.01));
+        let mut m = Mf32::identity();
+        for _ in 0..1000 {
+            m = m.then(&step);
+        }
+        // Introduce some obvious drift.
+        m.m21 += 0.01;
+        m.m33 *= 1.01;
+
+        let o = m.orthonormalize().unwrap();
+        let x: default::Vector3D<f32> = vec3(o.m11, o.m12, o.m13);
+        let y: default::Vector3D<f32> = vec3(o.m21, o.m22, o.m23);
+        let z: default::Vector3D<f32> = vec3(o.m31, o.m32, o.m33);
+        assert!(x.length().approx_eq(&1.0));
+        assert!(y.length().approx_eq(&1.0));
+        assert!(z.length().approx_eq(&1.0));
+        assert!(x.dot(y).approx_eq(&0.0));
+        assert!(x.dot(z).approx_eq(&0.0));
+        assert!(y.dot(z).approx_eq(&0.0));
+        assert!(o.approx_eq_eps(&Mf32::rotation(0.6, 0.0, 0.8, rad(10.0)), &0.02));
+
+        // Translation is preserved and scale is removed.
+        let m = Mf32::scale(2.0, 3.0, 4.0).then_translate(vec3(1.0, 2.0, 3.0));
+        assert!(m.orthonormalize().unwrap().approx_eq(&Mf32::translation(1.0, 2.0, 3.0)));
+
+        // Rank deficient blocks have no orthonormal form.
+        assert_eq!(Mf32::scale(1.0, 0.0, 1.0).orthonormalize(), None);
+        let mut m = Mf32::rotation(0.6, 0.0, 0.8, rad(1.0));
+        m.m31 = m.m11 + m.m21;
+        m.m32 = m.m12 + m.m22;
+        m.m33 = m.m13 + m.m23;
+        assert_eq!(m.orthonormalize(), None);
+    }
+
+    #[test]
+    pub fn test_try_rotation() {
+        let r = Mf32::try_rotation(3.0, 0.0, 4.0, rad(1.0)).unwrap();
+        assert!(r.approx_eq(&Mf32::rotation(0.6, 0.0, 0.8, rad(1.0))));
+        assert_eq!(Mf32::try_rotation(0.0, 0.0, 0.0, rad(1.0)), None);
+    }
+
+    #[test]
+    pub fn test_rotation_approximate_axis() {
+        // An axis with a few digits of precision is accepted.
+        let r = Mf32::rotation(0.577, 0.577, 0.577, rad(1.0));
+        let expected = Mf32::try_rotation(1.0, 1.0, 1.0, rad(1.0)).unwrap();
+        assert!(r.approx_eq_eps(&expected, &1e-2));
+    }
+
     #[test]
     pub fn test_is_backface_visible() {
         // backface is not visible for rotate-x 0 degree.
@@ -1402,6 +2385,30 @@ mod tests {
             m.transform_point3d_homogeneous(point3(1.0, 2.0, 4.0)),
             HomogeneousVector::new(8.0, 7.0, 4.0, 15.0),
         );
+        assert_eq!(
+            m.transform_homogeneous(HomogeneousVector::new(1.0, 2.0, 4.0, 1.0)),
+            m.transform_point3d_homogeneous(point3(1.0, 2.0, 4.0)),
+        );
+
+        // Chaining transforms before the divide gives the same result as composing them.
+        let n = Mf32::perspective(10.0).then_translate(vec3(1.0, 2.0, 3.0));
+        let v = n.transform_homogeneous(m.transform_point3d_homogeneous(point3(1.0, 2.0, 4.0)));
+        let p = m.then(&n).transform_point3d(point3(1.0, 2.0, 4.0));
+        assert!(v.to_point3d().unwrap().approx_eq(&p.unwrap()));
+    }
+
+    #[test]
+    pub fn test_transform_homogeneous_slice() {
+        let m = Mf32::perspective(10.0).then_translate(vec3(1.0, 2.0, 3.0));
+        let vectors = [
+            HomogeneousVector::new(1.0, 2.0, 4.0, 1.0),
+            HomogeneousVector::new(-1.0, 0.5, 0.0, 2.0),
+        ];
+        let mut transformed = vectors;
+        m.transform_homogeneous_slice(&mut transformed);
+        for (v, t) in vectors.iter().zip(transformed.iter()) {
+            assert_eq!(m.transform_homogeneous(*v), *t);
+        }
     }
 
     #[test]
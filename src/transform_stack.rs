@@ -0,0 +1,204 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A stack of nested transforms, as maintained while traversing a display list.
+//!
+//! ```rust
+//! use euclid::point2;
+//! use euclid::default::Transform3D;
+//! use euclid::transform_stack::TransformStack;
+//!
+//! enum WorldSpace {}
+//!
+//! let mut stack = TransformStack::<f32, WorldSpace>::new();
+//! stack.push(&Transform3D::translation(10.0, 0.0, 0.0));
+//! stack.push(&Transform3D::scale(2.0, 2.0, 1.0));
+//!
+//! // Points of the innermost space go through the scale, then the translation.
+//! assert_eq!(stack.current().transform_point2d(point2(1.0, 1.0)), Some(point2(12.0, 2.0)));
+//! let inverse = stack.inverse().unwrap();
+//! assert_eq!(inverse.transform_point2d(point2(12.0, 2.0)), Some(point2(1.0, 1.0)));
+//!
+//! stack.pop();
+//! assert_eq!(stack.depth(), 1);
+//! ```
+
+use crate::cached_transform::CachedTransform3D;
+use crate::num::{One, Zero};
+use crate::transform3d::Transform3D;
+use crate::UnknownUnit;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+struct Entry<T, Dst> {
+    transform: CachedTransform3D<T, UnknownUnit, Dst>,
+    flattened: Cell<Option<Transform3D<T, UnknownUnit, Dst>>>,
+}
+
+impl<T: Copy, Dst> Entry<T, Dst> {
+    fn new(transform: Transform3D<T, UnknownUnit, Dst>) -> Self {
+        Entry {
+            transform: CachedTransform3D::new(transform),
+            flattened: Cell::new(None),
+        }
+    }
+}
+
+/// A stack of transforms, each one being expressed in the space established by the
+/// transforms below it.
+///
+/// The stack keeps track of the accumulated transform from the innermost space to the
+/// `Dst` space, and caches its flattened version and its inverse, which are computed
+/// lazily and at most once per level.
+pub struct TransformStack<T, Dst> {
+    entries: Vec<Entry<T, Dst>>,
+}
+
+impl<T, Dst> TransformStack<T, Dst>
+where
+    T: Copy
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialOrd
+        + Zero
+        + One,
+{
+    /// Creates a stack containing only the identity transform.
+    pub fn new() -> Self {
+        Self::with_root(Transform3D::identity())
+    }
+
+    /// Creates a stack whose base level is the given transform.
+    pub fn with_root(root: Transform3D<T, UnknownUnit, Dst>) -> Self {
+        TransformStack {
+            entries: vec![Entry::new(root)],
+        }
+    }
+
+    /// Returns the number of transforms pushed on top of the base level.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.entries.len() - 1
+    }
+
+    /// Pushes a new level, `transform` mapping the new space to the current one.
+    pub fn push(&mut self, transform: &Transform3D<T, UnknownUnit, UnknownUnit>) {
+        let current = transform.then(self.current());
+        self.entries.push(Entry::new(current));
+    }
+
+    /// Removes the innermost level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is nothing to pop, that is if `depth()` is zero.
+    pub fn pop(&mut self) {
+        assert!(self.depth() > 0, "popped the base level of a transform stack");
+        self.entries.pop();
+    }
+
+    /// Applies a transform before the current one, without pushing a new level.
+    pub fn premultiply(&mut self, transform: &Transform3D<T, UnknownUnit, UnknownUnit>) {
+        let top = self.entries.last_mut().unwrap();
+        *top = Entry::new(transform.then(top.transform.get()));
+    }
+
+    /// Returns the accumulated transform from the innermost space to `Dst`.
+    #[inline]
+    pub fn current(&self) -> &Transform3D<T, UnknownUnit, Dst> {
+        self.entries.last().unwrap().transform.get()
+    }
+
+    /// Returns the current transform, flattened with `Transform3D::project_to_2d`.
+    pub fn flattened(&self) -> Transform3D<T, UnknownUnit, Dst> {
+        let top = self.entries.last().unwrap();
+        if let Some(flattened) = top.flattened.get() {
+            return flattened;
+        }
+
+        let flattened = top.transform.get().project_to_2d();
+        top.flattened.set(Some(flattened));
+
+        flattened
+    }
+
+    /// Returns the inverse of the current transform, or `None` if it is not invertible.
+    pub fn inverse(&self) -> Option<Transform3D<T, Dst, UnknownUnit>> {
+        self.entries.last().unwrap().transform.inverse()
+    }
+}
+
+impl<T, Dst> Default for TransformStack<T, Dst>
+where
+    T: Copy
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + PartialOrd
+        + Zero
+        + One,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approxeq::ApproxEq;
+    use crate::{point2, point3, Angle};
+
+    type Mf32 = Transform3D<f32, UnknownUnit, UnknownUnit>;
+
+    #[test]
+    fn test_push_pop() {
+        let mut stack: TransformStack<f32, UnknownUnit> = TransformStack::new();
+        stack.push(&Mf32::translation(5.0, 5.0, 0.0));
+        stack.push(&Mf32::rotation(1.0, 0.0, 0.0, Angle::degrees(60.0)));
+        assert_eq!(stack.depth(), 2);
+
+        // Rotating around the x axis by 60 degrees halves the height once flattened.
+        let flattened = stack.flattened();
+        let p = flattened.transform_point2d(point2(2.0, 2.0)).unwrap();
+        assert!(p.approx_eq(&point2(7.0, 6.0)));
+        assert_eq!(stack.flattened(), flattened);
+
+        let q = stack.current().transform_point3d(point3(2.0, 2.0, 0.0)).unwrap();
+        let back = stack.inverse().unwrap().transform_point3d(q).unwrap();
+        assert!(back.approx_eq(&point3(2.0, 2.0, 0.0)));
+
+        stack.pop();
+        assert_eq!(stack.current(), &Mf32::translation(5.0, 5.0, 0.0));
+        assert_eq!(stack.inverse(), Some(Mf32::translation(-5.0, -5.0, 0.0)));
+
+        // Premultiplying replaces the cached values.
+        stack.premultiply(&Mf32::scale(2.0, 2.0, 2.0));
+        assert_eq!(stack.depth(), 1);
+        assert_eq!(stack.current().transform_point2d(point2(1.0, 1.0)), Some(point2(7.0, 7.0)));
+        assert_eq!(stack.inverse().unwrap().transform_point2d(point2(7.0, 7.0)), Some(point2(1.0, 1.0)));
+
+        stack.push(&Mf32::scale(0.0, 1.0, 1.0));
+        assert_eq!(stack.inverse(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pop_base() {
+        let mut stack: TransformStack<f32, UnknownUnit> = TransformStack::new();
+        stack.pop();
+    }
+}
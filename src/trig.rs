@@ -7,7 +7,14 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(any(test, feature = "deterministic-trig"))]
+mod soft;
+
 /// Trait for basic trigonometry functions, so they can be used on generic numeric types
+///
+/// With the `deterministic-trig` feature, `sin`, `cos` and `tan` of `f32` and `f64` are
+/// computed in software and give bit-identical results on every platform, instead of
+/// depending on the system's math library.
 pub trait Trig {
     fn sin(self) -> Self;
     fn cos(self) -> Self;
@@ -17,20 +24,44 @@ pub trait Trig {
     fn radians_to_degrees(rad: Self) -> Self;
 }
 
+/// Returns the sine and cosine of `x`, like `Float::sin_cos`.
+///
+/// With the `deterministic-trig` feature, they are computed in software in `f64`, like the
+/// `Trig` implementations of `f32` and `f64`.
+#[inline]
+pub(crate) fn sin_cos<T: num_traits::Float>(x: T) -> (T, T) {
+    #[cfg(feature = "deterministic-trig")]
+    if let Some(x) = x.to_f64() {
+        let (sin, cos) = soft::sin_cos(x);
+        return (T::from(sin).unwrap(), T::from(cos).unwrap());
+    }
+
+    x.sin_cos()
+}
+
 macro_rules! trig {
     ($ty:ident) => {
         impl Trig for $ty {
             #[inline]
             fn sin(self) -> $ty {
-                num_traits::Float::sin(self)
+                #[cfg(feature = "deterministic-trig")]
+                return soft::sin(self as f64) as $ty;
+                #[cfg(not(feature = "deterministic-trig"))]
+                return num_traits::Float::sin(self);
             }
             #[inline]
             fn cos(self) -> $ty {
-                num_traits::Float::cos(self)
+                #[cfg(feature = "deterministic-trig")]
+                return soft::cos(self as f64) as $ty;
+                #[cfg(not(feature = "deterministic-trig"))]
+                return num_traits::Float::cos(self);
             }
             #[inline]
             fn tan(self) -> $ty {
-                num_traits::Float::tan(self)
+                #[cfg(feature = "deterministic-trig")]
+                return soft::tan(self as f64) as $ty;
+                #[cfg(not(feature = "deterministic-trig"))]
+                return num_traits::Float::tan(self);
             }
 
             /// A slightly faster approximation of `atan2`.
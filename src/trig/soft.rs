@@ -0,0 +1,145 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Software implementations of `sin`, `cos` and `tan`.
+//!
+//! They only use the basic IEEE 754 operations, which are correctly rounded, so their
+//! results are the same on every platform. The polynomial kernels are the ones of
+//! fdlibm, and the argument reduction is a simple Cody-Waite reduction which is
+//! accurate for the moderate angles used by transforms.
+//!
+//! Arguments larger than `MAX_REDUCED` in magnitude are first reduced modulo the `f64`
+//! closest to 2π. The remainder is exact, so the results stay deterministic and within
+//! `[-1, 1]`, but they lose accuracy as the magnitude grows: the error is about
+//! `|x| * 4e-17`.
+
+#![allow(clippy::excessive_precision)]
+// Only the tests use this module when the feature is disabled.
+#![cfg_attr(not(feature = "deterministic-trig"), allow(dead_code))]
+
+use core::f64::consts::{FRAC_2_PI, PI};
+
+// pi / 2 split in two parts, the first one having its low bits cleared so that
+// multiplying it by the quadrant number is exact.
+const PIO2_HI: f64 = 1.57079632673412561417e+00;
+const PIO2_LO: f64 = 6.07710050650619224932e-11;
+
+// Below this magnitude the quadrant number fits in 20 bits, so `n * PIO2_HI` is exact.
+const MAX_REDUCED: f64 = 1048576.0 * core::f64::consts::FRAC_PI_2;
+
+const S1: f64 = -1.66666666666666324348e-01;
+const S2: f64 = 8.33333333332248946124e-03;
+const S3: f64 = -1.98412698298579493134e-04;
+const S4: f64 = 2.75573137070700676789e-06;
+const S5: f64 = -2.50507602534068634195e-08;
+const S6: f64 = 1.58969099521155010221e-10;
+
+const C1: f64 = 4.16666666666666019037e-02;
+const C2: f64 = -1.38888888888741095749e-03;
+const C3: f64 = 2.48015872894767294178e-05;
+const C4: f64 = -2.75573143513906633035e-07;
+const C5: f64 = 2.08757232129817482790e-09;
+const C6: f64 = -1.13596475577881948265e-11;
+
+/// Sine on `[-pi/4, pi/4]`.
+fn kernel_sin(x: f64) -> f64 {
+    let z = x * x;
+    x + x * z * (S1 + z * (S2 + z * (S3 + z * (S4 + z * (S5 + z * S6)))))
+}
+
+/// Cosine on `[-pi/4, pi/4]`.
+fn kernel_cos(x: f64) -> f64 {
+    let z = x * x;
+    1.0 - 0.5 * z + z * z * (C1 + z * (C2 + z * (C3 + z * (C4 + z * (C5 + z * C6)))))
+}
+
+/// Returns the sine and cosine of `x`.
+pub fn sin_cos(x: f64) -> (f64, f64) {
+    if !x.is_finite() {
+        return (f64::NAN, f64::NAN);
+    }
+
+    let x = if x.abs() > MAX_REDUCED { x % (2.0 * PI) } else { x };
+
+    // Round to the nearest quadrant, halves away from zero.
+    let half = if x < 0.0 { -0.5 } else { 0.5 };
+    let n = (x * FRAC_2_PI + half) as i64;
+    let nf = n as f64;
+    let r = (x - nf * PIO2_HI) - nf * PIO2_LO;
+
+    let (s, c) = (kernel_sin(r), kernel_cos(r));
+    match n & 3 {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+
+/// Returns the sine of `x`.
+#[inline]
+pub fn sin(x: f64) -> f64 {
+    sin_cos(x).0
+}
+
+/// Returns the cosine of `x`.
+#[inline]
+pub fn cos(x: f64) -> f64 {
+    sin_cos(x).1
+}
+
+/// Returns the tangent of `x`.
+#[inline]
+pub fn tan(x: f64) -> f64 {
+    let (s, c) = sin_cos(x);
+    s / c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sin_cos, tan};
+    use core::f64::consts::{FRAC_PI_2, FRAC_PI_6, PI};
+
+    #[test]
+    fn test_against_std() {
+        for i in -2000..2000 {
+            let x = i as f64 * 0.01;
+            let (s, c) = sin_cos(x);
+            assert!((s - x.sin()).abs() < 1e-14, "sin({})", x);
+            assert!((c - x.cos()).abs() < 1e-14, "cos({})", x);
+        }
+    }
+
+    #[test]
+    fn test_special_values() {
+        assert_eq!(sin_cos(0.0), (0.0, 1.0));
+        assert!((sin_cos(FRAC_PI_6).0 - 0.5).abs() < 1e-16);
+        assert!((sin_cos(FRAC_PI_2).0 - 1.0).abs() < 1e-16);
+        assert!((sin_cos(PI).1 + 1.0).abs() < 1e-16);
+        assert!((tan(PI / 4.0) - 1.0).abs() < 1e-15);
+        assert!(sin_cos(f64::NAN).0.is_nan());
+        assert!(sin_cos(f64::INFINITY).1.is_nan());
+    }
+
+    #[test]
+    fn test_large_arguments() {
+        for &x in &[1.0e7, -3.0e9, 1.0e12] {
+            let (s, c) = sin_cos(x);
+            assert!((s - x.sin()).abs() < 1e-4, "sin({})", x);
+            assert!((c - x.cos()).abs() < 1e-4, "cos({})", x);
+        }
+
+        // The quadrant number would overflow an i64 without the first reduction.
+        for &x in &[1.0e19, -1.0e19, 1.0e300, f64::MAX, f64::MIN] {
+            let (s, c) = sin_cos(x);
+            assert!(s.abs() <= 1.0 && c.abs() <= 1.0, "sin_cos({})", x);
+            assert!((s * s + c * c - 1.0).abs() < 1e-15, "sin_cos({})", x);
+        }
+    }
+}
@@ -0,0 +1,291 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Validation of untrusted values, for example values deserialized from IPC messages.
+//!
+//! Deserialization accepts any value of the scalar type, including NaN, infinities and
+//! negative sizes, which most of the geometry code doesn't expect. The [`Validate`]
+//! trait checks these invariants once, where the values are received.
+//!
+//! ```rust
+//! use euclid::default::{Rect, Transform2D};
+//! use euclid::validate::{Validate, ValidationErrorKind};
+//! use euclid::{point2, size2};
+//!
+//! let r: Rect<f32> = Rect::new(point2(0.0, 0.0), size2(10.0, -1.0));
+//! let error = r.validate().unwrap_err();
+//! assert_eq!(error.component(), "size.height");
+//! assert_eq!(error.kind(), ValidationErrorKind::Negative);
+//!
+//! let m: Transform2D<f32> = Transform2D::new(1.0, 0.0, 0.0, 1.0, f32::NAN, 0.0);
+//! assert_eq!(m.validate().unwrap_err().component(), "m31");
+//! ```
+
+use crate::{
+    Angle, Box2D, Box3D, Length, Point2D, Point3D, Rect, Scale, SideOffsets2D, Size2D,
+    Size3D, Transform2D, Transform3D, Vector2D, Vector3D,
+};
+use core::fmt;
+use num_traits::Float;
+
+/// The reason why a value failed to validate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValidationErrorKind {
+    /// The component is NaN or infinite.
+    NotFinite,
+    /// The component is negative, for example the width of a size.
+    Negative,
+}
+
+/// Error returned by [`Validate::validate`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ValidationError {
+    component: &'static str,
+    kind: ValidationErrorKind,
+}
+
+impl ValidationError {
+    /// The name of the first invalid component, for example `"x"` or `"size.height"`.
+    pub fn component(&self) -> &'static str {
+        self.component
+    }
+
+    /// The reason why the component is invalid.
+    pub fn kind(&self) -> ValidationErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ValidationErrorKind::NotFinite => {
+                write!(f, "component `{}` is not finite", self.component)
+            }
+            ValidationErrorKind::Negative => {
+                write!(f, "component `{}` is negative", self.component)
+            }
+        }
+    }
+}
+
+/// Checks the invariants that the geometry code expects of a value.
+///
+/// All components must be finite. Sizes, and the sizes of rectangles and boxes, must not
+/// be negative.
+pub trait Validate {
+    /// Returns an error describing the first invalid component, if any.
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+fn finite<T: Float>(value: T, component: &'static str) -> Result<(), ValidationError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(ValidationError { component, kind: ValidationErrorKind::NotFinite })
+    }
+}
+
+fn non_negative<T: Float>(value: T, component: &'static str) -> Result<(), ValidationError> {
+    finite(value, component)?;
+    if value < T::zero() {
+        return Err(ValidationError { component, kind: ValidationErrorKind::Negative });
+    }
+
+    Ok(())
+}
+
+/// Validates the maximum coordinate of a box, which must not be less than `min`.
+///
+/// The coordinates are compared directly, since their difference may overflow.
+fn not_below<T: Float>(max: T, min: T, component: &'static str) -> Result<(), ValidationError> {
+    finite(max, component)?;
+    if max < min {
+        return Err(ValidationError { component, kind: ValidationErrorKind::Negative });
+    }
+
+    Ok(())
+}
+
+impl<T: Float, U> Validate for Length<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.0, "0")
+    }
+}
+
+impl<T: Float, U> Validate for Point2D<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.x, "x")?;
+        finite(self.y, "y")
+    }
+}
+
+impl<T: Float, U> Validate for Point3D<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.x, "x")?;
+        finite(self.y, "y")?;
+        finite(self.z, "z")
+    }
+}
+
+impl<T: Float, U> Validate for Vector2D<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.x, "x")?;
+        finite(self.y, "y")
+    }
+}
+
+impl<T: Float, U> Validate for Vector3D<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.x, "x")?;
+        finite(self.y, "y")?;
+        finite(self.z, "z")
+    }
+}
+
+impl<T: Float, U> Validate for Size2D<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_negative(self.width, "width")?;
+        non_negative(self.height, "height")
+    }
+}
+
+impl<T: Float, U> Validate for Size3D<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        non_negative(self.width, "width")?;
+        non_negative(self.height, "height")?;
+        non_negative(self.depth, "depth")
+    }
+}
+
+impl<T: Float, U> Validate for Rect<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.origin.x, "origin.x")?;
+        finite(self.origin.y, "origin.y")?;
+        non_negative(self.size.width, "size.width")?;
+        non_negative(self.size.height, "size.height")
+    }
+}
+
+/// The maximum corner may not be less than the minimum corner, which is reported as a
+/// negative extent on the `max` component.
+impl<T: Float, U> Validate for Box2D<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.min.x, "min.x")?;
+        finite(self.min.y, "min.y")?;
+        not_below(self.max.x, self.min.x, "max.x")?;
+        not_below(self.max.y, self.min.y, "max.y")
+    }
+}
+
+/// The maximum corner may not be less than the minimum corner, which is reported as a
+/// negative extent on the `max` component.
+impl<T: Float, U> Validate for Box3D<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.min.x, "min.x")?;
+        finite(self.min.y, "min.y")?;
+        finite(self.min.z, "min.z")?;
+        not_below(self.max.x, self.min.x, "max.x")?;
+        not_below(self.max.y, self.min.y, "max.y")?;
+        not_below(self.max.z, self.min.z, "max.z")
+    }
+}
+
+impl<T: Float, U> Validate for SideOffsets2D<T, U> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.top, "top")?;
+        finite(self.right, "right")?;
+        finite(self.bottom, "bottom")?;
+        finite(self.left, "left")
+    }
+}
+
+impl<T: Float> Validate for Angle<T> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.radians, "radians")
+    }
+}
+
+impl<T: Float, Src, Dst> Validate for Scale<T, Src, Dst> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        finite(self.0, "0")
+    }
+}
+
+impl<T: Float, Src, Dst> Validate for Transform2D<T, Src, Dst> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        const NAMES: [&str; 6] = ["m11", "m12", "m21", "m22", "m31", "m32"];
+        for (&value, &name) in self.to_array().iter().zip(NAMES.iter()) {
+            finite(value, name)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Float, Src, Dst> Validate for Transform3D<T, Src, Dst> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        const NAMES: [&str; 16] = [
+            "m11", "m12", "m13", "m14",
+            "m21", "m22", "m23", "m24",
+            "m31", "m32", "m33", "m34",
+            "m41", "m42", "m43", "m44",
+        ];
+        for (&value, &name) in self.to_array().iter().zip(NAMES.iter()) {
+            finite(value, name)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default;
+    use crate::{point2, point3, size2, vec3};
+
+    fn kind<V: Validate>(value: V) -> Option<(&'static str, ValidationErrorKind)> {
+        value.validate().err().map(|e| (e.component(), e.kind()))
+    }
+
+    #[test]
+    fn test_validate() {
+        use ValidationErrorKind::*;
+
+        assert_eq!(kind(point2::<f32, ()>(1.0, 2.0)), None);
+        assert_eq!(kind(point2::<f32, ()>(1.0, f32::INFINITY)), Some(("y", NotFinite)));
+        assert_eq!(kind(vec3::<f64, ()>(f64::NAN, 0.0, 0.0)), Some(("x", NotFinite)));
+
+        assert_eq!(kind(size2::<f32, ()>(0.0, 0.0)), None);
+        assert_eq!(kind(size2::<f32, ()>(-1.0, 0.0)), Some(("width", Negative)));
+        assert_eq!(kind(size2::<f32, ()>(f32::NAN, -1.0)), Some(("width", NotFinite)));
+
+        let r: default::Rect<f32> = Rect::new(point2(-5.0, f32::NEG_INFINITY), size2(1.0, 1.0));
+        assert_eq!(kind(r), Some(("origin.y", NotFinite)));
+
+        let b: default::Box2D<f32> = Box2D::new(point2(0.0, 0.0), point2(1.0, -1.0));
+        assert_eq!(kind(b), Some(("max.y", Negative)));
+        assert_eq!(kind(default::Box2D::<f32>::zero()), None);
+
+        // The extent of the box overflows but the box is valid.
+        let b: default::Box2D<f32> = Box2D::new(point2(-f32::MAX, 0.0), point2(f32::MAX, 1.0));
+        assert_eq!(kind(b), None);
+        let b: default::Box3D<f32> =
+            Box3D::new(point3(0.0, -f32::MAX, 0.0), point3(1.0, f32::MAX, f32::NAN));
+        assert_eq!(kind(b), Some(("max.z", NotFinite)));
+
+        let m: default::Transform3D<f32> = Transform3D::translation(1.0, 2.0, f32::NAN);
+        assert_eq!(kind(m), Some(("m43", NotFinite)));
+        assert_eq!(kind(default::Transform2D::<f64>::identity()), None);
+
+        let error = size2::<f32, ()>(-1.0, 0.0).validate().unwrap_err();
+        assert_eq!(format!("{}", error), "component `width` is negative");
+    }
+}
@@ -10,6 +10,7 @@
 use super::UnknownUnit;
 use crate::approxeq::ApproxEq;
 use crate::approxord::{max, min};
+use crate::interpolate::Interpolate;
 use crate::length::Length;
 use crate::num::*;
 use crate::point::{point2, point3, Point2D, Point3D};
@@ -103,6 +104,16 @@ where
     }
 }
 
+#[cfg(feature = "rand")]
+impl<T, U> rand::distributions::Distribution<Vector2D<T, U>> for rand::distributions::Standard
+where
+    rand::distributions::Standard: rand::distributions::Distribution<T>,
+{
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Vector2D<T, U> {
+        Vector2D::new(rng.gen(), rng.gen())
+    }
+}
+
 impl<T: Eq, U> Eq for Vector2D<T, U> {}
 
 impl<T: PartialEq, U> PartialEq for Vector2D<T, U> {
@@ -552,6 +563,16 @@ where
     }
 }
 
+impl<T, U> Interpolate<T> for Vector2D<T, U>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    #[inline]
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
 impl<T: PartialOrd, U> Vector2D<T, U> {
     /// Returns the vector each component of which are minimum of this vector and another.
     #[inline]
@@ -639,6 +660,16 @@ impl<T: NumCast + Copy, U> Vector2D<T, U> {
         }
     }
 
+    /// Fallible cast from one numeric representation to another, preserving the units.
+    ///
+    /// Unlike `try_cast`, the error names the first component that could not be cast.
+    pub fn try_cast_detailed<NewT: NumCast>(self) -> Result<Vector2D<NewT, U>, CastError> {
+        Ok(vec2(
+            cast_component(self.x, "x")?,
+            cast_component(self.y, "y")?,
+        ))
+    }
+
     // Convenience functions for common casts.
 
     /// Cast into an `f32` vector.
@@ -1402,6 +1433,16 @@ where
     }
 }
 
+impl<T, U> Interpolate<T> for Vector3D<T, U>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    #[inline]
+    fn interpolate(&self, other: &Self, t: T) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
 impl<T: PartialOrd, U> Vector3D<T, U> {
     /// Returns the vector each component of which are minimum of this vector and another.
     #[inline]
@@ -1774,6 +1815,7 @@ impl<T, U> From<(T, T, T)> for Vector3D<T, U> {
 
 /// A 2d vector of booleans, useful for component-wise logic operations.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoolVector2D {
     pub x: bool,
     pub y: bool,
@@ -1781,6 +1823,7 @@ pub struct BoolVector2D {
 
 /// A 3d vector of booleans, useful for component-wise logic operations.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoolVector3D {
     pub x: bool,
     pub y: bool,